@@ -0,0 +1,76 @@
+use cairo_vm::{
+    hint_processor::{
+        builtin_hint_processor::secp::ec_utils::{ec_mul_inner, ec_mul_inner_cached},
+        hint_processor_definition::HintReference,
+    },
+    serde::deserialize_program::ApTracking,
+    stdlib::collections::HashMap,
+    types::{exec_scope::ExecutionScopes, relocatable::Relocatable},
+    vm::vm_core::VirtualMachine,
+    Felt252,
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+#[cfg(feature = "with_mimalloc")]
+use mimalloc::MiMalloc;
+
+#[cfg(feature = "with_mimalloc")]
+#[global_allocator]
+static ALLOC: MiMalloc = MiMalloc;
+
+// Number of iterations of the ec_mul_inner loop for a 252-bit scalar
+const N_BITS: usize = 252;
+
+// Builds a vm holding the scalar of each iteration of the loop at [fp - 1] of its own frame
+fn setup() -> VirtualMachine {
+    let mut vm = VirtualMachine::new(false);
+    vm.add_memory_segment();
+    let segment = vm.add_memory_segment();
+    let mut scalar = Felt252::MAX;
+    let half = Felt252::TWO.inverse().unwrap();
+    for i in 0..N_BITS {
+        vm.insert_value((segment + 2 * i).unwrap(), scalar).unwrap();
+        let bit = Felt252::from(scalar.to_le_digits()[0] & 1);
+        scalar = (scalar - bit) * half;
+    }
+    vm
+}
+
+fn run_loop(vm: &mut VirtualMachine, cached: bool) {
+    let ids_data = HashMap::from([("scalar".to_string(), HintReference::new_simple(-1))]);
+    let ap_tracking = ApTracking::new();
+    let mut exec_scopes = ExecutionScopes::new();
+    for i in 0..N_BITS {
+        vm.set_fp(2 * i + 1);
+        vm.set_ap(2 * i + 1);
+        if cached {
+            ec_mul_inner_cached(vm, &mut exec_scopes, &ids_data, &ap_tracking).unwrap();
+        } else {
+            ec_mul_inner(vm, &ids_data, &ap_tracking).unwrap();
+        }
+    }
+    black_box(
+        vm.get_integer(Relocatable::from((1, 2 * N_BITS - 1)))
+            .unwrap(),
+    );
+}
+
+fn ec_mul_inner_loop(c: &mut Criterion) {
+    c.bench_function("ec_mul_inner 252-bit scalar", |b| {
+        b.iter_batched(
+            setup,
+            |mut vm| run_loop(&mut vm, false),
+            BatchSize::SmallInput,
+        )
+    });
+    c.bench_function("ec_mul_inner cached 252-bit scalar", |b| {
+        b.iter_batched(
+            setup,
+            |mut vm| run_loop(&mut vm, true),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(ec_mul, ec_mul_inner_loop);
+criterion_main!(ec_mul);
@@ -0,0 +1,411 @@
+#![deny(warnings)]
+#![forbid(unsafe_code)]
+//! Differential testing harness against the Python VM.
+//!
+//! Runs a compiled program both in cairo-vm and in cairo-lang's `cairo-run` (whose path is taken
+//! from the `CAIRO_RUN` environment variable, defaulting to `cairo-run`), and compares the
+//! resulting relocated trace and memory byte-for-byte. When they differ, the first divergent
+//! step/cell is reported along with the decoded instruction and the registers on both sides.
+use bincode::enc::write::Writer;
+use cairo_vm::cairo_run::{self, write_encoded_memory, write_encoded_trace, EncodeTraceError};
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+use cairo_vm::vm::decoding::decoder::decode_instruction;
+use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
+use cairo_vm::vm::errors::trace_errors::TraceError;
+use cairo_vm::Felt252;
+use clap::{Parser, ValueHint};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+use thiserror::Error;
+
+/// Environment variable holding the path to the Python VM's `cairo-run` executable
+const CAIRO_RUN_ENV: &str = "CAIRO_RUN";
+const DEFAULT_CAIRO_RUN: &str = "cairo-run";
+
+// Each encoded trace entry holds ap, fp & pc as 8-byte little endian words
+const TRACE_ENTRY_SIZE: usize = 3 * 8;
+// Each encoded memory cell holds an 8-byte address and a 32-byte value
+const MEMORY_CELL_SIZE: usize = 8 + 32;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(value_parser, value_hint=ValueHint::FilePath)]
+    filename: PathBuf,
+    #[clap(long = "layout", default_value = "plain")]
+    layout: String,
+    #[structopt(long = "proof_mode")]
+    proof_mode: bool,
+}
+
+#[derive(Debug, Error)]
+enum Error {
+    #[error("Invalid arguments")]
+    Cli(#[from] clap::Error),
+    #[error("Failed to interact with the file system")]
+    IO(#[from] std::io::Error),
+    #[error("The cairo program execution failed")]
+    Runner(#[from] CairoRunError),
+    #[error(transparent)]
+    EncodeTrace(#[from] EncodeTraceError),
+    #[error(transparent)]
+    Trace(#[from] TraceError),
+    #[error("Python VM not found at {0}, set the {CAIRO_RUN_ENV} environment variable")]
+    PythonVmNotFound(String),
+    #[error("Python VM failed to run the program:\n{0}")]
+    PythonVm(String),
+}
+
+/// Encoded trace and memory of a single run, in the format written by `--trace_file` and `--memory_file`
+#[derive(Debug)]
+struct RunArtifacts {
+    trace: Vec<u8>,
+    memory: Vec<u8>,
+}
+
+#[derive(Default)]
+struct VecWriter(Vec<u8>);
+
+impl Writer for VecWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), bincode::error::EncodeError> {
+        self.0.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Registers {
+    pc: u64,
+    ap: u64,
+    fp: u64,
+}
+
+fn decode_registers(trace: &[u8], step: usize) -> Option<Registers> {
+    let entry = trace.chunks_exact(TRACE_ENTRY_SIZE).nth(step)?;
+    let word = |i: usize| u64::from_le_bytes(entry[i * 8..(i + 1) * 8].try_into().unwrap());
+    Some(Registers {
+        ap: word(0),
+        fp: word(1),
+        pc: word(2),
+    })
+}
+
+/// Returns the index of the first trace entry that differs between both traces
+fn first_trace_divergence(ours: &[u8], theirs: &[u8]) -> Option<usize> {
+    let n_steps = ours.len().max(theirs.len()).div_ceil(TRACE_ENTRY_SIZE);
+    (0..n_steps).find(|step| decode_registers(ours, *step) != decode_registers(theirs, *step))
+}
+
+fn decode_memory(memory: &[u8]) -> Vec<(u64, Felt252)> {
+    memory
+        .chunks_exact(MEMORY_CELL_SIZE)
+        .map(|cell| {
+            let addr = u64::from_le_bytes(cell[..8].try_into().unwrap());
+            let value = Felt252::from_bytes_le_slice(&cell[8..]);
+            (addr, value)
+        })
+        .collect()
+}
+
+fn felt_to_u64(value: &Felt252) -> Option<u64> {
+    let bytes = value.to_bytes_le();
+    bytes[8..]
+        .iter()
+        .all(|b| *b == 0)
+        .then(|| u64::from_le_bytes(bytes[..8].try_into().unwrap()))
+}
+
+/// Returns the first address whose value differs between both memories, with the value on each side
+fn first_memory_divergence(
+    ours: &[u8],
+    theirs: &[u8],
+) -> Option<(u64, Option<Felt252>, Option<Felt252>)> {
+    let (ours, theirs) = (decode_memory(ours), decode_memory(theirs));
+    let lookup = |cells: &[(u64, Felt252)], addr| {
+        cells
+            .binary_search_by_key(&addr, |(a, _)| *a)
+            .ok()
+            .map(|i| cells[i].1)
+    };
+    let mut addresses: Vec<u64> = ours.iter().chain(theirs.iter()).map(|(a, _)| *a).collect();
+    addresses.sort_unstable();
+    addresses.dedup();
+    addresses.into_iter().find_map(|addr| {
+        let (a, b) = (lookup(&ours, addr), lookup(&theirs, addr));
+        (a != b).then_some((addr, a, b))
+    })
+}
+
+fn run_cairo_vm(program: &Path, args: &Args) -> Result<RunArtifacts, Error> {
+    let cairo_run_config = cairo_run::CairoRunConfig {
+        trace_enabled: true,
+        relocate_mem: true,
+        layout: &args.layout,
+        proof_mode: args.proof_mode,
+        ..Default::default()
+    };
+    let mut hint_executor = BuiltinHintProcessor::new_empty();
+    let program_content = std::fs::read(program)?;
+    let (cairo_runner, _) =
+        cairo_run::cairo_run(&program_content, &cairo_run_config, &mut hint_executor)?;
+
+    let relocated_trace = cairo_runner
+        .relocated_trace
+        .as_ref()
+        .ok_or(TraceError::TraceNotRelocated)?;
+    let (mut trace_writer, mut memory_writer) = (VecWriter::default(), VecWriter::default());
+    write_encoded_trace(relocated_trace, &mut trace_writer)?;
+    write_encoded_memory(&cairo_runner.relocated_memory, &mut memory_writer)?;
+    Ok(RunArtifacts {
+        trace: trace_writer.0,
+        memory: memory_writer.0,
+    })
+}
+
+/// Directory the Python VM writes its trace and memory files to, removed when dropped
+struct OutputDir(PathBuf);
+
+impl OutputDir {
+    fn create(path: PathBuf) -> Result<Self, Error> {
+        std::fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for OutputDir {
+    fn drop(&mut self) {
+        // A leftover directory isn't worth failing the comparison over
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+fn python_vm_output_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("compare_vm_{}", std::process::id()))
+}
+
+fn run_python_vm(cairo_run: &str, program: &Path, args: &Args) -> Result<RunArtifacts, Error> {
+    let out_dir = OutputDir::create(python_vm_output_dir())?;
+    let (trace_path, memory_path) = (out_dir.0.join("trace"), out_dir.0.join("memory"));
+
+    let mut command = Command::new(cairo_run);
+    command
+        .arg(format!("--program={}", program.display()))
+        .arg(format!("--layout={}", args.layout))
+        .arg(format!("--trace_file={}", trace_path.display()))
+        .arg(format!("--memory_file={}", memory_path.display()));
+    if args.proof_mode {
+        command.arg("--proof_mode");
+    }
+    let output = command.output().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => Error::PythonVmNotFound(cairo_run.to_string()),
+        _ => Error::IO(e),
+    })?;
+    if !output.status.success() {
+        return Err(Error::PythonVm(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        ));
+    }
+
+    Ok(RunArtifacts {
+        trace: std::fs::read(&trace_path)?,
+        memory: std::fs::read(&memory_path)?,
+    })
+}
+
+fn describe_step(side: &str, trace: &[u8], memory: &[u8], step: usize) -> String {
+    let Some(registers) = decode_registers(trace, step) else {
+        return format!("{side}: <trace ended>");
+    };
+    let instruction = decode_memory(memory)
+        .into_iter()
+        .find(|(addr, _)| *addr == registers.pc)
+        .and_then(|(_, value)| felt_to_u64(&value))
+        .map(|encoded| match decode_instruction(encoded) {
+            Ok(instruction) => format!("{instruction:?}"),
+            Err(err) => format!("<{err}>"),
+        })
+        .unwrap_or_else(|| String::from("<unknown>"));
+    format!(
+        "{side}: pc={} ap={} fp={} instruction={instruction}",
+        registers.pc, registers.ap, registers.fp
+    )
+}
+
+/// Compares both runs, returning a report of the first divergences found (if any)
+fn compare(ours: &RunArtifacts, theirs: &RunArtifacts) -> Option<String> {
+    let mut report = Vec::new();
+    if ours.trace != theirs.trace {
+        if let Some(step) = first_trace_divergence(&ours.trace, &theirs.trace) {
+            report.push(format!("Traces diverge at step {step}"));
+            report.push(describe_step(
+                "  cairo-vm ",
+                &ours.trace,
+                &ours.memory,
+                step,
+            ));
+            report.push(describe_step(
+                "  python vm",
+                &theirs.trace,
+                &theirs.memory,
+                step,
+            ));
+        }
+    }
+    if ours.memory != theirs.memory {
+        if let Some((addr, a, b)) = first_memory_divergence(&ours.memory, &theirs.memory) {
+            let fmt = |v: Option<Felt252>| v.map_or(String::from("<unset>"), |v| v.to_string());
+            report.push(format!("Memories diverge at address {addr}"));
+            report.push(format!("  cairo-vm : {}", fmt(a)));
+            report.push(format!("  python vm: {}", fmt(b)));
+        }
+    }
+    (!report.is_empty()).then(|| report.join("\n"))
+}
+
+fn run(args: impl Iterator<Item = String>) -> Result<Option<String>, Error> {
+    let args = Args::try_parse_from(args)?;
+    let cairo_run = std::env::var(CAIRO_RUN_ENV).unwrap_or(DEFAULT_CAIRO_RUN.to_string());
+    let ours = run_cairo_vm(&args.filename, &args)?;
+    let theirs = run_python_vm(&cairo_run, &args.filename, &args)?;
+    Ok(compare(&ours, &theirs))
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args()) {
+        Ok(None) => {
+            println!("No discrepancies found");
+            ExitCode::SUCCESS
+        }
+        Ok(Some(report)) => {
+            println!("{report}");
+            ExitCode::FAILURE
+        }
+        Err(Error::Cli(err)) => err.exit(),
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_trace(entries: &[(u64, u64, u64)]) -> Vec<u8> {
+        entries
+            .iter()
+            .flat_map(|(pc, ap, fp)| [ap, fp, pc].map(|w| w.to_le_bytes()).concat())
+            .collect()
+    }
+
+    fn encode_memory(cells: &[(u64, u64)]) -> Vec<u8> {
+        cells
+            .iter()
+            .flat_map(|(addr, value)| {
+                [
+                    addr.to_le_bytes().to_vec(),
+                    Felt252::from(*value).to_bytes_le().to_vec(),
+                ]
+                .concat()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn equal_runs_have_no_divergence() {
+        let artifacts = RunArtifacts {
+            trace: encode_trace(&[(1, 5, 5), (3, 6, 5)]),
+            memory: encode_memory(&[(1, 10), (2, 20)]),
+        };
+        let copy = RunArtifacts {
+            trace: artifacts.trace.clone(),
+            memory: artifacts.memory.clone(),
+        };
+        assert_eq!(compare(&artifacts, &copy), None);
+    }
+
+    #[test]
+    fn reports_first_divergent_step_and_cell() {
+        let ours = RunArtifacts {
+            trace: encode_trace(&[(1, 5, 5), (3, 6, 5), (5, 7, 5)]),
+            memory: encode_memory(&[(1, 10), (2, 20), (3, 30)]),
+        };
+        let theirs = RunArtifacts {
+            trace: encode_trace(&[(1, 5, 5), (3, 7, 5), (5, 8, 5)]),
+            memory: encode_memory(&[(1, 10), (3, 31)]),
+        };
+        assert_eq!(first_trace_divergence(&ours.trace, &theirs.trace), Some(1));
+        assert_eq!(
+            first_memory_divergence(&ours.memory, &theirs.memory),
+            Some((2, Some(Felt252::from(20)), None))
+        );
+        let report = compare(&ours, &theirs).unwrap();
+        assert!(report.contains("Traces diverge at step 1"));
+        assert!(report.contains("pc=3 ap=6 fp=5"));
+        assert!(report.contains("pc=3 ap=7 fp=5"));
+        assert!(report.contains("Memories diverge at address 2"));
+    }
+
+    #[test]
+    fn shorter_trace_diverges_where_it_ends() {
+        let ours = encode_trace(&[(1, 5, 5), (3, 6, 5)]);
+        let theirs = encode_trace(&[(1, 5, 5)]);
+        assert_eq!(first_trace_divergence(&ours, &theirs), Some(1));
+    }
+
+    #[test]
+    fn report_decodes_the_divergent_instruction() {
+        // [ap] = 0; ap++
+        let instruction = 0x480680017fff8000;
+        let ours = RunArtifacts {
+            trace: encode_trace(&[(1, 5, 5), (3, 6, 5)]),
+            memory: encode_memory(&[(1, 10), (3, instruction)]),
+        };
+        let theirs = RunArtifacts {
+            trace: encode_trace(&[(1, 5, 5), (4, 6, 5)]),
+            memory: encode_memory(&[(1, 10), (3, instruction)]),
+        };
+        let report = compare(&ours, &theirs).unwrap();
+        assert_eq!(report.lines().count(), 3);
+        assert!(report.contains(&format!(
+            "pc=3 ap=6 fp=5 instruction={:?}",
+            decode_instruction(instruction).unwrap()
+        )));
+        assert!(report.contains("pc=4 ap=6 fp=5 instruction=<unknown>"));
+    }
+
+    #[test]
+    fn python_vm_output_dir_is_removed_when_the_run_fails() {
+        let program = Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../cairo_programs/fibonacci.json"
+        ));
+        let args = Args::try_parse_from(["compare_vm", program.to_str().unwrap()]).unwrap();
+        assert_matches::assert_matches!(
+            run_python_vm("./missing/cairo-run", program, &args),
+            Err(Error::PythonVmNotFound(_))
+        );
+        assert!(!python_vm_output_dir().exists());
+        // `false` exits with an error without writing the trace and memory files
+        #[cfg(unix)]
+        {
+            assert_matches::assert_matches!(
+                run_python_vm("false", program, &args),
+                Err(Error::PythonVm(_))
+            );
+            assert!(!python_vm_output_dir().exists());
+        }
+    }
+
+    #[test]
+    #[ignore = "requires cairo-lang's cairo-run, run with `cargo test -- --ignored`"]
+    fn compare_against_python_vm_smoke() {
+        let program = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../cairo_programs/fibonacci.json"
+        );
+        let args = ["compare_vm", program].map(String::from);
+        assert_matches::assert_matches!(run(args.into_iter()), Ok(None));
+    }
+}
@@ -2,7 +2,7 @@
 #![forbid(unsafe_code)]
 use bincode::enc::write::Writer;
 use cairo_vm::air_public_input::PublicInputError;
-use cairo_vm::cairo_run::{self, EncodeTraceError};
+use cairo_vm::cairo_run::{self, EncodeTraceError, ProgramSource};
 use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
 #[cfg(feature = "with_tracer")]
 use cairo_vm::serde::deserialize_program::DebugInfo;
@@ -20,6 +20,7 @@ use cairo_vm_tracer::tracer::run_tracer;
 use clap::{Parser, ValueHint};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::process::Command;
 use thiserror::Error;
 
 #[cfg(feature = "with_mimalloc")]
@@ -62,8 +63,15 @@ struct Args {
         conflicts_with_all = ["proof_mode", "air_private_input", "air_public_input"]
     )]
     cairo_pie_output: Option<String>,
+    #[clap(long = "disable_memory_holes_count", conflicts_with = "proof_mode")]
+    disable_memory_holes_count: bool,
     #[structopt(long = "allow_missing_builtins")]
     allow_missing_builtins: Option<bool>,
+    #[clap(long = "recent_steps", value_parser)]
+    recent_steps: Option<usize>,
+    /// Command used to compile the program file before running it, the filename is appended to it
+    #[clap(long = "compile_command", value_parser)]
+    compile_command: Option<String>,
     #[structopt(long = "tracer")]
     #[cfg(feature = "with_tracer")]
     tracer: bool,
@@ -164,6 +172,26 @@ fn start_tracer(cairo_runner: &CairoRunner, vm: &VirtualMachine) -> Result<(), T
     Ok(())
 }
 
+fn compile_program(compile_command: &str, filename: &Path) -> Result<Vec<u8>, String> {
+    let mut command_parts = compile_command.split_whitespace();
+    let program = command_parts
+        .next()
+        .ok_or_else(|| String::from("empty compile command"))?;
+    let output = Command::new(program)
+        .args(command_parts)
+        .arg(filename)
+        .output()
+        .map_err(|e| format!("failed to execute `{compile_command}`: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`{compile_command}` exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
 fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
     let args = Args::try_parse_from(args)?;
 
@@ -177,19 +205,33 @@ fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
         proof_mode: args.proof_mode,
         secure_run: args.secure_run,
         allow_missing_builtins: args.allow_missing_builtins,
+        disable_memory_holes_count: args.disable_memory_holes_count,
+        recent_steps: args.recent_steps,
         ..Default::default()
     };
 
-    let program_content = std::fs::read(args.filename).map_err(Error::IO)?;
+    let program_source = match args.compile_command {
+        Some(ref compile_command) => {
+            let compile_command = compile_command.clone();
+            let filename = args.filename.clone();
+            ProgramSource::Compiler(Box::new(move || {
+                compile_program(&compile_command, &filename)
+            }))
+        }
+        None => ProgramSource::Bytes(std::fs::read(&args.filename).map_err(Error::IO)?),
+    };
 
-    let (cairo_runner, mut vm) =
-        match cairo_run::cairo_run(&program_content, &cairo_run_config, &mut hint_executor) {
-            Ok(runner) => runner,
-            Err(error) => {
-                eprintln!("{error}");
-                return Err(Error::Runner(error));
-            }
-        };
+    let (cairo_runner, mut vm) = match cairo_run::cairo_run_program_source(
+        &program_source,
+        &cairo_run_config,
+        &mut hint_executor,
+    ) {
+        Ok(runner) => runner,
+        Err(error) => {
+            eprintln!("{error}");
+            return Err(Error::Runner(error));
+        }
+    };
 
     if args.print_output {
         let mut output_buffer = "Program Output:\n".to_string();
@@ -249,6 +291,7 @@ fn run(args: impl Iterator<Item = String>) -> Result<(), Error> {
 
         let json = cairo_runner
             .get_air_private_input(&vm)
+            .map_err(CairoRunError::Runner)?
             .to_serializable(trace_path, memory_path)
             .serialize_json()
             .map_err(PublicInputError::Serde)?;
@@ -282,6 +325,7 @@ mod tests {
     #![allow(clippy::too_many_arguments)]
     use super::*;
     use assert_matches::assert_matches;
+    use cairo_vm::types::errors::program_errors::ProgramError;
     use rstest::rstest;
 
     #[rstest]
@@ -320,6 +364,13 @@ mod tests {
         assert_matches!(run(args), Err(Error::Cli(_)));
     }
 
+    #[rstest]
+    #[case(["cairo-vm-cli", "../cairo_programs/fibonacci.json", "--disable_memory_holes_count", "--proof_mode"].as_slice())]
+    fn test_run_disable_memory_holes_count_proof_mode(#[case] args: &[&str]) {
+        let args = args.iter().cloned().map(String::from);
+        assert_matches!(run(args), Err(Error::Cli(_)));
+    }
+
     #[rstest]
     fn test_run_ok(
         #[values(None,
@@ -408,6 +459,38 @@ mod tests {
         assert_matches!(run(args), Err(Error::Runner(_)));
     }
 
+    #[test]
+    fn test_run_compile_command() {
+        // `cat` acts as a compiler that outputs an already compiled program
+        let args = [
+            "cairo-vm-cli",
+            "../cairo_programs/manually_compiled/hintless_loop.json",
+            "--compile_command",
+            "cat",
+        ]
+        .into_iter()
+        .map(String::from);
+        assert_matches!(run(args), Ok(()));
+    }
+
+    #[test]
+    fn test_run_compile_command_fails() {
+        let args = [
+            "cairo-vm-cli",
+            "../cairo_programs/manually_compiled/hintless_loop.json",
+            "--compile_command",
+            "false",
+        ]
+        .into_iter()
+        .map(String::from);
+        assert_matches!(
+            run(args),
+            Err(Error::Runner(CairoRunError::Program(
+                ProgramError::Compiler(_)
+            )))
+        );
+    }
+
     //Since the functionality here is trivial, I just call the function
     //to fool Codecov.
     #[test]
@@ -64,6 +64,8 @@ pub struct Cairo1RunConfig<'a> {
     pub finalize_builtins: bool,
     // Appends return values to the output segment. This is performed by default when running in proof_mode
     pub append_return_values: bool,
+    // Gas amount passed to main as its GasBuiltin argument (if main uses it)
+    pub initial_gas: u64,
 }
 
 impl Default for Cairo1RunConfig<'_> {
@@ -77,6 +79,7 @@ impl Default for Cairo1RunConfig<'_> {
             proof_mode: false,
             finalize_builtins: false,
             append_return_values: false,
+            initial_gas: 9999999999999,
         }
     }
 }
@@ -104,8 +107,6 @@ pub fn cairo_run_program(
 
     let main_func = find_function(sierra_program, "::main")?;
 
-    let initial_gas = 9999999999999_usize;
-
     // Modified entry code to be compatible with custom cairo1 Proof Mode.
     // This adds code that's needed for dictionaries, adjusts ap for builtin pointers, adds initial gas for the gas builtin if needed, and sets up other necessary code for cairo1
     let (entry_code, builtins) = create_entry_code(
@@ -113,7 +114,7 @@ pub fn cairo_run_program(
         &casm_program,
         &type_sizes,
         main_func,
-        initial_gas,
+        cairo_run_config.initial_gas,
         cairo_run_config.proof_mode || cairo_run_config.append_return_values,
         cairo_run_config.args,
     )?;
@@ -261,6 +262,51 @@ pub fn cairo_run_program(
     Ok((runner, vm, return_values, serialized_output))
 }
 
+/// Returns the gas left after running the program's `main` function, which is the value of the
+/// `GasBuiltin` among main's return values.
+/// Returns None if `main` doesn't use the gas builtin.
+/// The `vm` should be the one returned by `cairo_run_program` for the same `sierra_program`.
+pub fn get_remaining_gas(
+    sierra_program: &SierraProgram,
+    vm: &VirtualMachine,
+) -> Result<Option<Felt252>, Error> {
+    let sierra_program_registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(sierra_program)?;
+    let type_sizes =
+        get_type_size_map(sierra_program, &sierra_program_registry).unwrap_or_default();
+    let main_func = find_function(sierra_program, "::main")?;
+    fetch_remaining_gas(
+        &sierra_program_registry,
+        &main_func.signature.ret_types,
+        &type_sizes,
+        vm,
+    )
+}
+
+fn fetch_remaining_gas(
+    sierra_program_registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    main_ret_types: &[ConcreteTypeId],
+    type_sizes: &UnorderedHashMap<ConcreteTypeId, i16>,
+    vm: &VirtualMachine,
+) -> Result<Option<Felt252>, Error> {
+    // Main's return values are laid out in order right before the final ap
+    let ret_types_sizes = main_ret_types
+        .iter()
+        .map(|id| type_sizes.get(id).cloned().unwrap_or_default());
+    let full_ret_types_size: i16 = ret_types_sizes.clone().sum();
+    let mut value_ptr =
+        (vm.get_ap() - full_ret_types_size as usize).map_err(VirtualMachineError::Math)?;
+
+    for (ty, size) in main_ret_types.iter().zip(ret_types_sizes) {
+        let info = get_info(sierra_program_registry, ty)
+            .ok_or_else(|| Error::NoInfoForType(ty.clone()))?;
+        if info.long_id.generic_id == GasBuiltinType::ID {
+            return Ok(Some(*vm.get_integer(value_ptr)?));
+        }
+        value_ptr.offset += size as usize;
+    }
+    Ok(None)
+}
+
 fn additional_initialization(vm: &mut VirtualMachine, data_len: usize) -> Result<(), Error> {
     // Create the builtin cost segment
     let builtin_cost_segment = vm.add_memory_segment();
@@ -410,7 +456,7 @@ fn create_entry_code(
     casm_program: &CairoProgram,
     type_sizes: &UnorderedHashMap<ConcreteTypeId, i16>,
     func: &Function,
-    initial_gas: usize,
+    initial_gas: u64,
     append_output: bool,
     args: &[FuncArg],
 ) -> Result<(Vec<Instruction>, Vec<BuiltinName>), Error> {
@@ -676,7 +722,7 @@ fn fetch_return_values(
         .starts_with("core::panics::PanicResult::")
     {
         // Check the failure flag (aka first return value)
-        if return_values.first() != Some(&MaybeRelocatable::from(0)) {
+        if return_values.first() != Some(&MaybeRelocatable::from(Felt252::ZERO)) {
             // In case of failure, extract the error from the return values (aka last two values)
             let panic_data_end = return_values
                 .last()
@@ -979,7 +1025,7 @@ fn serialize_output_inner(
                 // Remove padding
                 assert_eq!(
                     return_values_iter.next(),
-                    Some(&MaybeRelocatable::from(0)),
+                    Some(&MaybeRelocatable::from(Felt252::ZERO)),
                     "Malformed enum"
                 );
             }
@@ -1148,6 +1194,54 @@ mod tests {
             .unwrap_or_default()
     }
 
+    #[rstest]
+    #[case("../cairo_programs/cairo-1-programs/factorial.cairo")]
+    #[case("../cairo_programs/cairo-1-programs/recursion.cairo")]
+    fn get_remaining_gas_after_run(
+        #[case] filename: &str,
+        #[values(true, false)] proof_mode: bool,
+    ) {
+        let sierra_program = compile_to_sierra(filename);
+        let run_with_gas = |initial_gas| {
+            let cairo_run_config = Cairo1RunConfig {
+                proof_mode,
+                layout: "all_cairo",
+                initial_gas,
+                ..Default::default()
+            };
+            let (_, vm, _, _) = cairo_run_program(&sierra_program, cairo_run_config).unwrap();
+            get_remaining_gas(&sierra_program, &vm).unwrap().unwrap()
+        };
+        let initial_gas = 1_000_000_u64;
+        let remaining_gas = run_with_gas(initial_gas);
+        // Some gas was consumed
+        assert!(remaining_gas < Felt252::from(initial_gas));
+        // The amount of gas consumed doesn't depend on the initial gas
+        assert_eq!(
+            run_with_gas(2 * initial_gas) - remaining_gas,
+            Felt252::from(initial_gas)
+        );
+    }
+
+    #[test]
+    fn get_remaining_gas_without_gas_builtin() {
+        let sierra_program = compile_to_sierra("../cairo_programs/cairo-1-programs/simple.cairo");
+        let (_, vm, _, _) = cairo_run_program(&sierra_program, Default::default()).unwrap();
+        assert_eq!(get_remaining_gas(&sierra_program, &vm).unwrap(), None);
+    }
+
+    #[test]
+    fn run_out_of_gas() {
+        let sierra_program =
+            compile_to_sierra("../cairo_programs/cairo-1-programs/recursion.cairo");
+        let cairo_run_config = Cairo1RunConfig {
+            initial_gas: 0,
+            ..Default::default()
+        };
+        let result = cairo_run_program(&sierra_program, cairo_run_config);
+        assert!(matches!(result, Err(Error::RunPanic(_))));
+    }
+
     #[rstest]
     #[case("../cairo_programs/cairo-1-programs/array_append.cairo")]
     #[case("../cairo_programs/cairo-1-programs/array_get.cairo")]
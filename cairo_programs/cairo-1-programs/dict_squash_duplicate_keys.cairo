@@ -0,0 +1,16 @@
+fn main() -> felt252 {
+    // Create the dictionary
+    let mut d: Felt252Dict<felt252> = Default::default();
+
+    // Access the same keys several times so that squashing has to merge them
+    d.insert(1, 10);
+    d.insert(2, 20);
+    d.insert(1, 11);
+    d.insert(3, 30);
+    d.insert(2, 21);
+    d.insert(1, 12);
+
+    let total = d.get(1) + d.get(2) + d.get(3);
+    let _squashed = d.squash();
+    total
+}
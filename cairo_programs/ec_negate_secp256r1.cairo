@@ -0,0 +1,54 @@
+%builtins range_check
+
+// Exercises the secp256r1 negation hint, which hardcodes its prime, and the doubling slope hint
+// taking SECP_P and ALPHA from the scope, on the P-256 generator.
+from starkware.cairo.common.cairo_secp.bigint import BigInt3, nondet_bigint3
+from starkware.cairo.common.cairo_secp.ec import EcPoint
+
+func ec_negate{range_check_ptr}(point: EcPoint) -> (point: EcPoint) {
+    %{
+        from starkware.cairo.common.cairo_secp.secp_utils import pack
+        from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_P as SECP_P
+
+        y = pack(ids.point.y, PRIME) % SECP_P
+        # The modulo operation in python always returns a nonnegative number.
+        value = (-y) % SECP_P
+    %}
+    let (minus_y) = nondet_bigint3();
+    return (point=EcPoint(x=point.x, y=minus_y));
+}
+
+func compute_doubling_slope{range_check_ptr}(point: EcPoint) -> (slope: BigInt3) {
+    %{ from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_P as SECP_P %}
+    %{ from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_ALPHA as ALPHA %}
+    %{
+        from starkware.cairo.common.cairo_secp.secp_utils import pack
+        from starkware.python.math_utils import ec_double_slope
+
+        # Compute the slope.
+        x = pack(ids.point.x, PRIME)
+        y = pack(ids.point.y, PRIME)
+        value = slope = ec_double_slope(point=(x, y), alpha=ALPHA, p=SECP_P)
+    %}
+    let (slope) = nondet_bigint3();
+    return (slope=slope);
+}
+
+func main{range_check_ptr}() {
+    let point = EcPoint(
+        BigInt3(52227620040540588600771222, 33347259622618539004134583, 8091721874918813684698062),
+        BigInt3(59685082318776612195095029, 54599710628478995760242092, 6036146923926000695307902),
+    );
+
+    let (minus_point) = ec_negate(point);
+    assert minus_point.y = BigInt3(
+        17686170136559654986100234, 22771541826857271420954195, 13306666185404466473668993
+    );
+
+    let (slope) = compute_doubling_slope(point);
+    assert slope = BigInt3(
+        18381657395733761514123165, 60250936249481871670557728, 12262137276504867214243183
+    );
+
+    return ();
+}
@@ -51,10 +51,18 @@ fn run() {
     }
     let mut vm = VirtualMachine::new(false);
     let mut hint_executor = BuiltinHintProcessor::new_empty();
-    let (ap_tracking_data, reference_ids, references, mut exec_scopes, constants) = (
+    let (
+        ap_tracking_data,
+        reference_ids,
+        references,
+        accessible_scopes,
+        mut exec_scopes,
+        constants,
+    ) = (
         ApTracking::default(),
         HashMap::new(),
         Vec::new(),
+        Vec::new(),
         ExecutionScopes::new(),
         HashMap::new(),
     );
@@ -64,11 +72,17 @@ fn run() {
         .map(|ahe| ahe.hint_lines.join("\n"))
         .filter(|h| {
             let hint_data = hint_executor
-                .compile_hint(h, &ap_tracking_data, &reference_ids, &references)
+                .compile_hint(
+                    h,
+                    &ap_tracking_data,
+                    &reference_ids,
+                    &references,
+                    &accessible_scopes,
+                )
                 .expect("this implementation is infallible");
             matches!(
                 hint_executor.execute_hint(&mut vm, &mut exec_scopes, &hint_data, &constants,),
-                Err(HintError::UnknownHint(_)),
+                Err(HintError::UnknownHint(_) | HintError::HintFeatureDisabled(_)),
             )
         })
         .collect();
@@ -8,10 +8,15 @@ use crate::{
         POSEIDON_BUILTIN_NAME, RANGE_CHECK_BUILTIN_NAME, SIGNATURE_BUILTIN_NAME,
     },
 };
+use num_bigint::BigInt;
 use serde::{Deserialize, Serialize};
 
 use crate::Felt252;
 
+/// Key under which the secp points collected by the hint processor are stored in the
+/// [`AirPrivateInput`]
+pub const SECP_EC_POINTS_NAME: &str = "secp_ec_points";
+
 // Serializable format, matches the file output of the python implementation
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct AirPrivateInputSerializable {
@@ -31,6 +36,8 @@ pub struct AirPrivateInputSerializable {
     keccak: Option<Vec<PrivateInput>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     poseidon: Option<Vec<PrivateInput>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    secp_ec_points: Option<Vec<PrivateInput>>,
 }
 
 // Contains only builtin public inputs, useful for library users
@@ -46,6 +53,7 @@ pub enum PrivateInput {
     PoseidonState(PrivateInputPoseidonState),
     KeccakState(PrivateInputKeccakState),
     Signature(PrivateInputSignature),
+    EcPoint(PrivateInputEcPoint),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -71,6 +79,14 @@ pub struct PrivateInputEcOp {
     pub q_y: Felt252,
 }
 
+// Packed secp point consumed by an EC hint, indexed by the order in which hints read them
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PrivateInputEcPoint {
+    pub index: usize,
+    pub point_x: BigInt,
+    pub point_y: BigInt,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct PrivateInputPoseidonState {
     pub index: usize,
@@ -122,6 +138,7 @@ impl AirPrivateInput {
             ec_op: self.0.get(EC_OP_BUILTIN_NAME).cloned(),
             keccak: self.0.get(KECCAK_BUILTIN_NAME).cloned(),
             poseidon: self.0.get(POSEIDON_BUILTIN_NAME).cloned(),
+            secp_ec_points: self.0.get(SECP_EC_POINTS_NAME).cloned(),
         }
     }
 }
@@ -141,6 +158,7 @@ impl From<AirPrivateInputSerializable> for AirPrivateInput {
         insert_input(EC_OP_BUILTIN_NAME, private_input.ec_op);
         insert_input(KECCAK_BUILTIN_NAME, private_input.keccak);
         insert_input(POSEIDON_BUILTIN_NAME, private_input.poseidon);
+        insert_input(SECP_EC_POINTS_NAME, private_input.secp_ec_points);
 
         Self(inputs)
     }
@@ -224,6 +242,11 @@ mod tests {
                     input_s2: Felt252::from(3),
                 },
             )]),
+            secp_ec_points: Some(vec![PrivateInput::EcPoint(PrivateInputEcPoint {
+                index: 0,
+                point_x: BigInt::from(5),
+                point_y: BigInt::from(7),
+            })]),
         };
 
         let private_input = AirPrivateInput::from(serializable_private_input.clone());
@@ -235,6 +258,7 @@ mod tests {
         assert_matches!(private_input.0.get(EC_OP_BUILTIN_NAME), data if data == serializable_private_input.ec_op.as_ref());
         assert_matches!(private_input.0.get(KECCAK_BUILTIN_NAME), data if data == serializable_private_input.keccak.as_ref());
         assert_matches!(private_input.0.get(POSEIDON_BUILTIN_NAME), data if data == serializable_private_input.poseidon.as_ref());
+        assert_matches!(private_input.0.get(SECP_EC_POINTS_NAME), data if data == serializable_private_input.secp_ec_points.as_ref());
     }
 
     #[test]
@@ -247,7 +271,7 @@ mod tests {
             ..Default::default()
         };
         let (runner, vm) = crate::cairo_run::cairo_run(include_bytes!("../../cairo_programs/proof_programs/fibonacci.json"), &config, &mut crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor::new_empty()).unwrap();
-        let public_input = runner.get_air_private_input(&vm);
+        let public_input = runner.get_air_private_input(&vm).unwrap();
         let serialized_public_input =
             public_input.to_serializable("/dev/null".to_string(), "/dev/null".to_string());
         assert!(serialized_public_input.pedersen.is_some());
@@ -257,5 +281,28 @@ mod tests {
         assert!(serialized_public_input.ec_op.is_none());
         assert!(serialized_public_input.keccak.is_none());
         assert!(serialized_public_input.poseidon.is_none());
+        assert!(serialized_public_input.secp_ec_points.is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ec_point_private_input_json_round_trip() {
+        let private_input = vec![
+            PrivateInput::Pair(PrivateInputPair {
+                index: 0,
+                x: Felt252::from(1),
+                y: Felt252::from(2),
+            }),
+            PrivateInput::EcPoint(PrivateInputEcPoint {
+                index: 0,
+                point_x: BigInt::from(1) << 255,
+                point_y: BigInt::from(3),
+            }),
+        ];
+        let json = serde_json::to_string(&private_input).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<PrivateInput>>(&json).unwrap(),
+            private_input
+        );
     }
 }
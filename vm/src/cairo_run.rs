@@ -1,16 +1,20 @@
 use crate::{
     hint_processor::hint_processor_definition::HintProcessor,
-    types::program::Program,
+    types::{errors::program_errors::ProgramError, program::Program},
     vm::{
         errors::{cairo_run_errors::CairoRunError, vm_exception::VmException},
         runners::cairo_runner::CairoRunner,
         security::verify_secure_runner,
         vm_core::VirtualMachine,
+        vm_memory::memory::MemoryLimits,
     },
 };
 
+use crate::stdlib::prelude::*;
 use crate::Felt252;
 use bincode::enc::write::Writer;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
 
 use thiserror_no_std::Error;
 
@@ -28,7 +32,14 @@ pub struct CairoRunConfig<'a> {
     pub proof_mode: bool,
     pub secure_run: Option<bool>,
     pub disable_trace_padding: bool,
+    pub disable_memory_holes_count: bool,
     pub allow_missing_builtins: Option<bool>,
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+    pub memory_limits: MemoryLimits,
+    pub recent_steps: Option<usize>,
+    /// If set, the program hash (computed with bootloader version 0) must match this value,
+    /// otherwise the run fails with [`ProgramError::ProgramHashMismatch`] before executing.
+    pub expected_program_hash: Option<Felt252>,
 }
 
 #[cfg(feature = "arbitrary")]
@@ -57,7 +68,11 @@ impl<'a> Default for CairoRunConfig<'a> {
             proof_mode: false,
             secure_run: None,
             disable_trace_padding: false,
+            disable_memory_holes_count: false,
             allow_missing_builtins: None,
+            memory_limits: MemoryLimits::default(),
+            recent_steps: None,
+            expected_program_hash: None,
         }
     }
 }
@@ -75,14 +90,32 @@ pub fn cairo_run_program(
         .allow_missing_builtins
         .unwrap_or(cairo_run_config.proof_mode);
 
+    if let Some(expected_hash) = cairo_run_config.expected_program_hash {
+        let program_hash = program.compute_hash(0)?;
+        if program_hash != expected_hash {
+            return Err(
+                ProgramError::ProgramHashMismatch(Box::new((expected_hash, program_hash))).into(),
+            );
+        }
+    }
+
     let mut cairo_runner = CairoRunner::new(
         program,
         cairo_run_config.layout,
         cairo_run_config.proof_mode,
     )?;
+    cairo_runner.disable_memory_holes_count = cairo_run_config.disable_memory_holes_count;
 
     let mut vm = VirtualMachine::new(cairo_run_config.trace_enabled);
+    vm.segments
+        .set_memory_limits(cairo_run_config.memory_limits);
+    if let Some(capacity) = cairo_run_config.recent_steps {
+        vm.enable_recent_steps(capacity);
+    }
     let end = cairo_runner.initialize(&mut vm, allow_missing_builtins)?;
+    if secure_run {
+        cairo_runner.mark_program_segment_read_only(&mut vm);
+    }
     // check step calculation
 
     cairo_runner
@@ -122,6 +155,41 @@ pub fn cairo_run(
     cairo_run_program(&program, cairo_run_config, hint_executor)
 }
 
+/// Where to get the compiled program to run from
+pub enum ProgramSource {
+    /// Path to the JSON output of the compiler
+    #[cfg(feature = "std")]
+    Json(PathBuf),
+    /// Contents of the JSON output of the compiler
+    Bytes(Vec<u8>),
+    /// Hook producing the JSON output of the compiler, e.g. by invoking an external one.
+    /// The error it returns is propagated as `ProgramError::Compiler`
+    Compiler(Box<dyn Fn() -> Result<Vec<u8>, String>>),
+}
+
+impl ProgramSource {
+    pub fn load(&self, entrypoint: Option<&str>) -> Result<Program, ProgramError> {
+        match self {
+            #[cfg(feature = "std")]
+            ProgramSource::Json(path) => Program::from_file(path, entrypoint),
+            ProgramSource::Bytes(bytes) => Program::from_bytes(bytes, entrypoint),
+            ProgramSource::Compiler(compile) => {
+                Program::from_bytes(&compile().map_err(ProgramError::Compiler)?, entrypoint)
+            }
+        }
+    }
+}
+
+pub fn cairo_run_program_source(
+    program_source: &ProgramSource,
+    cairo_run_config: &CairoRunConfig,
+    hint_executor: &mut dyn HintProcessor,
+) -> Result<(CairoRunner, VirtualMachine), CairoRunError> {
+    let program = program_source.load(Some(cairo_run_config.entrypoint))?;
+
+    cairo_run_program(&program, cairo_run_config, hint_executor)
+}
+
 #[cfg(feature = "arbitrary")]
 pub fn cairo_run_fuzzed_program(
     program: Program,
@@ -140,8 +208,14 @@ pub fn cairo_run_fuzzed_program(
         cairo_run_config.layout,
         cairo_run_config.proof_mode,
     )?;
+    cairo_runner.disable_memory_holes_count = cairo_run_config.disable_memory_holes_count;
 
     let mut vm = VirtualMachine::new(cairo_run_config.trace_enabled);
+    vm.segments
+        .set_memory_limits(cairo_run_config.memory_limits);
+    if let Some(capacity) = cairo_run_config.recent_steps {
+        vm.enable_recent_steps(capacity);
+    }
 
     let _end = cairo_runner.initialize(
         &mut vm,
@@ -149,6 +223,9 @@ pub fn cairo_run_fuzzed_program(
             .allow_missing_builtins
             .unwrap_or(cairo_run_config.proof_mode),
     )?;
+    if secure_run {
+        cairo_runner.mark_program_segment_read_only(&mut vm);
+    }
 
     let res = match cairo_runner.run_until_steps(steps_limit, &mut vm, hint_executor) {
         Err(VirtualMachineError::EndOfProgram(_remaining)) => Ok(()), // program ran OK but ended before steps limit
@@ -223,14 +300,17 @@ pub fn write_encoded_memory(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stdlib::prelude::*;
-    use crate::Felt252;
+    use crate::{felt_hex, Felt252};
     use crate::{
         hint_processor::{
             builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
             hint_processor_definition::HintProcessor,
         },
+        types::relocatable::Relocatable,
         utils::test_utils::*,
+        vm::errors::{
+            hint_errors::HintError, memory_errors::MemoryError, vm_errors::VirtualMachineError,
+        },
     };
     use bincode::enc::write::SliceWriter;
 
@@ -313,6 +393,338 @@ mod tests {
         assert!(cairo_run(invalid_memory, &cairo_run_config, &mut hint_processor,).is_err());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_proof_mode_output_only_small_layout() {
+        // main writes 7 to the output, which has no ratio in the small layout
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/proof_mode_output_only.json");
+        let cairo_run_config = CairoRunConfig {
+            layout: "small",
+            proof_mode: true,
+            relocate_mem: true,
+            trace_enabled: true,
+            ..Default::default()
+        };
+        let (cairo_runner, mut vm) =
+            cairo_run(program, &cairo_run_config, &mut hint_processor).unwrap();
+
+        let mut output_buffer = String::new();
+        vm.write_output(&mut output_buffer).unwrap();
+        assert_eq!(&output_buffer, "7\n");
+
+        let public_input = cairo_runner.get_air_public_input(&vm).unwrap();
+        assert_eq!(public_input.n_steps, 512);
+        let output = &public_input.memory_segments["output"];
+        assert_eq!(output.stop_ptr - output.begin_addr, 1);
+        for builtin in ["pedersen", "range_check", "ecdsa"] {
+            let segment = &public_input.memory_segments[builtin];
+            assert_eq!(segment.begin_addr, segment.stop_ptr);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_program_source_compiler_hook() {
+        // Fake compiler returning an already compiled program
+        let program_source = ProgramSource::Compiler(Box::new(|| {
+            Ok(
+                include_bytes!("../../cairo_programs/manually_compiled/hintless_loop.json")
+                    .to_vec(),
+            )
+        }));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let (_, vm) = cairo_run_program_source(
+            &program_source,
+            &CairoRunConfig::default(),
+            &mut hint_processor,
+        )
+        .unwrap();
+        assert_eq!(vm.current_step, 200_002);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_program_source_compiler_hook_error() {
+        let program_source =
+            ProgramSource::Compiler(Box::new(|| Err(String::from("syntax error in main.cairo"))));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let err = cairo_run_program_source(
+            &program_source,
+            &CairoRunConfig::default(),
+            &mut hint_processor,
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::Program(ProgramError::Compiler(ref msg)) if msg == "syntax error in main.cairo"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Failed to compile the program: syntax error in main.cairo"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn cairo_run_program_source_json_path() {
+        let program_source = ProgramSource::Json(PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../cairo_programs/manually_compiled/hintless_loop.json"
+        )));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let (_, vm) = cairo_run_program_source(
+            &program_source,
+            &CairoRunConfig::default(),
+            &mut hint_processor,
+        )
+        .unwrap();
+        assert_eq!(vm.current_step, 200_002);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_self_writing_program_secure_run() {
+        // write_to_program writes 7 right after the end of the program, through its return pc
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/self_writing_program.json");
+        let cairo_run_config = CairoRunConfig {
+            secure_run: Some(true),
+            ..Default::default()
+        };
+        let err = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::VmException(ref e) if e.pc == Relocatable::from((0, 5)) && matches!(
+                e.inner_exc,
+                VirtualMachineError::Memory(MemoryError::WriteToReadOnlySegment(ref bx))
+                if **bx == Relocatable::from((0, 7))
+            )
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_self_writing_program_permissive_run() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/self_writing_program.json");
+        let cairo_run_config = CairoRunConfig {
+            secure_run: Some(false),
+            ..Default::default()
+        };
+        let (_, vm) = cairo_run(program, &cairo_run_config, &mut hint_processor).unwrap();
+        assert_eq!(
+            vm.get_integer((0, 7).into()).unwrap().into_owned(),
+            Felt252::from(7)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_runaway_writes_stopped_by_segment_size_limit() {
+        // main writes to [ap] and increments ap forever
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/runaway_memory_writes.json");
+        let cairo_run_config = CairoRunConfig {
+            memory_limits: MemoryLimits {
+                max_segment_size: Some(100),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::VmException(ref e) if matches!(
+                e.inner_exc,
+                VirtualMachineError::Memory(MemoryError::SegmentSizeLimitExceeded(ref bx))
+                    if **bx == (Relocatable::from((1, 100)), 100)
+            )
+        ));
+        assert!(err
+            .to_string()
+            .contains("Can't write to 1:100: segment size limit of 100 cells exceeded"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_runaway_segments_stopped_by_segment_count_limit() {
+        // main runs `memory[ap] = segments.add()` and increments ap forever
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/runaway_segments.json");
+        let cairo_run_config = CairoRunConfig {
+            memory_limits: MemoryLimits {
+                max_segments: Some(10),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::VmException(ref e) if matches!(
+                e.inner_exc,
+                VirtualMachineError::Hint(ref bx) if matches!(
+                    bx.1,
+                    HintError::Memory(MemoryError::SegmentCountLimitExceeded(10))
+                )
+            )
+        ));
+        assert!(err
+            .to_string()
+            .contains("Can't add a segment: segment count limit of 10 exceeded"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_error_includes_recent_steps() {
+        // main writes to [ap] and increments ap forever, until reaching the segment size limit
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/runaway_memory_writes.json");
+        let cairo_run_config = CairoRunConfig {
+            trace_enabled: false,
+            memory_limits: MemoryLimits {
+                max_segment_size: Some(100),
+                ..Default::default()
+            },
+            recent_steps: Some(3),
+            ..Default::default()
+        };
+        let err = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(err.to_string().ends_with(
+            "Recent steps (oldest first):
+pc=0:0 ap=99 fp=2 opcode=AssertEq
+pc=0:2 ap=100 fp=2 opcode=NOp
+pc=0:0 ap=100 fp=2 opcode=AssertEq
+"
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_runaway_writes_stopped_by_total_cells_limit() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/runaway_memory_writes.json");
+        let cairo_run_config = CairoRunConfig {
+            memory_limits: MemoryLimits {
+                max_total_cells: Some(100),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::VmException(ref e) if matches!(
+                e.inner_exc,
+                VirtualMachineError::Memory(MemoryError::TotalCellsLimitExceeded(ref bx))
+                    if bx.1 == 100
+            )
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_read_unwritten_output_cell() {
+        // main writes output_ptr[0..3] and then reads output_ptr[5]
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program =
+            include_bytes!("../../cairo_programs/manually_compiled/read_unwritten_output.json");
+        let cairo_run_config = CairoRunConfig {
+            layout: "small",
+            ..Default::default()
+        };
+        let err = cairo_run(program, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::VmException(ref e) if matches!(
+                e.inner_exc,
+                VirtualMachineError::Memory(MemoryError::UnknownMemoryCellOutputBuiltin(ref bx))
+                    if **bx == Relocatable::from((2, 5))
+            )
+        ));
+        assert!(err
+            .to_string()
+            .contains("Unknown output builtin cell at address 2:5"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_hintless_loop() {
+        // main counts down from 100000 to 0 in a loop without hints
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let program = include_bytes!("../../cairo_programs/manually_compiled/hintless_loop.json");
+        let (_, vm) = cairo_run(program, &CairoRunConfig::default(), &mut hint_processor).unwrap();
+        assert_eq!(vm.current_step, 200_002);
+        assert_eq!(
+            vm.get_integer((vm.get_ap() - 1).unwrap()).unwrap().as_ref(),
+            &Felt252::ZERO
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_expected_program_hash_matches() {
+        let program_content = include_bytes!("../../cairo_programs/fibonacci.json");
+        let cairo_run_config = CairoRunConfig {
+            // Computed with cairo-lang's `compute_program_hash_chain`
+            expected_program_hash: Some(felt_hex!(
+                "0x43b17e9592f33142246af4c06cd2b574b460dd1f718d76b51341175a62b220f"
+            )),
+            ..Default::default()
+        };
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        assert!(cairo_run(program_content, &cairo_run_config, &mut hint_processor).is_ok());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_run_expected_program_hash_mismatch() {
+        let program_content =
+            include_bytes!("../../cairo_programs/manually_compiled/hintless_loop.json");
+        let program_hash = Program::from_bytes(program_content, Some("main"))
+            .unwrap()
+            .compute_hash(0)
+            .unwrap();
+        let cairo_run_config = CairoRunConfig {
+            expected_program_hash: Some(Felt252::from(42)),
+            ..Default::default()
+        };
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let err = cairo_run(program_content, &cairo_run_config, &mut hint_processor)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            CairoRunError::Program(ProgramError::ProgramHashMismatch(ref bx))
+                if **bx == (Felt252::from(42), program_hash)
+        ));
+        assert_eq!(
+            err.to_string(),
+            format!("Program hash mismatch: expected 42, computed {program_hash}")
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn write_output_program() {
@@ -1,18 +1,5 @@
 use super::{
-    blake2s_utils::finalize_blake2s_v3,
-    ec_recover::{
-        ec_recover_divmod_n_packed, ec_recover_product_div_m, ec_recover_product_mod,
-        ec_recover_sub_a_b,
-    },
     field_arithmetic::{u256_get_square_root, u384_get_square_root, uint384_div},
-    secp::{
-        ec_utils::{
-            compute_doubling_slope_external_consts, compute_slope_and_assing_secp_p,
-            ec_double_assign_new_y, ec_mul_inner, ec_negate_embedded_secp_p,
-            ec_negate_import_secp_p, square_slope_minus_xs,
-        },
-        secp_utils::{ALPHA, ALPHA_V2, SECP_P, SECP_P_V2},
-    },
     uint384::sub_reduced_a_and_reduced_b,
     vrf::{
         fq::{inv_mod_p_uint256, uint512_unsigned_div_rem},
@@ -20,76 +7,35 @@ use super::{
         pack::*,
     },
 };
+use crate::any_box;
 use crate::Felt252;
 use crate::{
-    hint_processor::{
-        builtin_hint_processor::secp::ec_utils::{
-            ec_double_assign_new_x, ec_double_assign_new_x_v2,
-        },
-        hint_processor_definition::HintProcessorLogic,
-    },
+    air_private_input::PrivateInput,
+    hint_processor::hint_processor_definition::HintProcessorLogic,
     vm::runners::cairo_runner::{ResourceTracker, RunResources},
 };
 use crate::{
     hint_processor::{
         builtin_hint_processor::{
             bigint::{bigint_pack_div_mod_hint, bigint_safe_div_hint},
-            blake2s_utils::{
-                blake2s_add_uint256, blake2s_add_uint256_bigend, compute_blake2s, finalize_blake2s,
-            },
-            cairo_keccak::keccak_hints::{
-                block_permutation_v1, block_permutation_v2, cairo_keccak_finalize_v1,
-                cairo_keccak_finalize_v2, cairo_keccak_is_full_word, compare_bytes_in_word_nondet,
-                compare_keccak_full_rate_in_bytes_nondet, keccak_write_args,
-            },
-            dict_hint_utils::{
-                default_dict_new, dict_new, dict_read, dict_squash_copy_dict,
-                dict_squash_update_ptr, dict_update, dict_write,
-            },
             ec_utils::{chained_ec_op_random_ec_point_hint, random_ec_point_hint, recover_y_hint},
             find_element_hint::{find_element, search_sorted_lower},
             garaga::get_felt_bitlenght,
             hint_code,
-            keccak_utils::{
-                split_input, split_n_bytes, split_output, split_output_mid_low_high, unsafe_keccak,
-                unsafe_keccak_finalize,
-            },
             math_utils::*,
             memcpy_hint_utils::{add_segment, enter_scope, exit_scope, memcpy_enter_scope},
             memset_utils::{memset_enter_scope, memset_step_loop},
             poseidon_utils::{elements_over_x, n_greater_than_10, n_greater_than_2},
             pow_utils::pow,
-            secp::{
-                bigint_utils::{bigint_to_uint256, hi_max_bitlen, nondet_bigint3},
-                ec_utils::{
-                    compute_doubling_slope, compute_slope, di_bit, fast_ec_add_assign_new_x,
-                    fast_ec_add_assign_new_y, import_secp256r1_alpha, import_secp256r1_n,
-                    import_secp256r1_p, quad_bit,
-                },
-                field_utils::{
-                    is_zero_assign_scope_variables, is_zero_assign_scope_variables_external_const,
-                    is_zero_nondet, is_zero_pack, is_zero_pack_external_secp, reduce_v1, reduce_v2,
-                    verify_zero, verify_zero_with_external_const,
-                },
-                signature::{
-                    div_mod_n_packed_divmod, div_mod_n_packed_external_n, div_mod_n_safe_div,
-                    get_point_from_x, pack_modn_div_modn,
-                },
+            program_hash_utils::{
+                allocate_program_data_segment, load_program, validate_program_hash,
             },
-            segments::{relocate_segment, temporary_array},
-            set::set_add,
-            sha256_utils::{
-                sha256_finalize, sha256_input, sha256_main_arbitrary_input_length,
-                sha256_main_constant_input_length,
+            secp::bigint_utils::{bigint_to_uint256, hi_max_bitlen, nondet_bigint3},
+            segments::{
+                add_segment_into_var, parse_add_segment_hint, relocate_segment, temporary_array,
             },
+            set::set_add,
             signature::verify_ecdsa_signature,
-            squash_dict_utils::{
-                squash_dict, squash_dict_inner_assert_len_keys,
-                squash_dict_inner_check_access_index, squash_dict_inner_continue_loop,
-                squash_dict_inner_first_iteration, squash_dict_inner_len_assert,
-                squash_dict_inner_next_key, squash_dict_inner_skip_loop,
-                squash_dict_inner_used_accesses_assert,
-            },
             uint256_utils::{
                 split_64, uint128_add, uint256_add, uint256_expanded_unsigned_div_rem,
                 uint256_mul_div_mod, uint256_signed_nn, uint256_sqrt, uint256_sub,
@@ -105,26 +51,100 @@ use crate::{
                 verify_multiplicity_body, verify_usort,
             },
         },
-        hint_processor_definition::HintReference,
+        hint_processor_definition::{get_ids_data, HintReference},
     },
     serde::deserialize_program::ApTracking,
     stdlib::{any::Any, collections::HashMap, prelude::*, rc::Rc},
     types::exec_scope::ExecutionScopes,
-    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
+    vm::{
+        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+        vm_core::VirtualMachine,
+    },
 };
+use num_bigint::BigInt;
 
 #[cfg(feature = "skip_next_instruction_hint")]
 use crate::hint_processor::builtin_hint_processor::skip_next_instruction::skip_next_instruction;
 
 #[cfg(feature = "print")]
-use crate::hint_processor::builtin_hint_processor::print::{print_array, print_dict, print_felt};
+use crate::hint_processor::builtin_hint_processor::print::{
+    print_array, print_dict, print_felt, print_locals,
+};
+
+#[cfg(feature = "hints-blake2s")]
+use crate::hint_processor::builtin_hint_processor::blake2s_utils::{
+    blake2s_add_uint256, blake2s_add_uint256_bigend, compute_blake2s, example_blake2s_compress,
+    finalize_blake2s, finalize_blake2s_v3,
+};
+
+#[cfg(feature = "hints-dict")]
+use crate::hint_processor::builtin_hint_processor::{
+    dict_hint_utils::{
+        default_dict_new, dict_new, dict_read, dict_squash_copy_dict, dict_squash_update_ptr,
+        dict_update, dict_write,
+    },
+    squash_dict_utils::{
+        squash_dict, squash_dict_inner_assert_len_keys, squash_dict_inner_check_access_index,
+        squash_dict_inner_continue_loop, squash_dict_inner_first_iteration,
+        squash_dict_inner_len_assert, squash_dict_inner_next_key, squash_dict_inner_skip_loop,
+        squash_dict_inner_used_accesses_assert,
+    },
+};
 
-use super::blake2s_utils::example_blake2s_compress;
+#[cfg(feature = "hints-keccak")]
+use crate::hint_processor::builtin_hint_processor::{
+    cairo_keccak::keccak_hints::{
+        block_permutation_v1, block_permutation_v2, cairo_keccak_finalize_v1,
+        cairo_keccak_finalize_v2, cairo_keccak_is_full_word, compare_bytes_in_word_nondet,
+        compare_keccak_full_rate_in_bytes_nondet, keccak_write_args,
+    },
+    keccak_utils::{
+        split_input, split_n_bytes, split_output, split_output_mid_low_high, unsafe_keccak,
+        unsafe_keccak_finalize,
+    },
+};
+
+#[cfg(feature = "hints-secp")]
+use crate::hint_processor::builtin_hint_processor::{
+    ec_recover::{
+        ec_recover_divmod_n_packed, ec_recover_product_div_m, ec_recover_product_mod,
+        ec_recover_sub_a_b,
+    },
+    secp::{
+        ec_utils::{
+            collect_ec_points, compute_doubling_slope, compute_doubling_slope_external_consts,
+            compute_doubling_slope_secp256r1, compute_slope, compute_slope_and_assing_secp_p,
+            di_bit, ec_double_assign_new_x, ec_double_assign_new_x_v2, ec_double_assign_new_y,
+            ec_mul_inner_packed, ec_negate_embedded_secp_p, ec_negate_import_secp_p,
+            ec_negate_secp256r1, fast_ec_add_assign_new_x, fast_ec_add_assign_new_y,
+            import_secp256r1_alpha, import_secp256r1_n, import_secp256r1_p, quad_bit,
+            square_slope_minus_xs,
+        },
+        field_utils::{
+            is_zero_assign_scope_variables, is_zero_assign_scope_variables_external_const,
+            is_zero_nondet, is_zero_pack, is_zero_pack_external_secp, reduce_v1, reduce_v2,
+            verify_zero, verify_zero_with_external_const,
+        },
+        secp_utils::{ALPHA, ALPHA_V2, SECP256R1_P, SECP_P, SECP_P_V2},
+        signature::{
+            div_mod_n_packed_divmod, div_mod_n_packed_external_n, div_mod_n_safe_div_with_k,
+            get_point_from_x, pack_modn_div_modn,
+        },
+    },
+};
+
+#[cfg(feature = "hints-sha256")]
+use crate::hint_processor::builtin_hint_processor::sha256_utils::{
+    sha256_finalize, sha256_input, sha256_main_arbitrary_input_length,
+    sha256_main_constant_input_length,
+};
 
 pub struct HintProcessorData {
     pub code: String,
     pub ap_tracking: ApTracking,
     pub ids_data: HashMap<String, HintReference>,
+    /// Scopes accessible from the hint, from outermost to innermost
+    pub accessible_scopes: Vec<String>,
 }
 
 impl HintProcessorData {
@@ -133,6 +153,7 @@ impl HintProcessorData {
             code,
             ap_tracking: ApTracking::default(),
             ids_data,
+            accessible_scopes: Vec::new(),
         }
     }
 }
@@ -150,42 +171,192 @@ pub struct HintFunc(
             + Sync,
     >,
 );
+/// Strips the trailing whitespace of each line (and trailing blank lines) from a hint's code,
+/// so that hints emitted with different formatting are matched as the same hint.
+pub fn normalize_hint_code(hint_code: &str) -> String {
+    let normalized = hint_code
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    normalized.trim_end().to_string()
+}
+
+/// Prefix of the hint codes given to closures by [`BuiltinHintProcessor::add_rust_closure_hint`]
+pub const RUST_CLOSURE_HINT_PREFIX: &str = "__rust_closure_";
+
+type BeforeHintHook = Rc<dyn Fn(&str)>;
+type AfterHintHook = Rc<dyn Fn(&str, &Result<(), HintError>)>;
+
 pub struct BuiltinHintProcessor {
     pub extra_hints: HashMap<String, Rc<HintFunc>>,
     run_resources: RunResources,
+    before_hint: Option<BeforeHintHook>,
+    after_hint: Option<AfterHintHook>,
+    ec_points: Option<Vec<PrivateInput>>,
+    ec_trace: bool,
 }
 impl BuiltinHintProcessor {
     pub fn new_empty() -> Self {
         BuiltinHintProcessor {
             extra_hints: HashMap::new(),
             run_resources: RunResources::default(),
+            before_hint: None,
+            after_hint: None,
+            ec_points: None,
+            ec_trace: false,
         }
     }
 
     pub fn new(extra_hints: HashMap<String, Rc<HintFunc>>, run_resources: RunResources) -> Self {
         BuiltinHintProcessor {
-            extra_hints,
+            extra_hints: extra_hints
+                .into_iter()
+                .map(|(hint_code, hint_func)| (normalize_hint_code(&hint_code), hint_func))
+                .collect(),
             run_resources,
+            before_hint: None,
+            after_hint: None,
+            ec_points: None,
+            ec_trace: false,
         }
     }
 
     pub fn add_hint(&mut self, hint_code: String, hint_func: Rc<HintFunc>) {
-        self.extra_hints.insert(hint_code, hint_func);
+        self.extra_hints
+            .insert(normalize_hint_code(&hint_code), hint_func);
     }
-}
 
-impl HintProcessorLogic for BuiltinHintProcessor {
-    fn execute_hint(
+    /// Registers `hint_func` under a new code of the form `__rust_closure_<id>` and returns it.
+    /// Placing that code in a program's hints runs the closure at the hint's pc, which allows
+    /// tests and embedders to manipulate the vm between steps, e.g. to reproduce a bug report.
+    pub fn add_rust_closure_hint(&mut self, hint_func: Rc<HintFunc>) -> String {
+        let hint_code = (0..)
+            .map(|id| format!("{RUST_CLOSURE_HINT_PREFIX}{id}"))
+            .find(|code| !self.extra_hints.contains_key(code))
+            .expect("there is always an unused id");
+        self.extra_hints.insert(hint_code.clone(), hint_func);
+        hint_code
+    }
+
+    /// Sets functions to be called with the code of every hint right before it is executed,
+    /// and with its code and result right after. Useful for tracing or profiling hints.
+    pub fn with_hooks(
+        mut self,
+        before: impl Fn(&str) + 'static,
+        after: impl Fn(&str, &Result<(), HintError>) + 'static,
+    ) -> Self {
+        self.before_hint = Some(Rc::new(before));
+        self.after_hint = Some(Rc::new(after));
+        self
+    }
+
+    /// Enables the collection of the secp points consumed by the EC slope hints, so that they
+    /// can be added to the air private input.
+    pub fn with_ec_point_collection(mut self) -> Self {
+        self.ec_points = Some(Vec::new());
+        self
+    }
+
+    /// Returns the points collected so far, or None if the collection is disabled.
+    /// Use them as the [`SECP_EC_POINTS_NAME`](crate::air_private_input::SECP_EC_POINTS_NAME)
+    /// entry of the air private input.
+    pub fn collected_ec_points(&self) -> Option<&[PrivateInput]> {
+        self.ec_points.as_deref()
+    }
+
+    /// Makes the secp hints append every slope, new_x and new_y they compute, in order, to the
+    /// `ec_trace` scope variable (a `Vec<BigInt>`), which is created on the first append.
+    /// Useful to dump a full computation trace when building reference test vectors.
+    pub fn with_ec_trace(mut self) -> Self {
+        self.ec_trace = true;
+        self
+    }
+
+    fn record_ec_trace(
+        &self,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &HintProcessorData,
+    ) -> Result<(), HintError> {
+        if !self.ec_trace {
+            return Ok(());
+        }
+        let name = match &*hint_data.code {
+            hint_code::EC_DOUBLE_SLOPE_V1
+            | hint_code::EC_DOUBLE_SLOPE_V2
+            | hint_code::EC_DOUBLE_SLOPE_V3
+            | hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS
+            | hint_code::EC_DOUBLE_SLOPE_SECP256R1 => "slope",
+            hint_code::COMPUTE_SLOPE_V1
+            | hint_code::COMPUTE_SLOPE_V2
+            | hint_code::COMPUTE_SLOPE_SECP256R1
+            | hint_code::COMPUTE_SLOPE_WHITELIST => {
+                // No slope is computed when one of the points is the point at infinity
+                if exec_scopes.get::<Felt252>("has_infinity")? == Felt252::ONE {
+                    return Ok(());
+                }
+                "slope"
+            }
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_V1
+            | hint_code::EC_DOUBLE_ASSIGN_NEW_X_V2
+            | hint_code::EC_DOUBLE_ASSIGN_NEW_X_V3
+            | hint_code::EC_DOUBLE_ASSIGN_NEW_X_V4
+            | hint_code::EC_DOUBLE_ASSIGN_NEW_X_SECP256R1
+            | hint_code::FAST_EC_ADD_ASSIGN_NEW_X
+            | hint_code::FAST_EC_ADD_ASSIGN_NEW_X_V2
+            | hint_code::FAST_EC_ADD_ASSIGN_NEW_X_V3
+            | hint_code::SQUARE_SLOPE_X_MOD_P => "new_x",
+            hint_code::EC_DOUBLE_ASSIGN_NEW_Y | hint_code::FAST_EC_ADD_ASSIGN_NEW_Y => "new_y",
+            _ => return Ok(()),
+        };
+        let value: BigInt = exec_scopes.get(name)?;
+        if exec_scopes.get_local_variables()?.contains_key("ec_trace") {
+            exec_scopes
+                .get_mut_list_ref::<BigInt>("ec_trace")?
+                .push(value);
+        } else {
+            exec_scopes.insert_value("ec_trace", vec![value]);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "hints-secp")]
+    fn collect_ec_points(
+        &mut self,
+        vm: &VirtualMachine,
+        hint_data: &HintProcessorData,
+    ) -> Result<(), HintError> {
+        let Some(collector) = self.ec_points.as_mut() else {
+            return Ok(());
+        };
+        let point_names: &[&str] = match &*hint_data.code {
+            hint_code::COMPUTE_SLOPE_V1
+            | hint_code::COMPUTE_SLOPE_V2
+            | hint_code::COMPUTE_SLOPE_SECP256R1 => &["point0", "point1"],
+            hint_code::COMPUTE_SLOPE_WHITELIST => &["pt0", "pt1"],
+            hint_code::EC_DOUBLE_SLOPE_V1
+            | hint_code::EC_DOUBLE_SLOPE_V2
+            | hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS
+            | hint_code::EC_DOUBLE_SLOPE_SECP256R1 => &["point"],
+            hint_code::EC_DOUBLE_SLOPE_V3 => &["pt"],
+            _ => return Ok(()),
+        };
+        collect_ec_points(
+            point_names,
+            vm,
+            &hint_data.ids_data,
+            &hint_data.ap_tracking,
+            collector,
+        )
+    }
+
+    fn execute_builtin_hint(
         &mut self,
         vm: &mut VirtualMachine,
         exec_scopes: &mut ExecutionScopes,
-        hint_data: &Box<dyn Any>,
+        hint_data: &HintProcessorData,
         constants: &HashMap<String, Felt252>,
     ) -> Result<(), HintError> {
-        let hint_data = hint_data
-            .downcast_ref::<HintProcessorData>()
-            .ok_or(HintError::WrongHintData)?;
-
         if let Some(hint_func) = self.extra_hints.get(&hint_data.code) {
             return hint_func.0(
                 vm,
@@ -196,7 +367,9 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             );
         }
         match &*hint_data.code {
-            hint_code::ADD_SEGMENT => add_segment(vm),
+            hint_code::ADD_SEGMENT | hint_code::ADD_SEGMENT_TO_FELT_OR_RELOCATABLE => {
+                add_segment(vm)
+            }
             hint_code::IS_NN => is_nn(vm, &hint_data.ids_data, &hint_data.ap_tracking),
             hint_code::IS_NN_OUT_OF_RANGE => {
                 is_nn_out_of_range(vm, &hint_data.ids_data, &hint_data.ap_tracking)
@@ -212,9 +385,13 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::ASSERT_LE_FELT_EXCLUDED_1 => assert_le_felt_excluded_1(vm, exec_scopes),
             hint_code::ASSERT_LE_FELT_EXCLUDED_0 => assert_le_felt_excluded_0(vm, exec_scopes),
             hint_code::IS_LE_FELT => is_le_felt(vm, &hint_data.ids_data, &hint_data.ap_tracking),
-            hint_code::ASSERT_250_BITS => {
-                assert_250_bit(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
+            hint_code::ASSERT_250_BITS => assert_250_bit(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
             hint_code::IS_250_BITS => is_250_bits(vm, &hint_data.ids_data, &hint_data.ap_tracking),
             hint_code::IS_ADDR_BOUNDED => {
                 is_addr_bounded(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
@@ -256,9 +433,13 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 "continue_loop",
             ),
-            hint_code::SPLIT_FELT => {
-                split_felt(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
+            hint_code::SPLIT_FELT => split_felt(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
             hint_code::UNSIGNED_DIV_REM => {
                 unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -276,16 +457,21 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             }
             hint_code::POW => pow(vm, &hint_data.ids_data, &hint_data.ap_tracking),
             hint_code::SET_ADD => set_add(vm, &hint_data.ids_data, &hint_data.ap_tracking),
+            #[cfg(feature = "hints-dict")]
             hint_code::DICT_NEW => dict_new(vm, exec_scopes),
+            #[cfg(feature = "hints-dict")]
             hint_code::DICT_READ => {
                 dict_read(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::DICT_WRITE => {
                 dict_write(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::DEFAULT_DICT_NEW => {
                 default_dict_new(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_FIRST_ITERATION => squash_dict_inner_first_iteration(
                 vm,
                 exec_scopes,
@@ -306,9 +492,11 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-blake2s")]
             hint_code::BLAKE2S_COMPUTE => {
                 compute_blake2s(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::VERIFY_ZERO_V1 | hint_code::VERIFY_ZERO_V2 => verify_zero(
                 vm,
                 exec_scopes,
@@ -316,6 +504,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 &SECP_P,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::VERIFY_ZERO_V3 => verify_zero(
                 vm,
                 exec_scopes,
@@ -323,6 +512,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 &SECP_P_V2,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::VERIFY_ZERO_EXTERNAL_SECP => verify_zero_with_external_const(
                 vm,
                 exec_scopes,
@@ -332,39 +522,49 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::NONDET_BIGINT3_V1 | hint_code::NONDET_BIGINT3_V2 => {
                 nondet_bigint3(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::REDUCE_V1 => {
                 reduce_v1(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::REDUCE_V2 => {
                 reduce_v2(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
             hint_code::REDUCE_ED25519 => {
                 ed25519_reduce(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-blake2s")]
             hint_code::BLAKE2S_FINALIZE | hint_code::BLAKE2S_FINALIZE_V2 => {
                 finalize_blake2s(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-blake2s")]
             hint_code::BLAKE2S_FINALIZE_V3 => {
                 finalize_blake2s_v3(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-blake2s")]
             hint_code::BLAKE2S_ADD_UINT256 => {
                 blake2s_add_uint256(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-blake2s")]
             hint_code::BLAKE2S_ADD_UINT256_BIGEND => {
                 blake2s_add_uint256_bigend(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::UNSAFE_KECCAK => {
                 unsafe_keccak(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::UNSAFE_KECCAK_FINALIZE => {
                 unsafe_keccak_finalize(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_SKIP_LOOP => squash_dict_inner_skip_loop(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_CHECK_ACCESS_INDEX => {
                 squash_dict_inner_check_access_index(
                     vm,
@@ -373,16 +573,20 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                     &hint_data.ap_tracking,
                 )
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_CONTINUE_LOOP => squash_dict_inner_continue_loop(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_ASSERT_LEN_KEYS => {
                 squash_dict_inner_assert_len_keys(exec_scopes)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_LEN_ASSERT => squash_dict_inner_len_assert(exec_scopes),
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_USED_ACCESSES_ASSERT => {
                 squash_dict_inner_used_accesses_assert(
                     vm,
@@ -391,22 +595,27 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                     &hint_data.ap_tracking,
                 )
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT_INNER_NEXT_KEY => squash_dict_inner_next_key(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-dict")]
             hint_code::SQUASH_DICT => {
                 squash_dict(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
             hint_code::VM_ENTER_SCOPE => enter_scope(exec_scopes),
+            #[cfg(feature = "hints-dict")]
             hint_code::DICT_UPDATE => {
                 dict_update(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::DICT_SQUASH_COPY_DICT => {
                 dict_squash_copy_dict(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-dict")]
             hint_code::DICT_SQUASH_UPDATE_PTR => {
                 dict_squash_update_ptr(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -437,10 +646,13 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::BIGINT_TO_UINT256 => {
                 bigint_to_uint256(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::IS_ZERO_PACK_V1 | hint_code::IS_ZERO_PACK_V2 => {
                 is_zero_pack(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::IS_ZERO_NONDET | hint_code::IS_ZERO_INT => is_zero_nondet(vm, exec_scopes),
+            #[cfg(feature = "hints-secp")]
             hint_code::IS_ZERO_PACK_EXTERNAL_SECP_V1 | hint_code::IS_ZERO_PACK_EXTERNAL_SECP_V2 => {
                 is_zero_pack_external_secp(
                     vm,
@@ -452,13 +664,16 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::IS_ZERO_PACK_ED25519 => {
                 ed25519_is_zero_pack(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::IS_ZERO_ASSIGN_SCOPE_VARS => is_zero_assign_scope_variables(exec_scopes),
+            #[cfg(feature = "hints-secp")]
             hint_code::IS_ZERO_ASSIGN_SCOPE_VARS_EXTERNAL_SECP => {
                 is_zero_assign_scope_variables_external_const(exec_scopes)
             }
             hint_code::IS_ZERO_ASSIGN_SCOPE_VARS_ED25519 => {
                 ed25519_is_zero_assign_scope_vars(exec_scopes)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::DIV_MOD_N_PACKED_DIVMOD_V1 => div_mod_n_packed_divmod(
                 vm,
                 exec_scopes,
@@ -477,14 +692,22 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::BIGINT_SAFE_DIV => {
                 bigint_safe_div_hint(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::DIV_MOD_N_PACKED_DIVMOD_EXTERNAL_N => div_mod_n_packed_external_n(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
-            hint_code::DIV_MOD_N_SAFE_DIV => div_mod_n_safe_div(exec_scopes, "a", "b", 0),
-            hint_code::DIV_MOD_N_SAFE_DIV_PLUS_ONE => div_mod_n_safe_div(exec_scopes, "a", "b", 1),
+            #[cfg(feature = "hints-secp")]
+            hint_code::DIV_MOD_N_SAFE_DIV => {
+                div_mod_n_safe_div_with_k(exec_scopes, "a", "b", "k", 0)
+            }
+            #[cfg(feature = "hints-secp")]
+            hint_code::DIV_MOD_N_SAFE_DIV_PLUS_ONE => {
+                div_mod_n_safe_div_with_k(exec_scopes, "a", "b", "k_plus_one", 1)
+            }
+            #[cfg(feature = "hints-secp")]
             hint_code::GET_POINT_FROM_X => get_point_from_x(
                 vm,
                 exec_scopes,
@@ -492,18 +715,32 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 constants,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_NEGATE => ec_negate_import_secp_p(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_NEGATE_EMBEDDED_SECP => ec_negate_embedded_secp_p(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-secp")]
+            hint_code::EC_NEGATE_SECP256R1 => {
+                ec_negate_secp256r1(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
+            #[cfg(feature = "hints-secp")]
+            hint_code::EC_DOUBLE_SLOPE_SECP256R1 => compute_doubling_slope_secp256r1(
+                vm,
+                exec_scopes,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+            ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_SLOPE_V1 => compute_doubling_slope(
                 vm,
                 exec_scopes,
@@ -513,6 +750,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &SECP_P,
                 &ALPHA,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_SLOPE_V2 => compute_doubling_slope(
                 vm,
                 exec_scopes,
@@ -522,6 +760,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &SECP_P_V2,
                 &ALPHA_V2,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_SLOPE_V3 => compute_doubling_slope(
                 vm,
                 exec_scopes,
@@ -531,12 +770,14 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &SECP_P,
                 &ALPHA,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS => compute_doubling_slope_external_consts(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::COMPUTE_SLOPE_V1 => compute_slope_and_assing_secp_p(
                 vm,
                 exec_scopes,
@@ -546,9 +787,11 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "point1",
                 &SECP_P,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::SQUARE_SLOPE_X_MOD_P => {
                 square_slope_minus_xs(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::COMPUTE_SLOPE_V2 => compute_slope_and_assing_secp_p(
                 vm,
                 exec_scopes,
@@ -558,6 +801,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "point1",
                 &SECP_P_V2,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::COMPUTE_SLOPE_SECP256R1 => compute_slope(
                 vm,
                 exec_scopes,
@@ -566,7 +810,9 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "point0",
                 "point1",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::IMPORT_SECP256R1_P => import_secp256r1_p(exec_scopes),
+            #[cfg(feature = "hints-secp")]
             hint_code::COMPUTE_SLOPE_WHITELIST => compute_slope_and_assing_secp_p(
                 vm,
                 exec_scopes,
@@ -576,6 +822,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "pt1",
                 &SECP_P,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_ASSIGN_NEW_X_V1 => ec_double_assign_new_x(
                 vm,
                 exec_scopes,
@@ -584,6 +831,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &SECP_P,
                 "point",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_ASSIGN_NEW_X_V2 => ec_double_assign_new_x_v2(
                 vm,
                 exec_scopes,
@@ -591,6 +839,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 "point",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_ASSIGN_NEW_X_V3 => ec_double_assign_new_x(
                 vm,
                 exec_scopes,
@@ -599,6 +848,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &SECP_P_V2,
                 "point",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_ASSIGN_NEW_X_V4 => ec_double_assign_new_x(
                 vm,
                 exec_scopes,
@@ -607,57 +857,102 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 &SECP_P,
                 "pt",
             ),
+            #[cfg(feature = "hints-secp")]
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_SECP256R1 => ec_double_assign_new_x(
+                vm,
+                exec_scopes,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                &SECP256R1_P,
+                "point",
+            ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_DOUBLE_ASSIGN_NEW_Y => ec_double_assign_new_y(exec_scopes),
+            #[cfg(feature = "hints-keccak")]
             hint_code::KECCAK_WRITE_ARGS => {
                 keccak_write_args(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::COMPARE_BYTES_IN_WORD_NONDET => compare_bytes_in_word_nondet(
                 vm,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
                 constants,
+                &hint_data.accessible_scopes,
             ),
+            #[cfg(feature = "hints-sha256")]
             hint_code::SHA256_MAIN_CONSTANT_INPUT_LENGTH => sha256_main_constant_input_length(
                 vm,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
                 constants,
+                &hint_data.accessible_scopes,
             ),
+            #[cfg(feature = "hints-sha256")]
             hint_code::SHA256_MAIN_ARBITRARY_INPUT_LENGTH => sha256_main_arbitrary_input_length(
                 vm,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
                 constants,
+                &hint_data.accessible_scopes,
             ),
+            #[cfg(feature = "hints-sha256")]
             hint_code::SHA256_INPUT => {
                 sha256_input(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-sha256")]
             hint_code::SHA256_FINALIZE => {
                 sha256_finalize(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::CAIRO_KECCAK_INPUT_IS_FULL_WORD => {
                 cairo_keccak_is_full_word(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::COMPARE_KECCAK_FULL_RATE_IN_BYTES_NONDET => {
                 compare_keccak_full_rate_in_bytes_nondet(
                     vm,
                     &hint_data.ids_data,
                     &hint_data.ap_tracking,
                     constants,
+                    &hint_data.accessible_scopes,
                 )
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::BLOCK_PERMUTATION | hint_code::BLOCK_PERMUTATION_WHITELIST_V1 => {
-                block_permutation_v1(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
-            hint_code::BLOCK_PERMUTATION_WHITELIST_V2 => {
-                block_permutation_v2(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
-            hint_code::CAIRO_KECCAK_FINALIZE_V1 => {
-                cairo_keccak_finalize_v1(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
-            hint_code::CAIRO_KECCAK_FINALIZE_V2 => {
-                cairo_keccak_finalize_v2(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
+                block_permutation_v1(
+                    vm,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    constants,
+                    &hint_data.accessible_scopes,
+                )
             }
+            #[cfg(feature = "hints-keccak")]
+            hint_code::BLOCK_PERMUTATION_WHITELIST_V2 => block_permutation_v2(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
+            #[cfg(feature = "hints-keccak")]
+            hint_code::CAIRO_KECCAK_FINALIZE_V1 => cairo_keccak_finalize_v1(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
+            #[cfg(feature = "hints-keccak")]
+            hint_code::CAIRO_KECCAK_FINALIZE_V2 => cairo_keccak_finalize_v2(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
+            #[cfg(feature = "hints-secp")]
             hint_code::FAST_EC_ADD_ASSIGN_NEW_X => fast_ec_add_assign_new_x(
                 vm,
                 exec_scopes,
@@ -667,6 +962,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "point0",
                 "point1",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::FAST_EC_ADD_ASSIGN_NEW_X_V2 => fast_ec_add_assign_new_x(
                 vm,
                 exec_scopes,
@@ -676,6 +972,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "point0",
                 "point1",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::FAST_EC_ADD_ASSIGN_NEW_X_V3 => fast_ec_add_assign_new_x(
                 vm,
                 exec_scopes,
@@ -685,9 +982,11 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 "pt0",
                 "pt1",
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::FAST_EC_ADD_ASSIGN_NEW_Y => fast_ec_add_assign_new_y(exec_scopes),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_MUL_INNER => {
-                ec_mul_inner(vm, &hint_data.ids_data, &hint_data.ap_tracking)
+                ec_mul_inner_packed(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
             hint_code::RELOCATE_SEGMENT => {
                 relocate_segment(vm, &hint_data.ids_data, &hint_data.ap_tracking)
@@ -698,30 +997,43 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::VERIFY_ECDSA_SIGNATURE => {
                 verify_ecdsa_signature(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_OUTPUT_0 => {
                 split_output(vm, &hint_data.ids_data, &hint_data.ap_tracking, 0)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_OUTPUT_1 => {
                 split_output(vm, &hint_data.ids_data, &hint_data.ap_tracking, 1)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_INPUT_3 => {
                 split_input(vm, &hint_data.ids_data, &hint_data.ap_tracking, 3, 1)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_INPUT_6 => {
                 split_input(vm, &hint_data.ids_data, &hint_data.ap_tracking, 6, 2)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_INPUT_9 => {
                 split_input(vm, &hint_data.ids_data, &hint_data.ap_tracking, 9, 3)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_INPUT_12 => {
                 split_input(vm, &hint_data.ids_data, &hint_data.ap_tracking, 12, 4)
             }
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_INPUT_15 => {
                 split_input(vm, &hint_data.ids_data, &hint_data.ap_tracking, 15, 5)
             }
-            hint_code::SPLIT_N_BYTES => {
-                split_n_bytes(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
+            #[cfg(feature = "hints-keccak")]
+            hint_code::SPLIT_N_BYTES => split_n_bytes(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
+            #[cfg(feature = "hints-keccak")]
             hint_code::SPLIT_OUTPUT_MID_LOW_HIGH => {
                 split_output_mid_low_high(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -744,19 +1056,25 @@ impl HintProcessorLogic for BuiltinHintProcessor {
                 chained_ec_op_random_ec_point_hint(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
             hint_code::RECOVER_Y => recover_y_hint(vm, &hint_data.ids_data, &hint_data.ap_tracking),
+            #[cfg(feature = "hints-secp")]
             hint_code::PACK_MODN_DIV_MODN => {
                 pack_modn_div_modn(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
-            hint_code::XS_SAFE_DIV => div_mod_n_safe_div(exec_scopes, "x", "s", 0),
+            #[cfg(feature = "hints-secp")]
+            hint_code::XS_SAFE_DIV => div_mod_n_safe_div_with_k(exec_scopes, "x", "s", "k", 0),
             hint_code::UINT384_UNSIGNED_DIV_REM => {
                 uint384_unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
             hint_code::UINT384_SPLIT_128 => {
                 uint384_split_128(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
-            hint_code::ADD_NO_UINT384_CHECK => {
-                add_no_uint384_check(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
-            }
+            hint_code::ADD_NO_UINT384_CHECK => add_no_uint384_check(
+                vm,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+                constants,
+                &hint_data.accessible_scopes,
+            ),
             hint_code::UINT384_SQRT => {
                 uint384_sqrt(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -780,7 +1098,9 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::UINT256_MUL_DIV_MOD => {
                 uint256_mul_div_mod(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::IMPORT_SECP256R1_ALPHA => import_secp256r1_alpha(exec_scopes),
+            #[cfg(feature = "hints-secp")]
             hint_code::IMPORT_SECP256R1_N => import_secp256r1_n(exec_scopes),
             hint_code::UINT512_UNSIGNED_DIV_REM => {
                 uint512_unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
@@ -788,6 +1108,7 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::HI_MAX_BITLEN => {
                 hi_max_bitlen(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::QUAD_BIT => quad_bit(vm, &hint_data.ids_data, &hint_data.ap_tracking),
             hint_code::INV_MOD_P_UINT256 => {
                 inv_mod_p_uint256(vm, &hint_data.ids_data, &hint_data.ap_tracking)
@@ -795,16 +1116,20 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::INV_MOD_P_UINT512 => {
                 inv_mod_p_uint512(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::DI_BIT => di_bit(vm, &hint_data.ids_data, &hint_data.ap_tracking),
+            #[cfg(feature = "hints-blake2s")]
             hint_code::EXAMPLE_BLAKE2S_COMPRESS => {
                 example_blake2s_compress(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_RECOVER_DIV_MOD_N_PACKED => ec_recover_divmod_n_packed(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
             ),
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_RECOVER_SUB_A_B => {
                 ec_recover_sub_a_b(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -817,11 +1142,25 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::ASSERT_LE_FELT_V_0_8 => {
                 assert_le_felt_v_0_8(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_RECOVER_PRODUCT_MOD => {
                 ec_recover_product_mod(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            #[cfg(feature = "hints-secp")]
             hint_code::EC_RECOVER_PRODUCT_DIV_M => ec_recover_product_div_m(exec_scopes),
             hint_code::SPLIT_XX => split_xx(vm, &hint_data.ids_data, &hint_data.ap_tracking),
+            hint_code::EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT => allocate_program_data_segment(
+                vm,
+                exec_scopes,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+            ),
+            hint_code::EXECUTE_TASK_LOAD_PROGRAM => {
+                load_program(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
+            hint_code::EXECUTE_TASK_VALIDATE_HASH => {
+                validate_program_hash(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
             #[cfg(feature = "skip_next_instruction_hint")]
             hint_code::SKIP_NEXT_INSTRUCTION => skip_next_instruction(vm),
             #[cfg(feature = "print")]
@@ -832,11 +1171,211 @@ impl HintProcessorLogic for BuiltinHintProcessor {
             hint_code::PRINT_DICT => {
                 print_dict(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
-            code => Err(HintError::UnknownHint(code.to_string().into_boxed_str())),
+            #[cfg(feature = "print")]
+            hint_code::PRINT_LOCALS => print_locals(exec_scopes),
+            code => match parse_add_segment_hint(code) {
+                Some((var_name, temporary)) => add_segment_into_var(
+                    var_name,
+                    temporary,
+                    vm,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                ),
+                None => Err(unknown_hint_error(code)),
+            },
         }
     }
 }
 
+/// Builtin hints that are only compiled in when their hint family feature is enabled, along with
+/// the feature and whether it is enabled in this build
+const FEATURE_GATED_HINTS: &[(&str, bool, &[&str])] = &[
+    (
+        "hints-keccak",
+        cfg!(feature = "hints-keccak"),
+        &[
+            hint_code::UNSAFE_KECCAK,
+            hint_code::UNSAFE_KECCAK_FINALIZE,
+            hint_code::KECCAK_WRITE_ARGS,
+            hint_code::COMPARE_BYTES_IN_WORD_NONDET,
+            hint_code::CAIRO_KECCAK_INPUT_IS_FULL_WORD,
+            hint_code::COMPARE_KECCAK_FULL_RATE_IN_BYTES_NONDET,
+            hint_code::BLOCK_PERMUTATION,
+            hint_code::BLOCK_PERMUTATION_WHITELIST_V1,
+            hint_code::BLOCK_PERMUTATION_WHITELIST_V2,
+            hint_code::CAIRO_KECCAK_FINALIZE_V1,
+            hint_code::CAIRO_KECCAK_FINALIZE_V2,
+            hint_code::SPLIT_OUTPUT_0,
+            hint_code::SPLIT_OUTPUT_1,
+            hint_code::SPLIT_INPUT_3,
+            hint_code::SPLIT_INPUT_6,
+            hint_code::SPLIT_INPUT_9,
+            hint_code::SPLIT_INPUT_12,
+            hint_code::SPLIT_INPUT_15,
+            hint_code::SPLIT_N_BYTES,
+            hint_code::SPLIT_OUTPUT_MID_LOW_HIGH,
+        ],
+    ),
+    (
+        "hints-sha256",
+        cfg!(feature = "hints-sha256"),
+        &[
+            hint_code::SHA256_MAIN_CONSTANT_INPUT_LENGTH,
+            hint_code::SHA256_MAIN_ARBITRARY_INPUT_LENGTH,
+            hint_code::SHA256_INPUT,
+            hint_code::SHA256_FINALIZE,
+        ],
+    ),
+    (
+        "hints-blake2s",
+        cfg!(feature = "hints-blake2s"),
+        &[
+            hint_code::BLAKE2S_COMPUTE,
+            hint_code::BLAKE2S_FINALIZE,
+            hint_code::BLAKE2S_FINALIZE_V2,
+            hint_code::BLAKE2S_FINALIZE_V3,
+            hint_code::BLAKE2S_ADD_UINT256,
+            hint_code::BLAKE2S_ADD_UINT256_BIGEND,
+            hint_code::EXAMPLE_BLAKE2S_COMPRESS,
+        ],
+    ),
+    (
+        "hints-secp",
+        cfg!(feature = "hints-secp"),
+        &[
+            hint_code::VERIFY_ZERO_V1,
+            hint_code::VERIFY_ZERO_V2,
+            hint_code::VERIFY_ZERO_V3,
+            hint_code::VERIFY_ZERO_EXTERNAL_SECP,
+            hint_code::REDUCE_V1,
+            hint_code::REDUCE_V2,
+            hint_code::IS_ZERO_PACK_V1,
+            hint_code::IS_ZERO_PACK_V2,
+            hint_code::IS_ZERO_NONDET,
+            hint_code::IS_ZERO_INT,
+            hint_code::IS_ZERO_PACK_EXTERNAL_SECP_V1,
+            hint_code::IS_ZERO_PACK_EXTERNAL_SECP_V2,
+            hint_code::IS_ZERO_ASSIGN_SCOPE_VARS,
+            hint_code::IS_ZERO_ASSIGN_SCOPE_VARS_EXTERNAL_SECP,
+            hint_code::DIV_MOD_N_PACKED_DIVMOD_V1,
+            hint_code::DIV_MOD_N_PACKED_DIVMOD_EXTERNAL_N,
+            hint_code::DIV_MOD_N_SAFE_DIV,
+            hint_code::DIV_MOD_N_SAFE_DIV_PLUS_ONE,
+            hint_code::GET_POINT_FROM_X,
+            hint_code::EC_NEGATE,
+            hint_code::EC_NEGATE_EMBEDDED_SECP,
+            hint_code::EC_NEGATE_SECP256R1,
+            hint_code::EC_DOUBLE_SLOPE_V1,
+            hint_code::EC_DOUBLE_SLOPE_V2,
+            hint_code::EC_DOUBLE_SLOPE_V3,
+            hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS,
+            hint_code::EC_DOUBLE_SLOPE_SECP256R1,
+            hint_code::COMPUTE_SLOPE_V1,
+            hint_code::SQUARE_SLOPE_X_MOD_P,
+            hint_code::COMPUTE_SLOPE_V2,
+            hint_code::COMPUTE_SLOPE_SECP256R1,
+            hint_code::IMPORT_SECP256R1_P,
+            hint_code::COMPUTE_SLOPE_WHITELIST,
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_V1,
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_V2,
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_V3,
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_V4,
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_SECP256R1,
+            hint_code::EC_DOUBLE_ASSIGN_NEW_Y,
+            hint_code::FAST_EC_ADD_ASSIGN_NEW_X,
+            hint_code::FAST_EC_ADD_ASSIGN_NEW_X_V2,
+            hint_code::FAST_EC_ADD_ASSIGN_NEW_X_V3,
+            hint_code::FAST_EC_ADD_ASSIGN_NEW_Y,
+            hint_code::EC_MUL_INNER,
+            hint_code::PACK_MODN_DIV_MODN,
+            hint_code::XS_SAFE_DIV,
+            hint_code::IMPORT_SECP256R1_ALPHA,
+            hint_code::IMPORT_SECP256R1_N,
+            hint_code::QUAD_BIT,
+            hint_code::DI_BIT,
+            hint_code::EC_RECOVER_DIV_MOD_N_PACKED,
+            hint_code::EC_RECOVER_SUB_A_B,
+            hint_code::EC_RECOVER_PRODUCT_MOD,
+            hint_code::EC_RECOVER_PRODUCT_DIV_M,
+        ],
+    ),
+    (
+        "hints-dict",
+        cfg!(feature = "hints-dict"),
+        &[
+            hint_code::DICT_NEW,
+            hint_code::DICT_READ,
+            hint_code::DICT_WRITE,
+            hint_code::DEFAULT_DICT_NEW,
+            hint_code::SQUASH_DICT_INNER_FIRST_ITERATION,
+            hint_code::SQUASH_DICT_INNER_SKIP_LOOP,
+            hint_code::SQUASH_DICT_INNER_CHECK_ACCESS_INDEX,
+            hint_code::SQUASH_DICT_INNER_CONTINUE_LOOP,
+            hint_code::SQUASH_DICT_INNER_ASSERT_LEN_KEYS,
+            hint_code::SQUASH_DICT_INNER_LEN_ASSERT,
+            hint_code::SQUASH_DICT_INNER_USED_ACCESSES_ASSERT,
+            hint_code::SQUASH_DICT_INNER_NEXT_KEY,
+            hint_code::SQUASH_DICT,
+            hint_code::DICT_UPDATE,
+            hint_code::DICT_SQUASH_COPY_DICT,
+            hint_code::DICT_SQUASH_UPDATE_PTR,
+        ],
+    ),
+];
+
+/// Tells apart hints that are builtin but were compiled out from the truly unknown ones
+fn unknown_hint_error(code: &str) -> HintError {
+    match FEATURE_GATED_HINTS
+        .iter()
+        .find(|(_, enabled, codes)| !enabled && codes.contains(&code))
+    {
+        Some((feature, _, _)) => HintError::HintFeatureDisabled(Box::new((code.into(), feature))),
+        None => HintError::UnknownHint(code.to_string().into_boxed_str()),
+    }
+}
+
+impl HintProcessorLogic for BuiltinHintProcessor {
+    fn compile_hint(
+        &self,
+        hint_code: &str,
+        ap_tracking_data: &ApTracking,
+        reference_ids: &HashMap<String, usize>,
+        references: &[HintReference],
+        accessible_scopes: &[String],
+    ) -> Result<Box<dyn Any>, VirtualMachineError> {
+        Ok(any_box!(HintProcessorData {
+            code: normalize_hint_code(hint_code),
+            ap_tracking: ap_tracking_data.clone(),
+            ids_data: get_ids_data(reference_ids, references, accessible_scopes)?,
+            accessible_scopes: accessible_scopes.to_vec(),
+        }))
+    }
+
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &Box<dyn Any>,
+        constants: &HashMap<String, Felt252>,
+    ) -> Result<(), HintError> {
+        let hint_data = hint_data
+            .downcast_ref::<HintProcessorData>()
+            .ok_or(HintError::WrongHintData)?;
+
+        if let Some(before_hint) = &self.before_hint {
+            before_hint(&hint_data.code);
+        }
+        let result = self.execute_builtin_hint(vm, exec_scopes, hint_data, constants);
+        #[cfg(feature = "hints-secp")]
+        let result = result.and_then(|_| self.collect_ec_points(vm, hint_data));
+        let result = result.and_then(|_| self.record_ec_trace(exec_scopes, hint_data));
+        if let Some(after_hint) = &self.after_hint {
+            after_hint(&hint_data.code, &result);
+        }
+        result
+    }
+}
+
 impl ResourceTracker for BuiltinHintProcessor {
     fn consume_step(&mut self) {
         self.run_resources.consume_step();
@@ -858,8 +1397,10 @@ impl ResourceTracker for BuiltinHintProcessor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::stdlib::any::Any;
+    use crate::serde::deserialize_program::{FlowTrackingData, HintParams};
+    use crate::stdlib::{any::Any, cell::RefCell};
     use crate::types::relocatable::Relocatable;
+    use crate::vm::runners::cairo_runner::CairoRunner;
 
     use crate::{
         any_box,
@@ -937,6 +1478,53 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn feature_disabled_hint_error_message() {
+        let err = HintError::HintFeatureDisabled(Box::new((
+            hint_code::SHA256_INPUT.into(),
+            "hints-sha256",
+        )));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Unknown Hint: {}\nThis is a builtin hint, enable the \"hints-sha256\" cairo-vm feature to run it",
+                hint_code::SHA256_INPUT
+            )
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg(not(feature = "hints-sha256"))]
+    fn run_hint_from_disabled_feature() {
+        let mut vm = vm!();
+        assert_matches!(
+            run_hint!(vm, HashMap::new(), hint_code::SHA256_INPUT),
+            Err(HintError::HintFeatureDisabled(bx))
+            if bx.0.as_ref() == hint_code::SHA256_INPUT && bx.1 == "hints-sha256"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn feature_gated_hints_are_dispatched_when_enabled() {
+        for (feature, enabled, codes) in FEATURE_GATED_HINTS {
+            for code in codes.iter() {
+                let mut vm = vm!();
+                let result = run_hint!(vm, HashMap::new(), code);
+                if *enabled {
+                    assert!(
+                        !matches!(result, Err(HintError::UnknownHint(_))),
+                        "{feature} hint not dispatched: {code}"
+                    );
+                } else {
+                    assert_matches!(result, Err(HintError::HintFeatureDisabled(_)));
+                }
+            }
+        }
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn memcpy_enter_scope_valid() {
@@ -1292,4 +1880,213 @@ mod tests {
         );
         assert_eq!(exec_scopes.data.len(), 3);
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn add_rust_closure_hint_runs_at_its_step() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        // Writes the current step right after the cell written by the next instruction
+        let hint_code =
+            hint_processor.add_rust_closure_hint(Rc::new(HintFunc(Box::new(|vm, _, _, _, _| {
+                let addr = (vm.get_ap() + 1)?;
+                vm.insert_value(addr, Felt252::from(vm.current_step))?;
+                Ok(())
+            }))));
+        assert_eq!(hint_code, "__rust_closure_0");
+        assert_eq!(
+            hint_processor.add_rust_closure_hint(Rc::new(HintFunc(Box::new(enter_scope)))),
+            "__rust_closure_1"
+        );
+
+        let hint = HintParams {
+            code: hint_code,
+            accessible_scopes: vec![],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids: HashMap::new(),
+            },
+        };
+        // [ap] = 1; ap++, [ap] = 2; ap++, [ap] = 3; ap++, ret
+        let program = program!(
+            data = vec_data!(
+                (5189976364521848832_i64),
+                (1),
+                (5189976364521848832_i64),
+                (2),
+                (5189976364521848832_i64),
+                (3),
+                (2345108766317314046_i64)
+            ),
+            hints = BTreeMap::from([(4, vec![hint])]),
+            main = Some(0),
+        );
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm, false).unwrap();
+        assert_matches!(
+            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+        // The hint ran before the third instruction, at step 2, when ap was (1, 4)
+        check_memory![vm.segments.memory, ((1, 4), 3), ((1, 5), 2)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn hint_hooks_called_for_ec_negate() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let (before_calls, after_calls) = (Rc::clone(&calls), Rc::clone(&calls));
+        let mut hint_processor = BuiltinHintProcessor::new_empty().with_hooks(
+            move |code| {
+                before_calls
+                    .borrow_mut()
+                    .push(("before", code.to_string(), None))
+            },
+            move |code, result| {
+                after_calls
+                    .borrow_mut()
+                    .push(("after", code.to_string(), Some(result.is_ok())))
+            },
+        );
+        let mut vm = vm_with_range_check!();
+        vm.segments = segments![((1, 3), 2645i32), ((1, 4), 454i32), ((1, 5), 206i32)];
+        vm.run_context.fp = 1;
+        let hint_data =
+            HintProcessorData::new_default(hint_code::EC_NEGATE.to_string(), ids_data!["point"]);
+        assert_matches!(
+            hint_processor.execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                ("before", hint_code::EC_NEGATE.to_string(), None),
+                ("after", hint_code::EC_NEGATE.to_string(), Some(true)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn hint_hooks_receive_hint_error() {
+        let results = Rc::new(RefCell::new(Vec::new()));
+        let after_results = Rc::clone(&results);
+        let mut hint_processor = BuiltinHintProcessor::new_empty().with_hooks(
+            |_| {},
+            move |_, result| {
+                after_results
+                    .borrow_mut()
+                    .push(matches!(result, Err(HintError::UnknownHint(_))))
+            },
+        );
+        let mut vm = vm!();
+        let hint_data = HintProcessorData::new_default(String::from("unknown"), HashMap::new());
+        assert!(hint_processor
+            .execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+            )
+            .is_err());
+        assert_eq!(*results.borrow(), vec![true]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn normalize_hint_code_strips_trailing_whitespace() {
+        assert_eq!(
+            normalize_hint_code("a = 1  \r\n\nb = 2\t\n\n"),
+            "a = 1\n\nb = 2"
+        );
+        assert_eq!(
+            normalize_hint_code(hint_code::REDUCE_V1),
+            hint_code::REDUCE_V1
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_hint_with_trailing_whitespace() {
+        let mut vm = vm!();
+        vm.run_context.fp = 1;
+        vm.segments = segments![((1, 0), 2), ((1, 1), 0), ((1, 2), 0)];
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let hint_data = hint_processor
+            .compile_hint(
+                &format!("{}  \n\n", hint_code::REDUCE_V1),
+                &ApTracking::default(),
+                &HashMap::from([("x".to_string(), 0)]),
+                &[HintReference::new_simple(-1)],
+                &[],
+            )
+            .unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            hint_processor.execute_hint(&mut vm, &mut exec_scopes, &hint_data, &HashMap::new()),
+            Ok(())
+        );
+        assert_matches!(
+            exec_scopes.get::<num_bigint::BigInt>("value"),
+            Ok(x) if x == num_bigint::BigInt::from(2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_assert_le_felt_with_trailing_whitespace() {
+        let constants = HashMap::from([
+            (
+                "starkware.cairo.common.math.assert_le_felt.PRIME_OVER_3_HIGH".to_string(),
+                crate::felt_hex!("4000000000000088000000000000001"),
+            ),
+            (
+                "starkware.cairo.common.math.assert_le_felt.PRIME_OVER_2_HIGH".to_string(),
+                crate::felt_hex!("2AAAAAAAAAAAAB05555555555555556"),
+            ),
+        ]);
+        let run = |code: &str| {
+            let mut vm = vm_with_range_check!();
+            vm.run_context.fp = 3;
+            vm.segments = segments![((1, 0), 1), ((1, 1), 2), ((1, 2), (2, 0))];
+            add_segments!(vm, 1);
+            let mut hint_processor = BuiltinHintProcessor::new_empty();
+            let hint_data = hint_processor
+                .compile_hint(
+                    code,
+                    &ApTracking::default(),
+                    &HashMap::from([
+                        ("a".to_string(), 0),
+                        ("b".to_string(), 1),
+                        ("range_check_ptr".to_string(), 2),
+                    ]),
+                    &[
+                        HintReference::new_simple(-3),
+                        HintReference::new_simple(-2),
+                        HintReference::new_simple(-1),
+                    ],
+                    &[],
+                )
+                .unwrap();
+            let mut exec_scopes = ExecutionScopes::new();
+            assert_matches!(
+                hint_processor.execute_hint(&mut vm, &mut exec_scopes, &hint_data, &constants),
+                Ok(())
+            );
+            (
+                vm.segments.memory.data,
+                exec_scopes.get::<Felt252>("excluded").unwrap(),
+            )
+        };
+        let trailing_whitespace = hint_code::ASSERT_LE_FELT
+            .lines()
+            .flat_map(|line| [line, "  \n"])
+            .collect::<String>();
+        assert_eq!(run(&trailing_whitespace), run(hint_code::ASSERT_LE_FELT));
+    }
 }
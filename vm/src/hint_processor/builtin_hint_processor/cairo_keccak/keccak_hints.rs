@@ -7,8 +7,8 @@ use crate::stdlib::{
 use crate::{
     hint_processor::{
         builtin_hint_processor::hint_utils::{
-            get_integer_from_var_name, get_ptr_from_var_name, insert_value_from_var_name,
-            insert_value_into_ap,
+            get_constant_from_var_name_or_paths, get_integer_from_var_name, get_ptr_from_var_name,
+            insert_value_from_var_name, insert_value_into_ap,
         },
         hint_processor_definition::HintReference,
     },
@@ -29,9 +29,6 @@ const KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK: &str =
     "starkware.cairo.common.cairo_keccak.keccak.KECCAK_FULL_RATE_IN_BYTES";
 const KECCAK_FULL_RATE_IN_BYTES_BUILTIN_KECCAK: &str =
     "starkware.cairo.common.builtin_keccak.keccak.KECCAK_FULL_RATE_IN_BYTES";
-
-const KECCAK_FULL_RATE_IN_BYTES: &str = "KECCAK_FULL_RATE_IN_BYTES";
-
 const KECCAK_STATE_SIZE_FELTS: &str =
     "starkware.cairo.common.cairo_keccak.keccak.KECCAK_STATE_SIZE_FELTS";
 
@@ -81,6 +78,7 @@ pub fn compare_bytes_in_word_nondet(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
     let n_bytes = get_integer_from_var_name("n_bytes", vm, ids_data, ap_tracking)?;
     let n_bytes = n_bytes.as_ref();
@@ -90,9 +88,12 @@ pub fn compare_bytes_in_word_nondet(
     // making value be 0 (if it can't convert then it's either negative, which can't be in Cairo memory
     // or too big, which also means n_bytes > BYTES_IN_WORD). The other option is to exctract
     // Felt252::from(BYTES_INTO_WORD) into a lazy_static!
-    let bytes_in_word = constants
-        .get(BYTES_IN_WORD)
-        .ok_or_else(|| HintError::MissingConstant(Box::new(BYTES_IN_WORD)))?;
+    let bytes_in_word = get_constant_from_var_name_or_paths(
+        "BYTES_IN_WORD",
+        &[BYTES_IN_WORD],
+        constants,
+        accessible_scopes,
+    )?;
     let value = Felt252::from((n_bytes < bytes_in_word) as usize);
     insert_value_into_ap(vm, value)
 }
@@ -110,14 +111,20 @@ pub fn compare_keccak_full_rate_in_bytes_nondet(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
     let n_bytes = get_integer_from_var_name("n_bytes", vm, ids_data, ap_tracking)?;
     let n_bytes = n_bytes.as_ref();
 
-    let keccak_full_rate_in_bytes = constants
-        .get(KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK)
-        .or_else(|| constants.get(KECCAK_FULL_RATE_IN_BYTES_BUILTIN_KECCAK))
-        .ok_or_else(|| HintError::MissingConstant(Box::new(KECCAK_FULL_RATE_IN_BYTES)))?;
+    let keccak_full_rate_in_bytes = get_constant_from_var_name_or_paths(
+        "KECCAK_FULL_RATE_IN_BYTES",
+        &[
+            KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK,
+            KECCAK_FULL_RATE_IN_BYTES_BUILTIN_KECCAK,
+        ],
+        constants,
+        accessible_scopes,
+    )?;
     let value = Felt252::from((n_bytes >= keccak_full_rate_in_bytes) as usize);
     insert_value_into_ap(vm, value)
 }
@@ -148,10 +155,14 @@ pub(crate) fn block_permutation_v1(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
-    let keccak_state_size_felts = constants
-        .get(KECCAK_STATE_SIZE_FELTS)
-        .ok_or_else(|| HintError::MissingConstant(Box::new(KECCAK_STATE_SIZE_FELTS)))?;
+    let keccak_state_size_felts = get_constant_from_var_name_or_paths(
+        "KECCAK_STATE_SIZE_FELTS",
+        &[KECCAK_STATE_SIZE_FELTS],
+        constants,
+        accessible_scopes,
+    )?;
     if keccak_state_size_felts >= &Felt252::from(100_i32) {
         return Err(HintError::InvalidKeccakStateSizeFelt252s(Box::new(
             *keccak_state_size_felts,
@@ -215,10 +226,14 @@ pub(crate) fn block_permutation_v2(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
-    let keccak_state_size_felts = constants
-        .get(KECCAK_STATE_SIZE_FELTS)
-        .ok_or_else(|| HintError::MissingConstant(Box::new(KECCAK_STATE_SIZE_FELTS)))?;
+    let keccak_state_size_felts = get_constant_from_var_name_or_paths(
+        "KECCAK_STATE_SIZE_FELTS",
+        &[KECCAK_STATE_SIZE_FELTS],
+        constants,
+        accessible_scopes,
+    )?;
     if keccak_state_size_felts >= &Felt252::from(100_i32) {
         return Err(HintError::InvalidKeccakStateSizeFelt252s(Box::new(
             *keccak_state_size_felts,
@@ -251,14 +266,21 @@ fn cairo_keccak_finalize(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
     block_size_limit: usize,
 ) -> Result<(), HintError> {
-    let keccak_state_size_felts = constants
-        .get(KECCAK_STATE_SIZE_FELTS)
-        .ok_or_else(|| HintError::MissingConstant(Box::new(KECCAK_STATE_SIZE_FELTS)))?;
-    let block_size = constants
-        .get(BLOCK_SIZE)
-        .ok_or_else(|| HintError::MissingConstant(Box::new(BLOCK_SIZE)))?;
+    let keccak_state_size_felts = get_constant_from_var_name_or_paths(
+        "KECCAK_STATE_SIZE_FELTS",
+        &[KECCAK_STATE_SIZE_FELTS],
+        constants,
+        accessible_scopes,
+    )?;
+    let block_size = get_constant_from_var_name_or_paths(
+        "BLOCK_SIZE",
+        &[BLOCK_SIZE],
+        constants,
+        accessible_scopes,
+    )?;
 
     if keccak_state_size_felts >= &Felt252::from(100_i32) {
         return Err(HintError::InvalidKeccakStateSizeFelt252s(Box::new(
@@ -312,8 +334,9 @@ pub(crate) fn cairo_keccak_finalize_v1(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
-    cairo_keccak_finalize(vm, ids_data, ap_tracking, constants, 10)
+    cairo_keccak_finalize(vm, ids_data, ap_tracking, constants, accessible_scopes, 10)
 }
 
 /* Implements hint:
@@ -333,8 +356,16 @@ pub(crate) fn cairo_keccak_finalize_v2(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
-    cairo_keccak_finalize(vm, ids_data, ap_tracking, constants, 1000)
+    cairo_keccak_finalize(
+        vm,
+        ids_data,
+        ap_tracking,
+        constants,
+        accessible_scopes,
+        1000,
+    )
 }
 
 // Helper function to transform a vector of MaybeRelocatables into a vector
@@ -370,14 +401,14 @@ mod tests {
     use crate::{
         any_box,
         hint_processor::{
-            builtin_hint_processor::builtin_hint_processor_definition::{
-                BuiltinHintProcessor, HintProcessorData,
+            builtin_hint_processor::{
+                builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
+                hint_code,
             },
-            hint_processor_definition::{HintProcessorLogic, HintReference},
+            hint_processor_definition::HintProcessorLogic,
         },
         types::{exec_scope::ExecutionScopes, relocatable::Relocatable},
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
     };
     use assert_matches::assert_matches;
     use rstest::*;
@@ -420,6 +451,13 @@ mod tests {
         //Create ids
         let ids_data = ids_data!["low", "high", "inputs"];
         assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        check_memory![
+            vm.segments.memory,
+            ((2, 0), 233),
+            ((2, 1), 0),
+            ((2, 2), 351),
+            ((2, 3), 0)
+        ];
     }
 
     #[test]
@@ -436,16 +474,18 @@ mod tests {
         assert_matches!(error, Err(HintError::Memory(_)));
     }
 
-    #[test]
+    #[rstest]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
-    fn compare_bytes_in_word_nondet_valid() {
-        let hint_code =
-            "memory[ap] = to_felt_or_relocatable(ids.n_bytes >= ids.KECCAK_FULL_RATE_IN_BYTES)";
+    #[case(0, 1)]
+    #[case(7, 1)]
+    #[case(8, 0)]
+    #[case(24, 0)]
+    fn compare_bytes_in_word_nondet_valid(#[case] n_bytes: i32, #[case] expected: i32) {
+        let hint_code = hint_code::COMPARE_BYTES_IN_WORD_NONDET;
         let mut vm = vm_with_range_check!();
 
-        vm.segments.add();
-        vm.segments = segments![((1, 0), 24)];
-
+        vm.segments = segments![((1, 0), n_bytes)];
+        add_segments!(vm, 1);
         run_context!(vm, 0, 1, 1);
         let ids_data = ids_data!["n_bytes"];
         assert_matches!(
@@ -454,26 +494,50 @@ mod tests {
                 ids_data,
                 hint_code,
                 exec_scopes_ref!(),
-                &[(KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK, Felt252::from(136))]
+                &[(BYTES_IN_WORD, Felt252::from(8))]
                     .into_iter()
                     .map(|(k, v)| (k.to_string(), v))
                     .collect()
             ),
             Ok(())
         );
+        check_memory![vm.segments.memory, ((1, 1), expected)];
     }
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
-    fn compare_keccak_full_rate_in_bytes_nondet_valid() {
-        let hint_code =
-            "memory[ap] = to_felt_or_relocatable(ids.n_bytes >= ids.KECCAK_FULL_RATE_IN_BYTES)";
-
+    fn compare_bytes_in_word_nondet_missing_constant() {
+        let hint_code = hint_code::COMPARE_BYTES_IN_WORD_NONDET;
         let mut vm = vm_with_range_check!();
 
-        vm.segments.add();
         vm.segments = segments![((1, 0), 24)];
+        run_context!(vm, 0, 1, 1);
+        let ids_data = ids_data!["n_bytes"];
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::MissingConstant(bx)) if *bx == "BYTES_IN_WORD"
+        );
+    }
+
+    #[rstest]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[case(24, KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK, 0)]
+    #[case(135, KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK, 0)]
+    #[case(136, KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK, 1)]
+    #[case(200, KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK, 1)]
+    #[case(135, KECCAK_FULL_RATE_IN_BYTES_BUILTIN_KECCAK, 0)]
+    #[case(136, KECCAK_FULL_RATE_IN_BYTES_BUILTIN_KECCAK, 1)]
+    fn compare_keccak_full_rate_in_bytes_nondet_valid(
+        #[case] n_bytes: i32,
+        #[case] constant_path: &str,
+        #[case] expected: i32,
+    ) {
+        let hint_code = hint_code::COMPARE_KECCAK_FULL_RATE_IN_BYTES_NONDET;
+
+        let mut vm = vm_with_range_check!();
 
+        vm.segments = segments![((1, 0), n_bytes)];
+        add_segments!(vm, 1);
         run_context!(vm, 0, 1, 1);
 
         let ids_data = ids_data!["n_bytes"];
@@ -483,13 +547,142 @@ mod tests {
                 ids_data,
                 hint_code,
                 exec_scopes_ref!(),
-                &[(KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK, Felt252::from(136))]
+                &[(constant_path, Felt252::from(136))]
                     .into_iter()
                     .map(|(k, v)| (k.to_string(), v))
                     .collect()
             ),
             Ok(())
         );
+        check_memory![vm.segments.memory, ((1, 1), expected)];
+    }
+
+    #[rstest]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[case("starkware.cairo.common.cairo_keccak.keccak", 0)]
+    #[case("starkware.cairo.common.builtin_keccak.keccak", 1)]
+    fn compare_keccak_full_rate_in_bytes_nondet_scoped_constant(
+        #[case] module: &str,
+        #[case] expected: i32,
+    ) {
+        let hint_code = hint_code::COMPARE_KECCAK_FULL_RATE_IN_BYTES_NONDET;
+
+        let mut vm = vm_with_range_check!();
+
+        vm.segments = segments![((1, 0), 100)];
+        add_segments!(vm, 1);
+        run_context!(vm, 0, 1, 1);
+
+        let ids_data = ids_data!["n_bytes"];
+        // Values made different to tell which of the two constants is used
+        let constants = HashMap::from([
+            (
+                KECCAK_FULL_RATE_IN_BYTES_CAIRO_KECCAK.to_string(),
+                Felt252::from(136),
+            ),
+            (
+                KECCAK_FULL_RATE_IN_BYTES_BUILTIN_KECCAK.to_string(),
+                Felt252::from(100),
+            ),
+        ]);
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code,
+                exec_scopes_ref!(),
+                &constants,
+                vec![module.to_string(), format!("{module}.keccak")]
+            ),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 1), expected)];
+    }
+
+    #[rstest]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[case(hint_code::CAIRO_KECCAK_FINALIZE_V1, 9)]
+    #[case(hint_code::CAIRO_KECCAK_FINALIZE_V2, 10)]
+    #[case(hint_code::CAIRO_KECCAK_FINALIZE_V2, 999)]
+    fn cairo_keccak_finalize_valid(#[case] hint_code: &str, #[case] block_size: usize) {
+        let mut vm = vm_with_range_check!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        add_segments!(vm, 1);
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["keccak_ptr_end"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code,
+                exec_scopes_ref!(),
+                &[
+                    (KECCAK_STATE_SIZE_FELTS, Felt252::from(25)),
+                    (BLOCK_SIZE, Felt252::from(block_size))
+                ]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect()
+            ),
+            Ok(())
+        );
+        assert_eq!(vm.segments.compute_effective_sizes()[2], 50 * block_size);
+    }
+
+    #[rstest]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[case(hint_code::CAIRO_KECCAK_FINALIZE_V1, 10)]
+    #[case(hint_code::CAIRO_KECCAK_FINALIZE_V1, 999)]
+    #[case(hint_code::CAIRO_KECCAK_FINALIZE_V2, 1000)]
+    fn cairo_keccak_finalize_invalid_block_size(
+        #[case] hint_code: &str,
+        #[case] block_size: usize,
+    ) {
+        let mut vm = vm_with_range_check!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["keccak_ptr_end"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code,
+                exec_scopes_ref!(),
+                &[
+                    (KECCAK_STATE_SIZE_FELTS, Felt252::from(25)),
+                    (BLOCK_SIZE, Felt252::from(block_size))
+                ]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect()
+            ),
+            Err(HintError::InvalidBlockSize(bx)) if *bx == Felt252::from(block_size)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn cairo_keccak_finalize_invalid_state_size() {
+        let mut vm = vm_with_range_check!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["keccak_ptr_end"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::CAIRO_KECCAK_FINALIZE_V2,
+                exec_scopes_ref!(),
+                &[
+                    (KECCAK_STATE_SIZE_FELTS, Felt252::from(100)),
+                    (BLOCK_SIZE, Felt252::from(3))
+                ]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect()
+            ),
+            Err(HintError::InvalidKeccakStateSizeFelt252s(bx)) if *bx == Felt252::from(100)
+        );
     }
 
     #[test]
@@ -268,7 +268,7 @@ mod tests {
         relocatable,
         types::relocatable::{MaybeRelocatable, Relocatable},
         utils::test_utils::*,
-        vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+        vm::errors::memory_errors::MemoryError,
     };
     use assert_matches::assert_matches;
 
@@ -335,7 +335,7 @@ mod tests {
             Err(HintError::Memory(
                 MemoryError::InconsistentMemory(bx)
             )) if *bx == (Relocatable::from((1, 0)),
-                    MaybeRelocatable::from(1),
+                    mayberelocatable!(1),
                     MaybeRelocatable::from((2, 0)))
         );
     }
@@ -362,7 +362,7 @@ mod tests {
                 .get(&MaybeRelocatable::from((1, 1)))
                 .unwrap()
                 .as_ref(),
-            &MaybeRelocatable::from(12)
+            &mayberelocatable!(12)
         );
         //Check that the tracker's current_ptr has moved accordingly
         check_dict_ptr!(&exec_scopes, 2, (2, 3));
@@ -383,7 +383,7 @@ mod tests {
         dict_manager!(&mut exec_scopes, 2, (5, 12));
         assert_matches!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::NoValueForKey(bx)) if *bx == MaybeRelocatable::from(6)
+            Err(HintError::NoValueForKey(bx)) if *bx == mayberelocatable!(6)
         );
     }
     #[test]
@@ -433,7 +433,7 @@ mod tests {
                 .get(&2),
             Some(&DictTracker::new_default_dict(
                 relocatable!(2, 0),
-                &MaybeRelocatable::from(17),
+                &mayberelocatable!(17),
                 None
             ))
         );
@@ -559,7 +559,7 @@ mod tests {
         //Execute the hint
         assert_matches!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::NoValueForKey(bx)) if *bx == MaybeRelocatable::from(5)
+            Err(HintError::NoValueForKey(bx)) if *bx == mayberelocatable!(5)
         );
     }
 
@@ -634,7 +634,7 @@ mod tests {
         assert_matches!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
             Err(HintError::WrongPrevValue(bx))
-            if *bx == (MaybeRelocatable::from(11), MaybeRelocatable::from(10), MaybeRelocatable::from(5))
+            if *bx == (mayberelocatable!(11), mayberelocatable!(10), mayberelocatable!(5))
         );
     }
 
@@ -658,7 +658,7 @@ mod tests {
         //Execute the hint
         assert_matches!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::NoValueForKey(bx)) if *bx == MaybeRelocatable::from(6)
+            Err(HintError::NoValueForKey(bx)) if *bx == mayberelocatable!(6)
         );
     }
 
@@ -733,7 +733,7 @@ mod tests {
         assert_matches!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
             Err(HintError::WrongPrevValue(bx))
-            if *bx == (MaybeRelocatable::from(11), MaybeRelocatable::from(10), MaybeRelocatable::from(5))
+            if *bx == (mayberelocatable!(11), mayberelocatable!(10), mayberelocatable!(5))
         );
     }
 
@@ -758,7 +758,7 @@ mod tests {
         assert_matches!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
             Err(HintError::WrongPrevValue(bx))
-            if *bx == (MaybeRelocatable::from(10), MaybeRelocatable::from(17), MaybeRelocatable::from(6))
+            if *bx == (mayberelocatable!(10), mayberelocatable!(17), mayberelocatable!(6))
         );
     }
 
@@ -841,9 +841,9 @@ mod tests {
                 .unwrap()
                 .downcast_ref::<HashMap<MaybeRelocatable, MaybeRelocatable>>(),
             Some(&HashMap::from([
-                (MaybeRelocatable::from(1), MaybeRelocatable::from(2)),
-                (MaybeRelocatable::from(3), MaybeRelocatable::from(4)),
-                (MaybeRelocatable::from(5), MaybeRelocatable::from(6))
+                (mayberelocatable!(1), mayberelocatable!(2)),
+                (mayberelocatable!(3), mayberelocatable!(4)),
+                (mayberelocatable!(5), mayberelocatable!(6))
             ]))
         );
     }
@@ -951,8 +951,8 @@ mod tests {
         );
         // Check that our relocatable was written into the dict
         let expected_dict = Dictionary::DefaultDictionary {
-            dict: HashMap::from([(MaybeRelocatable::from(5), MaybeRelocatable::from((1, 7)))]),
-            default_value: MaybeRelocatable::from(2),
+            dict: HashMap::from([(mayberelocatable!(5), MaybeRelocatable::from((1, 7)))]),
+            default_value: mayberelocatable!(2),
         };
         let expeced_dict_tracker = DictTracker {
             data: expected_dict,
@@ -70,7 +70,7 @@ impl DictManager {
         vm: &mut VirtualMachine,
         initial_dict: HashMap<MaybeRelocatable, MaybeRelocatable>,
     ) -> Result<MaybeRelocatable, HintError> {
-        let base = vm.add_memory_segment();
+        let base = vm.try_add_memory_segment()?;
         if self.trackers.contains_key(&base.segment_index) {
             return Err(HintError::CantCreateDictionaryOnTakenSegment(
                 base.segment_index,
@@ -91,7 +91,7 @@ impl DictManager {
         default_value: &MaybeRelocatable,
         initial_dict: Option<HashMap<MaybeRelocatable, MaybeRelocatable>>,
     ) -> Result<MaybeRelocatable, HintError> {
-        let base = vm.add_memory_segment();
+        let base = vm.try_add_memory_segment()?;
         if self.trackers.contains_key(&base.segment_index) {
             return Err(HintError::CantCreateDictionaryOnTakenSegment(
                 base.segment_index,
@@ -205,7 +205,7 @@ impl DictTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{relocatable, utils::test_utils::*, vm::vm_core::VirtualMachine};
+    use crate::{relocatable, utils::test_utils::*};
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
@@ -233,12 +233,12 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn create_dict_tracker_default() {
         let dict_tracker =
-            DictTracker::new_default_dict(relocatable!(1, 0), &MaybeRelocatable::from(5), None);
+            DictTracker::new_default_dict(relocatable!(1, 0), &mayberelocatable!(5), None);
         assert_eq!(
             dict_tracker.data,
             Dictionary::DefaultDictionary {
                 dict: HashMap::new(),
-                default_value: MaybeRelocatable::from(5)
+                default_value: mayberelocatable!(5)
             }
         );
         assert_eq!(dict_tracker.current_ptr, relocatable!(1, 0));
@@ -264,14 +264,14 @@ mod tests {
     fn dict_manager_new_dict_default() {
         let mut dict_manager = DictManager::new();
         let mut vm = vm!();
-        let base = dict_manager.new_default_dict(&mut vm, &MaybeRelocatable::from(5), None);
+        let base = dict_manager.new_default_dict(&mut vm, &mayberelocatable!(5), None);
         assert_matches!(base, Ok(x) if x == MaybeRelocatable::from((0, 0)));
         assert!(dict_manager.trackers.contains_key(&0));
         assert_eq!(
             dict_manager.trackers.get(&0),
             Some(&DictTracker::new_default_dict(
                 relocatable!(0, 0),
-                &MaybeRelocatable::from(5),
+                &mayberelocatable!(5),
                 None
             ))
         );
@@ -284,7 +284,7 @@ mod tests {
         let mut dict_manager = DictManager::new();
         let mut vm = vm!();
         let mut initial_dict = HashMap::<MaybeRelocatable, MaybeRelocatable>::new();
-        initial_dict.insert(MaybeRelocatable::from(5), MaybeRelocatable::from(5));
+        initial_dict.insert(mayberelocatable!(5), mayberelocatable!(5));
         let base = dict_manager.new_dict(&mut vm, initial_dict.clone());
         assert_matches!(base, Ok(x) if x == MaybeRelocatable::from((0, 0)));
         assert!(dict_manager.trackers.contains_key(&0));
@@ -304,10 +304,10 @@ mod tests {
         let mut dict_manager = DictManager::new();
         let mut initial_dict = HashMap::<MaybeRelocatable, MaybeRelocatable>::new();
         let mut vm = vm!();
-        initial_dict.insert(MaybeRelocatable::from(5), MaybeRelocatable::from(5));
+        initial_dict.insert(mayberelocatable!(5), mayberelocatable!(5));
         let base = dict_manager.new_default_dict(
             &mut vm,
-            &MaybeRelocatable::from(7),
+            &mayberelocatable!(7),
             Some(initial_dict.clone()),
         );
         assert_matches!(base, Ok(x) if x == MaybeRelocatable::from((0, 0)));
@@ -316,7 +316,7 @@ mod tests {
             dict_manager.trackers.get(&0),
             Some(&DictTracker::new_default_dict(
                 relocatable!(0, 0),
-                &MaybeRelocatable::from(7),
+                &mayberelocatable!(7),
                 Some(initial_dict)
             ))
         );
@@ -343,7 +343,7 @@ mod tests {
         let mut dict_manager = DictManager::new();
         dict_manager.trackers.insert(
             0,
-            DictTracker::new_default_dict(relocatable!(0, 0), &MaybeRelocatable::from(6), None),
+            DictTracker::new_default_dict(relocatable!(0, 0), &mayberelocatable!(6), None),
         );
         let mut vm = vm!();
         assert_matches!(
@@ -356,12 +356,12 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn dictionary_get_insert_simple() {
         let mut dictionary = Dictionary::SimpleDictionary(HashMap::new());
-        dictionary.insert(&MaybeRelocatable::from(1), &MaybeRelocatable::from(2));
+        dictionary.insert(&mayberelocatable!(1), &mayberelocatable!(2));
         assert_eq!(
-            dictionary.get(&MaybeRelocatable::from(1)),
-            Some(&MaybeRelocatable::from(2))
+            dictionary.get(&mayberelocatable!(1)),
+            Some(&mayberelocatable!(2))
         );
-        assert_eq!(dictionary.get(&MaybeRelocatable::from(2)), None);
+        assert_eq!(dictionary.get(&mayberelocatable!(2)), None);
     }
 
     #[test]
@@ -369,16 +369,16 @@ mod tests {
     fn dictionary_get_insert_default() {
         let mut dictionary = Dictionary::DefaultDictionary {
             dict: HashMap::new(),
-            default_value: MaybeRelocatable::from(7),
+            default_value: mayberelocatable!(7),
         };
-        dictionary.insert(&MaybeRelocatable::from(1), &MaybeRelocatable::from(2));
+        dictionary.insert(&mayberelocatable!(1), &mayberelocatable!(2));
         assert_eq!(
-            dictionary.get(&MaybeRelocatable::from(1)),
-            Some(&MaybeRelocatable::from(2))
+            dictionary.get(&mayberelocatable!(1)),
+            Some(&mayberelocatable!(2))
         );
         assert_eq!(
-            dictionary.get(&MaybeRelocatable::from(2)),
-            Some(&MaybeRelocatable::from(7))
+            dictionary.get(&mayberelocatable!(2)),
+            Some(&mayberelocatable!(7))
         );
     }
 }
@@ -221,7 +221,6 @@ mod tests {
     use crate::hint_processor::hint_processor_definition::HintProcessorLogic;
     use crate::relocatable;
     use crate::types::exec_scope::ExecutionScopes;
-    use crate::types::relocatable::Relocatable;
     use num_traits::Zero;
 
     use crate::hint_processor::builtin_hint_processor::hint_code;
@@ -324,29 +323,30 @@ mod tests {
         */
         add_segments!(vm, 2);
         vm.insert_value(
-            (1, 0).into(),
+            relocatable!(1, 0),
             felt_str!(
                 "3004956058830981475544150447242655232275382685012344776588097793621230049020"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (1, 1).into(),
+            relocatable!(1, 1),
             felt_str!(
                 "3232266734070744637901977159303149980795588196503166389060831401046564401743"
             ),
         )
         .unwrap();
-        vm.insert_value((1, 2).into(), Felt252::from(34)).unwrap();
+        vm.insert_value(relocatable!(1, 2), Felt252::from(34))
+            .unwrap();
         vm.insert_value(
-            (1, 3).into(),
+            relocatable!(1, 3),
             felt_str!(
                 "2864041794633455918387139831609347757720597354645583729611044800117714995244"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (1, 4).into(),
+            relocatable!(1, 4),
             felt_str!(
                 "2252415379535459416893084165764951913426528160630388985542241241048300343256"
             ),
@@ -358,13 +358,13 @@ mod tests {
         // s.x = 96578541406087262240552119423829615463800550101008760434566010168435227837635
         // s.y = 3412645436898503501401619513420382337734846074629040678138428701431530606439
         assert_eq!(
-            vm.get_integer((1, 5).into()).unwrap().as_ref(),
+            vm.get_integer(relocatable!(1, 5)).unwrap().as_ref(),
             &felt_str!(
                 "96578541406087262240552119423829615463800550101008760434566010168435227837635"
             )
         );
         assert_eq!(
-            vm.get_integer((1, 6).into()).unwrap().as_ref(),
+            vm.get_integer(relocatable!(1, 6)).unwrap().as_ref(),
             &felt_str!(
                 "3412645436898503501401619513420382337734846074629040678138428701431530606439"
             )
@@ -394,83 +394,89 @@ mod tests {
         add_segments!(vm, 4);
         //p
         vm.insert_value(
-            (1, 0).into(),
+            relocatable!(1, 0),
             felt_str!(
                 "3004956058830981475544150447242655232275382685012344776588097793621230049020"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (1, 1).into(),
+            relocatable!(1, 1),
             felt_str!(
                 "3232266734070744637901977159303149980795588196503166389060831401046564401743"
             ),
         )
         .unwrap();
         //m
-        vm.insert_value((1, 2).into(), relocatable!(2, 0)).unwrap();
-        vm.insert_value((2, 0).into(), Felt252::from(34)).unwrap();
-        vm.insert_value((2, 1).into(), Felt252::from(34)).unwrap();
-        vm.insert_value((2, 2).into(), Felt252::from(34)).unwrap();
+        vm.insert_value(relocatable!(1, 2), relocatable!(2, 0))
+            .unwrap();
+        vm.insert_value(relocatable!(2, 0), Felt252::from(34))
+            .unwrap();
+        vm.insert_value(relocatable!(2, 1), Felt252::from(34))
+            .unwrap();
+        vm.insert_value(relocatable!(2, 2), Felt252::from(34))
+            .unwrap();
         //q
-        vm.insert_value((1, 3).into(), relocatable!(3, 0)).unwrap();
+        vm.insert_value(relocatable!(1, 3), relocatable!(3, 0))
+            .unwrap();
         vm.insert_value(
-            (3, 0).into(),
+            relocatable!(3, 0),
             felt_str!(
                 "2864041794633455918387139831609347757720597354645583729611044800117714995244"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (3, 1).into(),
+            relocatable!(3, 1),
             felt_str!(
                 "2252415379535459416893084165764951913426528160630388985542241241048300343256"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (3, 2).into(),
+            relocatable!(3, 2),
             felt_str!(
                 "2864041794633455918387139831609347757720597354645583729611044800117714995244"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (3, 3).into(),
+            relocatable!(3, 3),
             felt_str!(
                 "2252415379535459416893084165764951913426528160630388985542241241048300343256"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (3, 4).into(),
+            relocatable!(3, 4),
             felt_str!(
                 "2864041794633455918387139831609347757720597354645583729611044800117714995244"
             ),
         )
         .unwrap();
         vm.insert_value(
-            (3, 5).into(),
+            relocatable!(3, 5),
             felt_str!(
                 "2252415379535459416893084165764951913426528160630388985542241241048300343256"
             ),
         )
         .unwrap();
         //len
-        vm.insert_value((1, 4).into(), Felt252::from(3)).unwrap();
+        vm.insert_value(relocatable!(1, 4), Felt252::from(3))
+            .unwrap();
         //Execute the hint
         assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
         // Check post-hint memory values
         // s.x = 1354562415074475070179359167082942891834423311678180448592849484844152837347
         // s.y = 907662328694455187848008017177970257426839229889571025406355869359245158736
         assert_eq!(
-            vm.get_integer((1, 5).into()).unwrap().as_ref(),
+            vm.get_integer(relocatable!(1, 5)).unwrap().as_ref(),
             &felt_str!(
                 "1354562415074475070179359167082942891834423311678180448592849484844152837347"
             )
         );
         assert_eq!(
-            vm.get_integer((1, 6).into()).unwrap().as_ref(),
+            vm.get_integer(relocatable!(1, 6)).unwrap().as_ref(),
             &felt_str!(
                 "907662328694455187848008017177970257426839229889571025406355869359245158736"
             )
@@ -489,7 +495,7 @@ mod tests {
         // x = 3004956058830981475544150447242655232275382685012344776588097793621230049020
         add_segments!(vm, 2);
         vm.insert_value(
-            (1, 0).into(),
+            relocatable!(1, 0),
             felt_str!(
                 "3004956058830981475544150447242655232275382685012344776588097793621230049020"
             ),
@@ -501,13 +507,13 @@ mod tests {
         // p.x = 3004956058830981475544150447242655232275382685012344776588097793621230049020
         // p.y = 386236054595386575795345623791920124827519018828430310912260655089307618738
         assert_eq!(
-            vm.get_integer((1, 2).into()).unwrap().as_ref(),
+            vm.get_integer(relocatable!(1, 2)).unwrap().as_ref(),
             &felt_str!(
                 "3004956058830981475544150447242655232275382685012344776588097793621230049020"
             )
         );
         assert_eq!(
-            vm.get_integer((1, 3).into()).unwrap().as_ref(),
+            vm.get_integer(relocatable!(1, 3)).unwrap().as_ref(),
             &felt_str!(
                 "386236054595386575795345623791920124827519018828430310912260655089307618738"
             )
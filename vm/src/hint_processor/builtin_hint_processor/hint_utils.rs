@@ -4,7 +4,7 @@ use crate::Felt252;
 
 use crate::hint_processor::hint_processor_definition::HintReference;
 use crate::hint_processor::hint_processor_utils::{
-    compute_addr_from_reference, get_ptr_from_reference,
+    check_ap_tracking_group, compute_addr_from_reference, get_ptr_from_reference,
 };
 use crate::hint_processor::hint_processor_utils::{
     get_integer_from_reference, get_maybe_relocatable_from_reference,
@@ -37,6 +37,16 @@ pub fn insert_value_into_ap(
         .map_err(HintError::Memory)
 }
 
+//Inserts value into ap + offset
+pub fn insert_value_at_ap_offset(
+    vm: &mut VirtualMachine,
+    value: impl Into<MaybeRelocatable>,
+    offset: usize,
+) -> Result<(), HintError> {
+    vm.insert_value((vm.get_ap() + offset)?, value)
+        .map_err(HintError::Memory)
+}
+
 //Returns the Relocatable value stored in the given ids variable
 pub fn get_ptr_from_var_name(
     var_name: &str,
@@ -51,6 +61,9 @@ pub fn get_ptr_from_var_name(
         Err(HintError::WrongIdentifierTypeInternal(var_addr)) => Err(
             HintError::IdentifierNotRelocatable(Box::new((var_name.to_string(), *var_addr))),
         ),
+        Err(err @ (HintError::InvalidTrackingGroup(_) | HintError::InvalidTrackingOffset(_))) => {
+            Err(err)
+        }
         _ => Err(HintError::UnknownIdentifier(
             var_name.to_string().into_boxed_str(),
         )),
@@ -74,9 +87,9 @@ pub fn get_relocatable_from_var_name(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
 ) -> Result<Relocatable, HintError> {
-    ids_data
-        .get(var_name)
-        .and_then(|x| compute_addr_from_reference(x, vm, ap_tracking))
+    let reference = get_reference_from_var_name(var_name, ids_data)?;
+    check_ap_tracking_group(reference, ap_tracking)?;
+    compute_addr_from_reference(reference, vm, ap_tracking)
         .ok_or_else(|| HintError::UnknownIdentifier(var_name.to_string().into_boxed_str()))
 }
 
@@ -96,6 +109,9 @@ pub fn get_integer_from_var_name<'a>(
         Err(HintError::WrongIdentifierTypeInternal(var_addr)) => Err(
             HintError::IdentifierNotInteger(Box::new((var_name.to_string(), *var_addr))),
         ),
+        Err(err @ (HintError::InvalidTrackingGroup(_) | HintError::InvalidTrackingOffset(_))) => {
+            Err(err)
+        }
         _ => Err(HintError::UnknownIdentifier(
             var_name.to_string().into_boxed_str(),
         )),
@@ -110,6 +126,7 @@ pub fn get_maybe_relocatable_from_var_name<'a>(
     ap_tracking: &ApTracking,
 ) -> Result<MaybeRelocatable, HintError> {
     let reference = get_reference_from_var_name(var_name, ids_data)?;
+    check_ap_tracking_group(reference, ap_tracking)?;
     get_maybe_relocatable_from_reference(vm, reference, ap_tracking)
         .ok_or_else(|| HintError::UnknownIdentifier(var_name.to_string().into_boxed_str()))
 }
@@ -123,15 +140,50 @@ pub fn get_reference_from_var_name<'a>(
         .ok_or_else(|| HintError::UnknownIdentifier(var_name.to_string().into_boxed_str()))
 }
 
+/// Returns the value of the constant `var_name` defined or imported in the innermost of the
+/// hint's `accessible_scopes` (ordered from outermost to innermost).
+/// If none of the scopes has `var_name` it is looked up by name, failing with
+/// `HintError::AmbiguousConstant` if constants with different values have it.
 pub fn get_constant_from_var_name<'a>(
     var_name: &'static str,
     constants: &'a HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<&'a Felt252, HintError> {
-    constants
+    get_constant_from_var_name_or_paths(var_name, &[], constants, accessible_scopes)
+}
+
+/// Like [`get_constant_from_var_name`], but tries the full `paths` of the constant before looking
+/// it up by name, for hints run without their accessible scopes.
+pub fn get_constant_from_var_name_or_paths<'a>(
+    var_name: &'static str,
+    paths: &[&str],
+    constants: &'a HashMap<String, Felt252>,
+    accessible_scopes: &[String],
+) -> Result<&'a Felt252, HintError> {
+    if let Some(value) = accessible_scopes
+        .iter()
+        .rev()
+        .find_map(|scope| constants.get(&format!("{scope}.{var_name}")))
+        .or_else(|| paths.iter().find_map(|path| constants.get(*path)))
+    {
+        return Ok(value);
+    }
+    let matches: Vec<_> = constants
         .iter()
-        .find(|(k, _)| k.rsplit('.').next() == Some(var_name))
-        .map(|(_, n)| n)
-        .ok_or_else(|| HintError::MissingConstant(Box::new(var_name)))
+        .filter(|(k, _)| k.rsplit('.').next() == Some(var_name))
+        .collect();
+    match matches.first() {
+        None => Err(HintError::MissingConstant(Box::new(var_name))),
+        // Imported constants are also stored under the importing module's path
+        Some((_, value)) if matches.iter().all(|(_, v)| v == value) => Ok(value),
+        Some(_) => {
+            let mut candidates: Vec<_> = matches.into_iter().map(|(k, _)| k.clone()).collect();
+            candidates.sort();
+            Err(HintError::AmbiguousConstant(Box::new((
+                var_name, candidates,
+            ))))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -140,11 +192,9 @@ mod tests {
     use crate::stdlib::string::ToString;
 
     use crate::{
-        hint_processor::hint_processor_definition::HintReference,
-        relocatable,
-        serde::deserialize_program::OffsetValue,
-        utils::test_utils::*,
-        vm::{vm_core::VirtualMachine, vm_memory::memory::Memory},
+        hint_processor::hint_processor_definition::HintReference, relocatable,
+        serde::deserialize_program::OffsetValue, types::instruction::Register,
+        utils::test_utils::*, vm::vm_memory::memory::Memory,
     };
     use assert_matches::assert_matches;
 
@@ -277,4 +327,139 @@ mod tests {
             Err(HintError::IdentifierNotInteger(bx)) if *bx == ("value".to_string(), (1,0).into())
         );
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_var_name_ap_reference_invalid_tracking_group() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 5)];
+        vm.run_context.ap = 1;
+        let mut hint_ref = HintReference::new(0, 0, false, true);
+        hint_ref.offset1 = OffsetValue::Reference(Register::AP, -1, false);
+        hint_ref.ap_tracking_data = Some(ApTracking {
+            group: 3,
+            offset: 0,
+        });
+        let ids_data = HashMap::from([("local".to_string(), hint_ref)]);
+        let hint_ap_tracking = ApTracking {
+            group: 4,
+            offset: 0,
+        };
+
+        assert_matches!(
+            get_relocatable_from_var_name("local", &vm, &ids_data, &hint_ap_tracking),
+            Err(HintError::InvalidTrackingGroup(bx)) if *bx == (3, 4)
+        );
+        assert_matches!(
+            get_integer_from_var_name("local", &vm, &ids_data, &hint_ap_tracking),
+            Err(HintError::InvalidTrackingGroup(bx)) if *bx == (3, 4)
+        );
+        assert_matches!(
+            get_maybe_relocatable_from_var_name("local", &vm, &ids_data, &hint_ap_tracking),
+            Err(HintError::InvalidTrackingGroup(bx)) if *bx == (3, 4)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn insert_value_at_ap_offset_writes_relative_to_ap() {
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        vm.set_ap(3);
+
+        assert_matches!(
+            insert_value_at_ap_offset(&mut vm, Felt252::from(7), 2),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 5), 7)];
+        assert!(vm.segments.memory.get(&relocatable!(1, 3)).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_constant_from_var_name_innermost_scope() {
+        let constants = HashMap::from([
+            ("a.BLOCK_SIZE".to_string(), Felt252::from(1)),
+            ("a.b.BLOCK_SIZE".to_string(), Felt252::from(2)),
+            ("c.BLOCK_SIZE".to_string(), Felt252::from(3)),
+        ]);
+        let scopes = |scopes: &[&str]| scopes.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_matches!(
+            get_constant_from_var_name("BLOCK_SIZE", &constants, &scopes(&["a", "a.b"])),
+            Ok(x) if x == &Felt252::from(2)
+        );
+        assert_matches!(
+            get_constant_from_var_name("BLOCK_SIZE", &constants, &scopes(&["a"])),
+            Ok(x) if x == &Felt252::from(1)
+        );
+        assert_matches!(
+            get_constant_from_var_name("BLOCK_SIZE", &constants, &scopes(&["c", "c.f"])),
+            Ok(x) if x == &Felt252::from(3)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_constant_from_var_name_outside_scopes() {
+        let constants = HashMap::from([
+            ("a.BLOCK_SIZE".to_string(), Felt252::from(1)),
+            ("a.SHIFT".to_string(), Felt252::from(4)),
+            ("b.SHIFT".to_string(), Felt252::from(5)),
+        ]);
+        let scopes = ["__main__".to_string()];
+        // Imported from a single module
+        assert_matches!(
+            get_constant_from_var_name("BLOCK_SIZE", &constants, &scopes),
+            Ok(x) if x == &Felt252::from(1)
+        );
+        // Defined by more than one module
+        assert_matches!(
+            get_constant_from_var_name("SHIFT", &constants, &scopes),
+            Err(HintError::AmbiguousConstant(bx))
+                if *bx == ("SHIFT", vec!["a.SHIFT".to_string(), "b.SHIFT".to_string()])
+        );
+        assert_matches!(
+            get_constant_from_var_name("MAX_HIGH", &constants, &scopes),
+            Err(HintError::MissingConstant(bx)) if *bx == "MAX_HIGH"
+        );
+    }
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_constant_from_var_name_through_alias() {
+        // BLOCK_SIZE is defined in packed_keccak and imported into keccak, while another module
+        // defines its own BLOCK_SIZE
+        let constants = HashMap::from([
+            (
+                "starkware.cairo.common.cairo_keccak.packed_keccak.BLOCK_SIZE".to_string(),
+                Felt252::from(3),
+            ),
+            (
+                "starkware.cairo.common.cairo_keccak.keccak.BLOCK_SIZE".to_string(),
+                Felt252::from(3),
+            ),
+            ("__main__.BLOCK_SIZE".to_string(), Felt252::from(16)),
+        ]);
+        let scopes = [
+            "starkware.cairo.common.cairo_keccak.keccak".to_string(),
+            "starkware.cairo.common.cairo_keccak.keccak.finalize_keccak".to_string(),
+        ];
+        assert_matches!(
+            get_constant_from_var_name("BLOCK_SIZE", &constants, &scopes),
+            Ok(x) if x == &Felt252::from(3)
+        );
+        // Without scopes, the full path is used
+        assert_matches!(
+            get_constant_from_var_name_or_paths(
+                "BLOCK_SIZE",
+                &["starkware.cairo.common.cairo_keccak.packed_keccak.BLOCK_SIZE"],
+                &constants,
+                &[]
+            ),
+            Ok(x) if x == &Felt252::from(3)
+        );
+        assert_matches!(
+            get_constant_from_var_name("BLOCK_SIZE", &constants, &[]),
+            Err(HintError::AmbiguousConstant(bx)) if bx.0 == "BLOCK_SIZE" && bx.1.len() == 3
+        );
+    }
 }
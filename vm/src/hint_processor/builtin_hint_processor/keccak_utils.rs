@@ -18,7 +18,7 @@ use num_integer::Integer;
 use num_traits::ToPrimitive;
 use sha3::{Digest, Keccak256};
 
-use super::hint_utils::insert_value_from_var_name;
+use super::hint_utils::{get_constant_from_var_name_or_paths, insert_value_from_var_name};
 
 const BYTES_IN_WORD: &str = "starkware.cairo.common.builtin_keccak.keccak.BYTES_IN_WORD";
 
@@ -240,6 +240,7 @@ pub fn split_n_bytes(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
     let n_bytes =
         get_integer_from_var_name("n_bytes", vm, ids_data, ap_tracking).and_then(|x| {
@@ -247,10 +248,14 @@ pub fn split_n_bytes(
                 HintError::Math(MathError::Felt252ToU64Conversion(Box::new(x.into_owned())))
             })
         })?;
-    let bytes_in_word = constants
-        .get(BYTES_IN_WORD)
-        .and_then(|x| x.to_u64())
-        .ok_or_else(|| HintError::MissingConstant(Box::new(BYTES_IN_WORD)))?;
+    let bytes_in_word = get_constant_from_var_name_or_paths(
+        "BYTES_IN_WORD",
+        &[BYTES_IN_WORD],
+        constants,
+        accessible_scopes,
+    )?
+    .to_u64()
+    .ok_or_else(|| HintError::MissingConstant(Box::new("BYTES_IN_WORD")))?;
     let (high, low) = n_bytes.div_mod_floor(&bytes_in_word);
     insert_value_from_var_name(
         "n_words_to_copy",
@@ -288,6 +293,7 @@ pub fn split_output_mid_low_high(
 #[cfg(test)]
 mod tests {
     use super::*;
+
     use crate::any_box;
     use crate::{
         hint_processor::{
@@ -296,11 +302,10 @@ mod tests {
                 hint_code,
                 keccak_utils::HashMap,
             },
-            hint_processor_definition::{HintProcessorLogic, HintReference},
+            hint_processor_definition::HintProcessorLogic,
         },
         types::exec_scope::ExecutionScopes,
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
     };
     use assert_matches::assert_matches;
 
@@ -1,5 +1,7 @@
 use crate::{
-    hint_processor::builtin_hint_processor::hint_utils::get_constant_from_var_name,
+    hint_processor::builtin_hint_processor::hint_utils::{
+        get_constant_from_var_name, get_constant_from_var_name_or_paths,
+    },
     math_utils::signed_felt,
     stdlib::{boxed::Box, collections::HashMap, prelude::*},
     types::errors::math_errors::MathError,
@@ -408,14 +410,15 @@ pub fn split_felt(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
     let assert = |b: bool, msg: &str| {
         b.then_some(())
             .ok_or_else(|| HintError::AssertionFailed(msg.to_string().into_boxed_str()))
     };
     let bound = pow2_const(128);
-    let max_high = get_constant_from_var_name("MAX_HIGH", constants)?;
-    let max_low = get_constant_from_var_name("MAX_LOW", constants)?;
+    let max_high = get_constant_from_var_name("MAX_HIGH", constants, accessible_scopes)?;
+    let max_low = get_constant_from_var_name("MAX_LOW", constants, accessible_scopes)?;
     assert(
         max_high < &bound && max_low < &bound,
         "assert ids.MAX_HIGH < 2**128 and ids.MAX_LOW < 2**128",
@@ -565,16 +568,19 @@ pub fn assert_250_bit(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
     const UPPER_BOUND: &str = "starkware.cairo.common.math.assert_250_bit.UPPER_BOUND";
     const SHIFT: &str = "starkware.cairo.common.math.assert_250_bit.SHIFT";
     //Declare constant values
-    let upper_bound = constants
-        .get(UPPER_BOUND)
-        .map_or_else(|| get_constant_from_var_name("UPPER_BOUND", constants), Ok)?;
-    let shift = constants
-        .get(SHIFT)
-        .map_or_else(|| get_constant_from_var_name("SHIFT", constants), Ok)?;
+    let upper_bound = get_constant_from_var_name_or_paths(
+        "UPPER_BOUND",
+        &[UPPER_BOUND],
+        constants,
+        accessible_scopes,
+    )?;
+    let shift =
+        get_constant_from_var_name_or_paths("SHIFT", &[SHIFT], constants, accessible_scopes)?;
     let value = Felt252::from(&signed_felt(*get_integer_from_var_name(
         "value",
         vm,
@@ -798,6 +804,40 @@ pub fn split_xx(
     Ok(())
 }
 
+/* Helper checking an ids variable against a program constant, for hint processors that assert
+   size bounds (such as a number of steps) before running a loop:
+   assert ids.<var_name> <= ids.<constant_name>    (strict = false)
+   assert ids.<var_name> < ids.<constant_name>     (strict = true)
+*/
+#[allow(clippy::too_many_arguments)]
+pub fn assert_bounded_by_constant(
+    vm: &VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
+    var_name: &str,
+    constant_name: &'static str,
+    strict: bool,
+) -> Result<(), HintError> {
+    let bound = get_constant_from_var_name(constant_name, constants, accessible_scopes)?;
+    let value = *get_integer_from_var_name(var_name, vm, ids_data, ap_tracking)?;
+    let (in_bounds, op) = if strict {
+        (value < *bound, "<")
+    } else {
+        (value <= *bound, "<=")
+    };
+    if !in_bounds {
+        return Err(HintError::AssertionFailed(
+            format!(
+                "assert ids.{var_name} {op} ids.{constant_name} failed: {var_name} = {value}, {constant_name} = {bound}"
+            )
+            .into_boxed_str(),
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -817,7 +857,7 @@ mod tests {
         types::exec_scope::ExecutionScopes,
         types::relocatable::Relocatable,
         utils::test_utils::*,
-        vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+        vm::errors::memory_errors::MemoryError,
     };
     use assert_matches::assert_matches;
 
@@ -1903,6 +1943,102 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_n_le_n_max_ok() {
+        let constants = HashMap::from([(
+            "starkware.cairo.common.hash_chain.N_MAX".to_string(),
+            Felt252::from(3),
+        )]);
+        let mut vm = vm!();
+        vm.run_context.fp = 1;
+        vm.segments = segments![((1, 0), 3)];
+        let ids_data = ids_data!["n"];
+        assert_matches!(
+            assert_bounded_by_constant(
+                &vm,
+                &ids_data,
+                &ApTracking::default(),
+                &constants,
+                &[],
+                "n",
+                "N_MAX",
+                false
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_n_le_n_max_fails() {
+        let constants = HashMap::from([("N_MAX".to_string(), Felt252::from(3))]);
+        let mut vm = vm!();
+        vm.run_context.fp = 1;
+        vm.segments = segments![((1, 0), 4)];
+        let ids_data = ids_data!["n"];
+        assert_matches!(
+            assert_bounded_by_constant(
+                &vm,
+                &ids_data,
+                &ApTracking::default(),
+                &constants,
+                &[],
+                "n",
+                "N_MAX",
+                false
+            ),
+            Err(HintError::AssertionFailed(bx))
+                if bx.as_ref() == "assert ids.n <= ids.N_MAX failed: n = 4, N_MAX = 3"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_n_le_n_max_missing_constant() {
+        let mut vm = vm!();
+        vm.run_context.fp = 1;
+        vm.segments = segments![((1, 0), 3)];
+        let ids_data = ids_data!["n"];
+        assert_matches!(
+            assert_bounded_by_constant(
+                &vm,
+                &ids_data,
+                &ApTracking::default(),
+                &HashMap::new(),
+                &[],
+                "n",
+                "N_MAX",
+                false
+            ),
+            Err(HintError::MissingConstant(bx)) if *bx == "N_MAX"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_n_steps_lt_max_n_steps_is_strict() {
+        let constants = HashMap::from([("MAX_N_STEPS".to_string(), Felt252::from(10))]);
+        let mut vm = vm!();
+        vm.run_context.fp = 1;
+        vm.segments = segments![((1, 0), 10)];
+        let ids_data = ids_data!["n_steps"];
+        assert_matches!(
+            assert_bounded_by_constant(
+                &vm,
+                &ids_data,
+                &ApTracking::default(),
+                &constants,
+                &[],
+                "n_steps",
+                "MAX_N_STEPS",
+                true
+            ),
+            Err(HintError::AssertionFailed(bx))
+                if bx.as_ref() == "assert ids.n_steps < ids.MAX_N_STEPS failed: n_steps = 10, MAX_N_STEPS = 10"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_assert_250_bit_valid() {
@@ -1957,6 +2093,57 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_assert_250_bit_constants_from_accessible_scopes() {
+        let hint_code = hint_code::ASSERT_250_BITS;
+        // Both the common library and the main module define UPPER_BOUND and SHIFT
+        let constants = HashMap::from([
+            (
+                "starkware.cairo.common.math.assert_250_bit.UPPER_BOUND".to_string(),
+                Felt252::from(15),
+            ),
+            (
+                "starkware.cairo.common.math.assert_250_bit.SHIFT".to_string(),
+                Felt252::from(5),
+            ),
+            ("__main__.UPPER_BOUND".to_string(), Felt252::from(5)),
+            ("__main__.SHIFT".to_string(), Felt252::from(2)),
+        ]);
+        let run = |accessible_scopes: &[&str]| {
+            let mut vm = vm!();
+            vm.run_context.fp = 3;
+            vm.segments = segments![((1, 0), 11)];
+            let ids_data = ids_data!["value", "high", "low"];
+            let result = run_hint!(
+                vm,
+                ids_data,
+                hint_code,
+                &mut exec_scopes_ref!(),
+                &constants,
+                accessible_scopes.iter().map(|s| s.to_string()).collect()
+            );
+            (result, vm)
+        };
+        // Hint in the common library's assert_250_bit: 11 <= 15, divmod(11, 5) = (2, 1)
+        let (result, vm) = run(&[
+            "starkware.cairo.common.math",
+            "starkware.cairo.common.math.assert_250_bit",
+        ]);
+        assert_matches!(result, Ok(()));
+        check_memory![vm.segments.memory, ((1, 1), 2), ((1, 2), 1)];
+        // Same hint in the main module: 11 > 5
+        let (result, _) = run(&["__main__", "__main__.main"]);
+        assert_matches!(
+            result,
+            Err(HintError::ValueOutside250BitRange(bx)) if *bx == Felt252::from(11)
+        );
+        // Without scopes the common library's constants are used
+        let (result, vm) = run(&[]);
+        assert_matches!(result, Ok(()));
+        check_memory![vm.segments.memory, ((1, 1), 2), ((1, 2), 1)];
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_is_250_bits_valid() {
@@ -12,7 +12,7 @@ use crate::{
 
 //Implements hint: memory[ap] = segments.add()
 pub fn add_segment(vm: &mut VirtualMachine) -> Result<(), HintError> {
-    let new_segment_base = vm.add_memory_segment();
+    let new_segment_base = vm.try_add_memory_segment()?;
     insert_value_into_ap(vm, new_segment_base)
 }
 
@@ -1,10 +1,20 @@
 pub mod bigint;
+#[cfg(feature = "hints-blake2s")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-blake2s")))]
 pub mod blake2s_hash;
+#[cfg(feature = "hints-blake2s")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-blake2s")))]
 pub mod blake2s_utils;
 pub mod builtin_hint_processor_definition;
+#[cfg(feature = "hints-keccak")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-keccak")))]
 pub mod cairo_keccak;
+#[cfg(feature = "hints-dict")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-dict")))]
 pub mod dict_hint_utils;
 pub mod dict_manager;
+#[cfg(feature = "hints-secp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-secp")))]
 pub mod ec_recover;
 pub mod ec_utils;
 pub mod field_arithmetic;
@@ -12,22 +22,29 @@ pub mod find_element_hint;
 pub mod garaga;
 pub mod hint_code;
 pub mod hint_utils;
+#[cfg(feature = "hints-keccak")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-keccak")))]
 pub mod keccak_utils;
 pub mod math_utils;
 pub mod memcpy_hint_utils;
 pub mod memset_utils;
 pub mod poseidon_utils;
 pub mod pow_utils;
+pub mod program_hash_utils;
 #[cfg(feature = "print")]
 pub mod print;
 pub mod secp;
 pub mod segments;
 pub mod set;
+#[cfg(feature = "hints-sha256")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-sha256")))]
 pub mod sha256_utils;
 pub mod signature;
 #[cfg(feature = "skip_next_instruction_hint")]
 #[cfg_attr(docsrs, doc(cfg(feature = "skip_next_instruction_hint")))]
 pub mod skip_next_instruction;
+#[cfg(feature = "hints-dict")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-dict")))]
 pub mod squash_dict_utils;
 pub mod uint256_utils;
 pub mod uint384;
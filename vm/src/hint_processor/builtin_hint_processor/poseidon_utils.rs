@@ -56,9 +56,7 @@ mod tests {
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintProcessorData;
     use crate::hint_processor::hint_processor_definition::HintProcessorLogic;
-    use crate::hint_processor::hint_processor_definition::HintReference;
     use crate::types::exec_scope::ExecutionScopes;
-    use crate::vm::vm_core::VirtualMachine;
 
     use crate::{hint_processor::builtin_hint_processor::hint_code, utils::test_utils::*};
     use assert_matches::assert_matches;
@@ -47,7 +47,7 @@ mod tests {
         },
         types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
         utils::test_utils::*,
-        vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+        vm::errors::memory_errors::MemoryError,
     };
     use assert_matches::assert_matches;
 
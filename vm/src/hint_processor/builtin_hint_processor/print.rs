@@ -122,3 +122,16 @@ pub fn print_dict(
     println!("{:?}", acc);
     Ok(())
 }
+
+pub fn print_locals(exec_scopes: &ExecutionScopes) -> Result<(), HintError> {
+    let locals = exec_scopes
+        .describe()?
+        .into_iter()
+        .map(|variable| {
+            let value = variable.value.as_deref().unwrap_or("<unknown>");
+            format!("{}: {}", variable.name, value)
+        })
+        .collect::<Vec<_>>();
+    println!("{{{}}}", locals.join(", "));
+    Ok(())
+}
@@ -0,0 +1,378 @@
+use crate::stdlib::{collections::HashMap, prelude::*};
+
+use crate::{
+    hint_processor::{
+        builtin_hint_processor::hint_utils::{get_ptr_from_var_name, insert_value_from_var_name},
+        hint_processor_definition::HintReference,
+    },
+    program_hash::{compute_program_hash_chain, program_hash_chain_data},
+    serde::deserialize_program::ApTracking,
+    types::{
+        exec_scope::ExecutionScopes,
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        errors::hint_errors::HintError, runners::cairo_pie::StrippedProgram,
+        vm_core::VirtualMachine,
+    },
+};
+
+// Offsets of the bootloader's ProgramHeader members
+const PROGRAM_HEADER_PROGRAM_MAIN_OFFSET: usize = 2;
+const PROGRAM_HEADER_BUILTIN_LIST_OFFSET: usize = 4;
+
+/*
+Implements hint:
+%{ ids.program_data_ptr = program_data_base = segments.add() %}
+*/
+pub fn allocate_program_data_segment(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let program_data_base = vm.try_add_memory_segment()?;
+    exec_scopes.insert_value("program_data_base", program_data_base);
+    insert_value_from_var_name(
+        "program_data_ptr",
+        program_data_base,
+        vm,
+        ids_data,
+        ap_tracking,
+    )
+}
+
+/*
+Implements hint:
+%{
+    from starkware.cairo.bootloaders.simple_bootloader.utils import load_program
+
+    # Call load_program to load the program header and code to memory.
+    program_address, program_data_size = load_program(
+        task=task, memory=memory, program_header=ids.program_header,
+        builtins_offset=ids.ProgramHeader.builtin_list)
+    segments.finalize(program_data_base.segment_index, program_data_size)
+%}
+The task to load is the `StrippedProgram` stored in scope as `task`.
+*/
+pub fn load_program(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let task: &StrippedProgram = exec_scopes.get_ref("task")?;
+    let n_builtins = task.builtins.len();
+    // The hashed data follows the memory layout of the program header and code:
+    // [data_length, bootloader_version, program_main, n_builtins, *builtin_list, *program_data]
+    // The bootloader_version is left for the Cairo code to fill in.
+    let program_data = program_hash_chain_data(task, 0)?
+        .into_iter()
+        .map(MaybeRelocatable::from)
+        .collect::<Vec<_>>();
+
+    let header_address = get_ptr_from_var_name("program_header", vm, ids_data, ap_tracking)?;
+    vm.insert_value(header_address, program_data[0].clone())?;
+    vm.load_data(
+        (header_address + PROGRAM_HEADER_PROGRAM_MAIN_OFFSET)?,
+        &program_data[PROGRAM_HEADER_PROGRAM_MAIN_OFFSET..].to_vec(),
+    )?;
+    let program_address = (header_address + (PROGRAM_HEADER_BUILTIN_LIST_OFFSET + n_builtins))?;
+
+    let program_data_base: Relocatable = exec_scopes.get("program_data_base")?;
+    vm.segments.finalize(
+        Some(program_data.len()),
+        program_data_base.segment_index as usize,
+        None,
+    );
+    exec_scopes.insert_value("program_address", program_address);
+    Ok(())
+}
+
+/*
+Implements hint:
+%{
+    # Validate hash.
+    from starkware.cairo.bootloaders.hash_program import compute_program_hash_chain
+
+    assert memory[ids.output_ptr + 1] == compute_program_hash_chain(task.get_program()), \
+      'Computed hash does not match input.'
+%}
+*/
+pub fn validate_program_hash(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let output_ptr = get_ptr_from_var_name("output_ptr", vm, ids_data, ap_tracking)?;
+    let program_hash = vm.get_integer((output_ptr + 1_usize)?)?;
+    let task: &StrippedProgram = exec_scopes.get_ref("task")?;
+    if *program_hash != compute_program_hash_chain(task, 0)? {
+        return Err(HintError::AssertionFailed(
+            "Computed hash does not match input."
+                .to_string()
+                .into_boxed_str(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        any_box,
+        hint_processor::{
+            builtin_hint_processor::{
+                builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
+                hint_code,
+            },
+            hint_processor_definition::HintProcessorLogic,
+        },
+        program_hash::compute_hash_chain,
+        types::program::Program,
+        utils::test_utils::*,
+        vm::{
+            errors::{cairo_run_errors::CairoRunError, vm_errors::VirtualMachineError},
+            runners::cairo_runner::CairoRunner,
+        },
+        Felt252,
+    };
+    use assert_matches::assert_matches;
+    use num_traits::ToPrimitive;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn task_program() -> StrippedProgram {
+        Program::from_bytes(
+            include_bytes!(
+                "../../../../cairo_programs/manually_compiled/proof_mode_output_only.json"
+            ),
+            Some("main"),
+        )
+        .unwrap()
+        .get_stripped_program()
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn allocate_program_data_segment_ok() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 1)];
+        vm.run_context.fp = 2;
+        let ids_data = ids_data!["program_data_ptr"];
+        let mut exec_scopes = ExecutionScopes::new();
+
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EXECUTE_TASK_ALLOCATE_PROGRAM_DATA_SEGMENT,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [("program_data_base", Relocatable::from((2, 0)))]
+        );
+        check_memory![vm.segments.memory, ((1, 1), (2, 0))];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_program_ok() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        vm.segments.add();
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["program_header"];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("task", task_program());
+        exec_scopes.insert_value("program_data_base", Relocatable::from((2, 0)));
+
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EXECUTE_TASK_LOAD_PROGRAM,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        let program_data = program_hash_chain_data(&task_program(), 0).unwrap();
+        // The bootloader version isn't written by the hint
+        assert_eq!(vm.get_maybe(&Relocatable::from((2, 1))), None);
+        assert_eq!(
+            vm.get_integer((2, 0).into()).unwrap().as_ref(),
+            &program_data[0]
+        );
+        assert_eq!(
+            vm.get_integer_range((2, 2).into(), program_data.len() - 2)
+                .unwrap()
+                .into_iter()
+                .map(|value| *value)
+                .collect::<Vec<_>>(),
+            program_data[2..]
+        );
+        // output is the only builtin of the task
+        check_scope!(
+            &exec_scopes,
+            [("program_address", Relocatable::from((2, 5)))]
+        );
+        assert_eq!(vm.segments.segment_sizes.get(&2), Some(&program_data.len()));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_program_no_task_in_scope() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0))];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["program_header"];
+
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code::EXECUTE_TASK_LOAD_PROGRAM),
+            Err(HintError::VariableNotInScopeError(name)) if name.as_ref() == "task"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_program_hash_ok() {
+        let program_hash = compute_program_hash_chain(&task_program(), 0).unwrap();
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((2, 1), program_hash)];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["output_ptr"];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("task", task_program());
+
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EXECUTE_TASK_VALIDATE_HASH,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn validate_program_hash_mismatch() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), (2, 0)), ((2, 1), 17)];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["output_ptr"];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("task", task_program());
+
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EXECUTE_TASK_VALIDATE_HASH,
+                &mut exec_scopes
+            ),
+            Err(HintError::AssertionFailed(bx)) if bx.as_ref() == "Computed hash does not match input."
+        );
+    }
+
+    // Runs the fixture, returning the vm, the output pointer, the program data segment allocated by
+    // the hints (if any) and the result of the run
+    fn run_execute_task_fixture(
+        program_hash: Felt252,
+    ) -> (
+        VirtualMachine,
+        Relocatable,
+        Option<Relocatable>,
+        Result<(), CairoRunError>,
+    ) {
+        let program = Program::from_bytes(
+            include_bytes!(
+                "../../../../cairo_programs/manually_compiled/bootloader_execute_task.json"
+            ),
+            Some("main"),
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let main = program.shared_program_data.main.unwrap();
+
+        cairo_runner.initialize_function_runner(&mut vm).unwrap();
+        let output_ptr = vm.add_memory_segment();
+        cairo_runner
+            .exec_scopes
+            .insert_value("task", task_program());
+        let result = cairo_runner.run_from_entrypoint(
+            main,
+            &[
+                &MaybeRelocatable::from(output_ptr).into(),
+                &MaybeRelocatable::from(program_hash).into(),
+            ],
+            true,
+            None,
+            &mut vm,
+            &mut hint_processor,
+        );
+        let program_data_base = cairo_runner
+            .exec_scopes
+            .get::<Relocatable>("program_data_base")
+            .ok();
+        (vm, output_ptr, program_data_base, result)
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn execute_task_fixture_loads_the_data_hashed_by_the_utility() {
+        let expected_hash = compute_program_hash_chain(&task_program(), 0).unwrap();
+        let (vm, output_ptr, program_data_base, result) = run_execute_task_fixture(expected_hash);
+        assert_matches!(result, Ok(()));
+
+        // Once the fixture has set the bootloader version, the program data segment holds what
+        // the bootloader's hash_chain hashes
+        let program_data_ptr = program_data_base.unwrap();
+        let data_len = vm
+            .get_integer(program_data_ptr)
+            .unwrap()
+            .to_usize()
+            .unwrap();
+        let program_data = vm
+            .get_integer_range(program_data_ptr, data_len + 1)
+            .unwrap()
+            .into_iter()
+            .map(|value| *value)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            program_data,
+            program_hash_chain_data(&task_program(), 0).unwrap()
+        );
+        assert_eq!(compute_hash_chain(&program_data), Ok(expected_hash));
+        assert_eq!(
+            vm.get_integer((output_ptr + 1_usize).unwrap())
+                .unwrap()
+                .as_ref(),
+            &expected_hash
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn execute_task_fixture_rejects_a_wrong_hash() {
+        let (_, _, _, result) = run_execute_task_fixture(Felt252::from(17));
+        assert_matches!(
+            result,
+            Err(CairoRunError::VmException(ref e)) if matches!(
+                e.inner_exc,
+                VirtualMachineError::Hint(ref bx)
+                if matches!(bx.1, HintError::AssertionFailed(ref msg) if msg.as_ref() == "Computed hash does not match input.")
+            )
+        );
+    }
+}
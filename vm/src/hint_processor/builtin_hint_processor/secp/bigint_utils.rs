@@ -194,8 +194,6 @@ mod tests {
     use crate::types::relocatable::Relocatable;
     use crate::utils::test_utils::*;
 
-    use crate::vm::vm_core::VirtualMachine;
-
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
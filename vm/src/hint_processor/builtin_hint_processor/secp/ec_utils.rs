@@ -1,5 +1,6 @@
 use crate::Felt252;
 use crate::{
+    air_private_input::{PrivateInput, PrivateInputEcPoint},
     hint_processor::{
         builtin_hint_processor::{
             hint_utils::{
@@ -8,16 +9,21 @@ use crate::{
             },
             secp::{
                 bigint_utils::BigInt3,
-                secp_utils::{SECP256R1_ALPHA, SECP256R1_N, SECP_P},
+                secp_utils::{
+                    bigint3_split, curve_constants_from_scope, SECP256R1_ALPHA, SECP256R1_N, SECP_P,
+                },
             },
         },
         hint_processor_definition::HintReference,
     },
-    math_utils::{ec_double_slope, line_slope},
+    math_utils::{div_mod, ec_double_slope, line_slope},
     serde::deserialize_program::ApTracking,
     stdlib::{collections::HashMap, prelude::*},
-    types::exec_scope::ExecutionScopes,
-    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
+    types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
+    vm::{
+        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+        vm_core::VirtualMachine,
+    },
 };
 use num_bigint::{BigInt, BigUint};
 use num_integer::Integer;
@@ -45,6 +51,41 @@ impl EcPoint<'_> {
             y: BigInt3::from_base_addr((point_addr + 3)?, &format!("{}.y", name), vm)?,
         })
     }
+
+    /// Returns the packed `(x, y)` coordinates of the point
+    fn pack86(self) -> (BigInt, BigInt) {
+        (self.x.pack86(), self.y.pack86())
+    }
+}
+
+/// Appends the packed value of each of the points `point_names` to `collector`, indexing them
+/// after the points it already holds.
+pub fn collect_ec_points(
+    point_names: &[&str],
+    vm: &VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    collector: &mut Vec<PrivateInput>,
+) -> Result<(), HintError> {
+    for name in point_names {
+        let point = EcPoint::from_var_name(name, vm, ids_data, ap_tracking)?;
+        collector.push(PrivateInput::EcPoint(PrivateInputEcPoint {
+            index: collector.len(),
+            point_x: point.x.pack86(),
+            point_y: point.y.pack86(),
+        }));
+    }
+    Ok(())
+}
+
+// Packs ids.point.y
+fn pack_point_y(
+    vm: &VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<BigInt, HintError> {
+    let point_y = (get_relocatable_from_var_name("point", vm, ids_data, ap_tracking)? + 3i32)?;
+    Ok(BigInt3::from_base_addr(point_y, "point.y", vm)?.pack86())
 }
 
 /*
@@ -57,10 +98,7 @@ pub fn ec_negate(
     ap_tracking: &ApTracking,
     secp_p: BigInt,
 ) -> Result<(), HintError> {
-    //ids.point
-    let point_y = (get_relocatable_from_var_name("point", vm, ids_data, ap_tracking)? + 3i32)?;
-    let y_bigint3 = BigInt3::from_base_addr(point_y, "point.y", vm)?;
-    let y = y_bigint3.pack86();
+    let y = pack_point_y(vm, ids_data, ap_tracking)?;
     let value = (-y).mod_floor(&secp_p);
     exec_scopes.insert_value("value", value);
     exec_scopes.insert_value("SECP_P", secp_p);
@@ -107,6 +145,26 @@ pub fn ec_negate_embedded_secp_p(
     ec_negate(vm, exec_scopes, ids_data, ap_tracking, secp_p)
 }
 
+/*
+Implements hint:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import pack
+    from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_P as SECP_P
+
+    y = pack(ids.point.y, PRIME) % SECP_P
+    # The modulo operation in python always returns a nonnegative number.
+    value = (-y) % SECP_P
+%}
+*/
+pub fn ec_negate_secp256r1(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    ec_negate(vm, exec_scopes, ids_data, ap_tracking, SECP256R1_P.clone())
+}
+
 /*
 Implements hint:
 %{
@@ -132,12 +190,44 @@ pub fn compute_doubling_slope(
     //ids.point
     let point = EcPoint::from_var_name(point_alias, vm, ids_data, ap_tracking)?;
 
-    let value = ec_double_slope(&(point.x.pack86(), point.y.pack86()), alpha, secp_p)?;
+    let value = ec_double_slope(&point.pack86(), alpha, secp_p)?;
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("slope", value);
     Ok(())
 }
 
+/*
+Implements hint:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import pack
+    from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_ALPHA as ALPHA
+    from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_P as SECP_P
+    from starkware.python.math_utils import ec_double_slope
+
+    # Compute the slope.
+    x = pack(ids.point.x, PRIME)
+    y = pack(ids.point.y, PRIME)
+    value = slope = ec_double_slope(point=(x, y), alpha=ALPHA, p=SECP_P)
+%}
+*/
+pub fn compute_doubling_slope_secp256r1(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    exec_scopes.insert_value("ALPHA", SECP256R1_ALPHA.clone());
+    compute_doubling_slope(
+        vm,
+        exec_scopes,
+        ids_data,
+        ap_tracking,
+        "point",
+        &SECP256R1_P,
+        &SECP256R1_ALPHA,
+    )
+}
+
 /*
 Implements hint:
 %{
@@ -158,10 +248,12 @@ pub fn compute_doubling_slope_external_consts(
 ) -> Result<(), HintError> {
     //ids.point
     let point = EcPoint::from_var_name("point", vm, ids_data, ap_tracking)?;
-    let secp_p: BigInt = exec_scopes.get("SECP_P")?;
-    let alpha: BigInt = exec_scopes.get("ALPHA")?;
+    let (secp_p, alpha) = match curve_constants_from_scope(exec_scopes)? {
+        Some(curve) => (curve.p.clone(), curve.alpha.clone()),
+        None => (exec_scopes.get("SECP_P")?, exec_scopes.get("ALPHA")?),
+    };
 
-    let value = ec_double_slope(&(point.x.pack86(), point.y.pack86()), &alpha, &secp_p)?;
+    let value = ec_double_slope(&point.pack86(), &alpha, &secp_p)?;
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("slope", value);
     Ok(())
@@ -216,16 +308,127 @@ pub fn compute_slope(
 
     let secp_p: BigInt = exec_scopes.get("SECP_P")?;
 
-    let value = line_slope(
-        &(point0.x.pack86(), point0.y.pack86()),
-        &(point1.x.pack86(), point1.y.pack86()),
-        &secp_p,
-    )?;
+    let point0 = point0.pack86();
+    let point1 = point1.pack86();
+
+    // The point at infinity is represented as (0, 0) and has no slope, flag it so that the
+    // cairo code can branch on it instead of failing on a division by zero. A slope left by a
+    // previous hint must not be taken for this one
+    if is_point_at_infinity(&point0) || is_point_at_infinity(&point1) {
+        exec_scopes.insert_value("has_infinity", Felt252::ONE);
+        exec_scopes.delete_variable("value");
+        exec_scopes.delete_variable("slope");
+        return Ok(());
+    }
+    exec_scopes.insert_value("has_infinity", Felt252::ZERO);
+
+    // The line through two equal points is undefined (its slope would divide by x0 - x1 = 0)
+    if point0.0.mod_floor(&secp_p) == point1.0.mod_floor(&secp_p)
+        && point0.1.mod_floor(&secp_p) == point1.1.mod_floor(&secp_p)
+    {
+        let point0_addr = get_relocatable_from_var_name(point0_alias, vm, ids_data, ap_tracking)?;
+        return Err(VirtualMachineError::PointsAreEqual(Box::new(point0_addr)).into());
+    }
+
+    let value = line_slope(&point0, &point1, &secp_p)?;
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("slope", value);
     Ok(())
 }
 
+/*
+Helper computing the slope of ids.point0 and ids.point1 from an inverse of their x difference
+provided by the caller, leaving it in scope as the compute_slope hints do. Cairo-lang has no hint
+for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack
+
+    # Compute the slope using the provided inverse of x1 - x0.
+    x0 = pack(ids.point0.x, PRIME)
+    y0 = pack(ids.point0.y, PRIME)
+    x1 = pack(ids.point1.x, PRIME)
+    y1 = pack(ids.point1.y, PRIME)
+    dx_inv = pack(ids.dx_inv, PRIME)
+    assert dx_inv * (x1 - x0) % SECP_P == 1, "dx_inv is not the inverse of x1 - x0."
+    value = slope = (y1 - y0) * dx_inv % SECP_P
+%}
+*/
+pub fn compute_slope_with_inv(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    exec_scopes.insert_value("SECP_P", SECP_P.clone());
+    let point0 = EcPoint::from_var_name("point0", vm, ids_data, ap_tracking)?;
+    let point1 = EcPoint::from_var_name("point1", vm, ids_data, ap_tracking)?;
+    let dx_inv = BigInt3::from_var_name("dx_inv", vm, ids_data, ap_tracking)?.pack86();
+
+    let dx = point1.x.pack86() - point0.x.pack86();
+    if !(&dx_inv * &dx).mod_floor(&SECP_P).is_one() {
+        return Err(HintError::SecpBadInverse(Box::new((dx_inv, dx))));
+    }
+
+    let value = ((point1.y.pack86() - point0.y.pack86()) * dx_inv).mod_floor(&SECP_P);
+    exec_scopes.insert_value("value", value.clone());
+    exec_scopes.insert_value("slope", value);
+    Ok(())
+}
+
+// Returns true if the packed point is the identity element, which is represented as (0, 0)
+fn is_point_at_infinity(point: &(BigInt, BigInt)) -> bool {
+    point.0.is_zero() && point.1.is_zero()
+}
+
+/*
+Helper converting ids.point from Jacobian to affine secp256k1 coordinates, writing them to ids.res.
+Cairo-lang has no hint for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack, split
+
+    x = pack(ids.point.x, PRIME)
+    y = pack(ids.point.y, PRIME)
+    z = pack(ids.point.z, PRIME) % SECP_P
+    assert z != 0, "The point at infinity has no affine representation."
+    z_inv = pow(z, -1, SECP_P)
+    segments.write_arg(ids.res.x.address_, split(x * pow(z_inv, 2, SECP_P) % SECP_P))
+    segments.write_arg(ids.res.y.address_, split(y * pow(z_inv, 3, SECP_P) % SECP_P))
+%}
+*/
+pub fn secp_jacobian_to_affine(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    //ids.point
+    let point_addr = get_relocatable_from_var_name("point", vm, ids_data, ap_tracking)?;
+    let x = BigInt3::from_base_addr(point_addr, "point.x", vm)?.pack86();
+    let y = BigInt3::from_base_addr((point_addr + 3)?, "point.y", vm)?.pack86();
+    let z = BigInt3::from_base_addr((point_addr + 6)?, "point.z", vm)?
+        .pack86()
+        .mod_floor(&SECP_P);
+    if z.is_zero() {
+        return Err(HintError::JacobianPointAtInfinity);
+    }
+    let z_inv = div_mod(&BigInt::one(), &z, &SECP_P)?;
+    let z_inv_sq = (&z_inv * &z_inv).mod_floor(&SECP_P);
+    let affine_x = (x * &z_inv_sq).mod_floor(&SECP_P);
+    let affine_y = (y * z_inv_sq * z_inv).mod_floor(&SECP_P);
+
+    //ids.res
+    let res_addr = get_relocatable_from_var_name("res", vm, ids_data, ap_tracking)?;
+    for (offset, value) in [(0, affine_x), (3, affine_y)] {
+        let value = value.to_biguint().ok_or(HintError::BigIntToBigUintFail)?;
+        let limbs: Vec<MaybeRelocatable> = bigint3_split(&value)?
+            .iter()
+            .map(|limb| Felt252::from(limb).into())
+            .collect();
+        vm.write_arg((res_addr + offset)?, &limbs)
+            .map_err(HintError::Memory)?;
+    }
+    Ok(())
+}
+
 /*
 Implements hint:
 %{from starkware.cairo.common.cairo_secp.secp_utils import pack
@@ -420,6 +623,111 @@ pub fn ec_mul_inner(
     insert_value_into_ap(vm, scalar)
 }
 
+/// Bit decomposition of the scalar of an `ec_mul_inner` loop, cached by [`ec_mul_inner_cached`].
+/// Each iteration of the loop runs in a new frame, so the cache is keyed by the value of the scalar
+/// expected in the next iteration (`scalar // 2`) instead of by its address.
+#[derive(Debug, Clone)]
+struct EcMulInnerBits {
+    next_scalar: Felt252,
+    // Little endian limbs of next_scalar
+    limbs: [u64; 4],
+    half: Felt252,
+}
+
+impl EcMulInnerBits {
+    fn new(scalar: &Felt252) -> Self {
+        EcMulInnerBits {
+            next_scalar: *scalar,
+            limbs: scalar.to_le_digits(),
+            half: Felt252::TWO.inverse().expect("nonzero by construction"),
+        }
+    }
+
+    // Returns the low bit of the current scalar and advances to the next one
+    fn pop(&mut self, scalar: &Felt252) -> Felt252 {
+        let bit = Felt252::from(self.limbs[0] & 1);
+        for i in 0..3 {
+            self.limbs[i] = (self.limbs[i] >> 1) | (self.limbs[i + 1] << 63);
+        }
+        self.limbs[3] >>= 1;
+        self.next_scalar = (scalar - bit) * self.half;
+        bit
+    }
+}
+
+/*
+Implements hint:
+%{ memory[ap] = (ids.scalar % PRIME) % 2 %}
+Same as ec_mul_inner, but the first iteration of the loop decomposes the scalar and caches its bits
+in the execution scopes, so the following iterations only check that the scalar is the expected one
+and pop its bit.
+*/
+pub fn ec_mul_inner_cached(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let scalar = get_integer_from_var_name("scalar", vm, ids_data, ap_tracking)?;
+    let bit = match exec_scopes.get_mut_ref::<EcMulInnerBits>("ec_mul_inner_bits") {
+        Ok(bits) if bits.next_scalar == *scalar => bits.pop(&scalar),
+        _ => {
+            let mut bits = EcMulInnerBits::new(&scalar);
+            let bit = bits.pop(&scalar);
+            exec_scopes.insert_value("ec_mul_inner_bits", bits);
+            bit
+        }
+    };
+    insert_value_into_ap(vm, bit)
+}
+
+/*
+Implements hint:
+%{ memory[ap] = (ids.scalar % PRIME) % 2 %}
+where ids.scalar may also be a BigInt3, in which case its limbs are packed before taking the low bit.
+The form of ids.scalar is detected from the type of its reference.
+*/
+pub fn ec_mul_inner_packed(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let is_bigint3 = ids_data
+        .get("scalar")
+        .and_then(|reference| reference.cairo_type.as_deref())
+        .is_some_and(|cairo_type| cairo_type.ends_with("BigInt3"));
+    if !is_bigint3 {
+        return ec_mul_inner_cached(vm, exec_scopes, ids_data, ap_tracking);
+    }
+    let scalar = BigInt3::from_var_name("scalar", vm, ids_data, ap_tracking)?.pack86();
+    let low_bit = if scalar.is_odd() {
+        Felt252::ONE
+    } else {
+        Felt252::ZERO
+    };
+    insert_value_into_ap(vm, low_bit)
+}
+
+/*
+Helper writing the loop condition of an ec_mul_inner style loop to memory[ap]. Cairo-lang has no
+hint for it, it mirrors:
+%{ memory[ap] = 1 if ids.scalar > 0 else 0 %}
+*/
+pub fn ec_mul_continue(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let scalar = get_integer_from_var_name("scalar", vm, ids_data, ap_tracking)?;
+    let continue_loop = if scalar.is_zero() {
+        Felt252::ZERO
+    } else {
+        Felt252::ONE
+    };
+    insert_value_into_ap(vm, continue_loop)
+}
+
 /*
 Implements hint:
 %{ from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_ALPHA as ALPHA %}
@@ -496,7 +804,7 @@ pub fn n_pair_bits(
     // If m is too high the shift result will always be zero
     let m = m_cow.as_ref().to_u32().unwrap_or(253);
     if m >= 253 {
-        return insert_value_from_var_name(result_name, 0, vm, ids_data, ap_tracking);
+        return insert_value_from_var_name(result_name, Felt252::ZERO, vm, ids_data, ap_tracking);
     }
     if m + 1 < number_of_pairs {
         return Err(HintError::NPairBitsTooLowM);
@@ -540,24 +848,46 @@ pub fn n_pair_bits(
     insert_value_from_var_name(result_name, res, vm, ids_data, ap_tracking)
 }
 
+/*
+Hint self-test mirroring the range check cairo applies to each limb before packing a point:
+memory[ap] = 1 if all the limbs of ids.point.x and ids.point.y are in [0, 2**86), 0 otherwise
+*/
+pub fn secp_assert_limbs_ranged(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let point = EcPoint::from_var_name("point", vm, ids_data, ap_tracking)?;
+    let in_range = point
+        .x
+        .limbs
+        .iter()
+        .chain(point.y.limbs.iter())
+        .all(|limb| limb.bits() <= 86);
+    insert_value_into_ap(vm, Felt252::from(in_range))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::hint_processor::builtin_hint_processor::hint_code;
+    use crate::hint_processor::builtin_hint_processor::hint_utils::get_reference_from_var_name;
     use crate::hint_processor::builtin_hint_processor::secp::secp_utils::SECP_P_V2;
     use crate::stdlib::string::ToString;
+    use crate::types::errors::math_errors::MathError;
 
     use crate::{
-        any_box,
+        any_box, felt_str,
         hint_processor::{
             builtin_hint_processor::builtin_hint_processor_definition::{
                 BuiltinHintProcessor, HintProcessorData,
             },
             hint_processor_definition::HintProcessorLogic,
         },
+        relocatable,
         types::{exec_scope::ExecutionScopes, relocatable::Relocatable},
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
+        vm::{runners::builtin_runner::RangeCheckBuiltinRunner, vm_core::VirtualMachineBuilder},
     };
     use assert_matches::assert_matches;
 
@@ -568,11 +898,17 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_ec_negate_ok() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\n\ny = pack(ids.point.y, PRIME) % SECP_P\n# The modulo operation in python always returns a nonnegative number.\nvalue = (-y) % SECP_P";
-        let mut vm = vm_with_range_check!();
 
-        vm.segments = segments![((1, 3), 2645i32), ((1, 4), 454i32), ((1, 5), 206i32)];
-        //Initialize fp
-        vm.run_context.fp = 1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 3), 2645i32),
+                ((1, 4), 454i32),
+                ((1, 5), 206i32)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
         //Create hint_data
         let ids_data = ids_data!["point"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -587,20 +923,67 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_assert_limbs_ranged_in_range() {
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), 77371252455336267181195263_i128),
+            ((1, 1), 0),
+            ((1, 2), 5),
+            ((1, 3), 3),
+            ((1, 4), 77371252455336267181195263_i128),
+            ((1, 5), 1)
+        ];
+        vm.run_context.fp = 1;
+        vm.run_context.ap = 6;
+        let ids_data = ids_data!["point"];
+        assert_matches!(
+            secp_assert_limbs_ranged(&mut vm, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 6), 1)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_assert_limbs_ranged_limb_too_large() {
+        let mut vm = vm!();
+        // ids.point.y.d1 = 2**86
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), 0),
+            ((1, 2), 5),
+            ((1, 3), 3),
+            ((1, 4), 77371252455336267181195264_i128),
+            ((1, 5), 1)
+        ];
+        vm.run_context.fp = 1;
+        vm.run_context.ap = 6;
+        let ids_data = ids_data!["point"];
+        assert_matches!(
+            secp_assert_limbs_ranged(&mut vm, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 6), 0)];
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_ec_negate_embedded_secp_p_ok() {
         let hint_code = hint_code::EC_NEGATE_EMBEDDED_SECP;
-        let mut vm = vm_with_range_check!();
 
         let (y0, y1, y2) = (2645i32, 454i32, 206i32);
 
         let y = (BigInt::from(y2) << (86 * 2)) + (BigInt::from(y1) << 86) + y0;
         let minus_y = (BigInt::one() << 255) - 19 - y;
 
-        vm.segments = segments![((1, 3), y0), ((1, 4), y1), ((1, 5), y2)];
-        //Initialize fp
-        vm.run_context.fp = 1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![((1, 3), y0), ((1, 4), y1), ((1, 5), y2)])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
         //Create hint_data
         let ids_data = ids_data!["point"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -617,18 +1000,20 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_compute_doubling_slope_ok() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\nfrom starkware.python.math_utils import ec_double_slope\n\n# Compute the slope.\nx = pack(ids.point.x, PRIME)\ny = pack(ids.point.y, PRIME)\nvalue = slope = ec_double_slope(point=(x, y), alpha=0, p=SECP_P)";
-        let mut vm = vm_with_range_check!();
-        vm.segments = segments![
-            ((1, 0), 614323u64),
-            ((1, 1), 5456867u64),
-            ((1, 2), 101208u64),
-            ((1, 3), 773712524u64),
-            ((1, 4), 77371252u64),
-            ((1, 5), 5298795u64)
-        ];
 
-        //Initialize fp
-        vm.run_context.fp = 1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 614323u64),
+                ((1, 1), 5456867u64),
+                ((1, 2), 101208u64),
+                ((1, 3), 773712524u64),
+                ((1, 4), 77371252u64),
+                ((1, 5), 5298795u64)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
 
         let ids_data = ids_data!["point"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -658,18 +1043,20 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_ec_double_scope_v2_hint_ok() {
         let hint_code = hint_code::EC_DOUBLE_SLOPE_V2;
-        let mut vm = vm_with_range_check!();
-        vm.segments = segments![
-            ((1, 0), 512),
-            ((1, 1), 2412),
-            ((1, 2), 133),
-            ((1, 3), 64),
-            ((1, 4), 0),
-            ((1, 5), 6546)
-        ];
 
-        //Initialize fp
-        vm.run_context.fp = 1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 512),
+                ((1, 1), 2412),
+                ((1, 2), 133),
+                ((1, 3), 64),
+                ((1, 4), 0),
+                ((1, 5), 6546)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
 
         let ids_data = ids_data!["point"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -700,18 +1087,20 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_compute_doubling_slope_wdivmod_ok() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\nfrom starkware.python.math_utils import div_mod\n\n# Compute the slope.\nx = pack(ids.pt.x, PRIME)\ny = pack(ids.pt.y, PRIME)\nvalue = slope = div_mod(3 * x ** 2, 2 * y, SECP_P)";
-        let mut vm = vm_with_range_check!();
-        vm.segments = segments![
-            ((1, 0), 614323u64),
-            ((1, 1), 5456867u64),
-            ((1, 2), 101208u64),
-            ((1, 3), 773712524u64),
-            ((1, 4), 77371252u64),
-            ((1, 5), 5298795u64)
-        ];
 
-        //Initialize fp
-        vm.run_context.fp = 1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 614323u64),
+                ((1, 1), 5456867u64),
+                ((1, 2), 101208u64),
+                ((1, 3), 773712524u64),
+                ((1, 4), 77371252u64),
+                ((1, 5), 5298795u64)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
 
         let ids_data = ids_data!["pt"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -741,18 +1130,20 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_compute_doubling_slope_with_custom_consts_ok() {
         let hint_code = hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS;
-        let mut vm = vm_with_range_check!();
-        vm.segments = segments![
-            ((1, 0), 614323u64),
-            ((1, 1), 5456867u64),
-            ((1, 2), 101208u64),
-            ((1, 3), 773712524u64),
-            ((1, 4), 77371252u64),
-            ((1, 5), 5298795u64)
-        ];
 
-        //Initialize fp
-        vm.run_context.fp = 1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 614323u64),
+                ((1, 1), 5456867u64),
+                ((1, 2), 101208u64),
+                ((1, 3), 773712524u64),
+                ((1, 4), 77371252u64),
+                ((1, 5), 5298795u64)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
 
         let ids_data = ids_data!["point"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -785,26 +1176,28 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_compute_slope_ok() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\nfrom starkware.python.math_utils import line_slope\n\n# Compute the slope.\nx0 = pack(ids.point0.x, PRIME)\ny0 = pack(ids.point0.y, PRIME)\nx1 = pack(ids.point1.x, PRIME)\ny1 = pack(ids.point1.y, PRIME)\nvalue = slope = line_slope(point1=(x0, y0), point2=(x1, y1), p=SECP_P)";
-        let mut vm = vm_with_range_check!();
 
         //Insert ids.point0 and ids.point1 into memory
-        vm.segments = segments![
-            ((1, 0), 134),
-            ((1, 1), 5123),
-            ((1, 2), 140),
-            ((1, 3), 1232),
-            ((1, 4), 4652),
-            ((1, 5), 720),
-            ((1, 6), 156),
-            ((1, 7), 6545),
-            ((1, 8), 100010),
-            ((1, 9), 1123),
-            ((1, 10), 1325),
-            ((1, 11), 910)
-        ];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 134),
+                ((1, 1), 5123),
+                ((1, 2), 140),
+                ((1, 3), 1232),
+                ((1, 4), 4652),
+                ((1, 5), 720),
+                ((1, 6), 156),
+                ((1, 7), 6545),
+                ((1, 8), 100010),
+                ((1, 9), 1123),
+                ((1, 10), 1325),
+                ((1, 11), 910)
+            ])
+            .unwrap()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
 
-        //Initialize fp
-        vm.run_context.fp = 14;
         let ids_data = HashMap::from([
             ("point0".to_string(), HintReference::new_simple(-14)),
             ("point1".to_string(), HintReference::new_simple(-8)),
@@ -834,91 +1227,612 @@ mod tests {
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
-    fn run_compute_slope_v2_ok() {
-        let mut vm = vm_with_range_check!();
-
-        //Insert ids.point0 and ids.point1 into memory
-        vm.segments = segments![
-            ((1, 0), 512),
-            ((1, 1), 2412),
-            ((1, 2), 133),
-            ((1, 3), 64),
-            ((1, 4), 0),
-            ((1, 5), 6546),
-            ((1, 6), 7),
-            ((1, 7), 8),
-            ((1, 8), 123),
-            ((1, 9), 1),
-            ((1, 10), 7),
-            ((1, 11), 465)
-        ];
-        // let point_1 = EcPoint(BigInt3(512,2412,133), BigInt3(64,0,6546));
-        // let point_2 = EcPoint(BigInt3(7,8,123), BigInt3(1,7,465));
+    fn run_compute_slope_equal_points() {
+        let hint_code = hint_code::COMPUTE_SLOPE_V1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 134),
+                ((1, 1), 5123),
+                ((1, 2), 140),
+                ((1, 3), 1232),
+                ((1, 4), 4652),
+                ((1, 5), 720),
+                ((1, 6), 134),
+                ((1, 7), 5123),
+                ((1, 8), 140),
+                ((1, 9), 1232),
+                ((1, 10), 4652),
+                ((1, 11), 720)
+            ])
+            .unwrap()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
 
-        //Initialize fp
-        vm.run_context.fp = 14;
         let ids_data = HashMap::from([
             ("point0".to_string(), HintReference::new_simple(-14)),
             ("point1".to_string(), HintReference::new_simple(-8)),
         ]);
         let mut exec_scopes = ExecutionScopes::new();
 
-        //Execute the hint
         assert_matches!(
-            run_hint!(vm, ids_data, hint_code::COMPUTE_SLOPE_V2, &mut exec_scopes),
-            Ok(())
-        );
-        check_scope!(
-            &exec_scopes,
-            [
-                (
-                    "value",
-                    bigint_str!(
-                        "39376930140709393693483102164172662915882483986415749881375763965703119677959"
-                    )
-                ),
-                (
-                    "slope",
-                    bigint_str!(
-                        "39376930140709393693483102164172662915882483986415749881375763965703119677959"
-                    )
-                )
-            ]
+            run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
+            Err(HintError::Internal(VirtualMachineError::PointsAreEqual(bx))) if *bx == Relocatable::from((1, 0))
         );
     }
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
-    fn run_compute_slope_wdivmod_ok() {
-        let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\nfrom starkware.python.math_utils import div_mod\n\n# Compute the slope.\nx0 = pack(ids.pt0.x, PRIME)\ny0 = pack(ids.pt0.y, PRIME)\nx1 = pack(ids.pt1.x, PRIME)\ny1 = pack(ids.pt1.y, PRIME)\nvalue = slope = div_mod(y0 - y1, x0 - x1, SECP_P)";
-        let mut vm = vm_with_range_check!();
-
-        // Insert ids.pt0 and ids.pt1 into memory
-        vm.segments = segments![
+    fn run_compute_slope_packed_points() {
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+
+        //Insert ids.point0 and ids.point1 into memory, same points as run_compute_slope_ok
+        pack_into_memory(
+            &mut vm.segments.memory,
+            (1, 0),
+            (
+                &biguint_str!("838083498911032969414721426845751663479194726707495046"),
+                &biguint_str!("4310143708685312414132851373791311001152018708061750480"),
+            ),
+        );
+        pack_into_memory(
+            &mut vm.segments.memory,
+            (1, 6),
+            (
+                &biguint_str!("598690933757802909079758930917732431123730945300852899996"),
+                &biguint_str!("5447542742921714301195686800590274179465290738470749283"),
+            ),
+        );
+        check_memory![
+            vm.segments.memory,
             ((1, 0), 134),
             ((1, 1), 5123),
             ((1, 2), 140),
-            ((1, 3), 1232),
-            ((1, 4), 4652),
-            ((1, 5), 720),
-            ((1, 6), 156),
-            ((1, 7), 6545),
-            ((1, 8), 100010),
             ((1, 9), 1123),
             ((1, 10), 1325),
             ((1, 11), 910)
         ];
 
-        // Initialize fp
-        vm.run_context.fp = 14;
         let ids_data = HashMap::from([
-            ("pt0".to_string(), HintReference::new_simple(-14)),
-            ("pt1".to_string(), HintReference::new_simple(-8)),
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
         ]);
         let mut exec_scopes = ExecutionScopes::new();
 
-        // Execute the hint
-        assert_matches!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        //Execute the hint
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code::COMPUTE_SLOPE_V1, &mut exec_scopes),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [(
+                "slope",
+                bigint_str!(
+                    "41419765295989780131385135514529906223027172305400087935755859001910844026631"
+                )
+            )]
+        );
+    }
+
+    // Writes the points of run_compute_slope_ok as ids.point0 and ids.point1, and `dx_inv` as
+    // ids.dx_inv
+    fn compute_slope_with_inv_vm(dx_inv: &BigUint) -> VirtualMachine {
+        let mut vm = VirtualMachineBuilder::default()
+            .with_run_context(0, 18, (0, 0).into())
+            .build();
+        pack_into_memory(
+            &mut vm.segments.memory,
+            (1, 0),
+            (
+                &biguint_str!("838083498911032969414721426845751663479194726707495046"),
+                &biguint_str!("4310143708685312414132851373791311001152018708061750480"),
+            ),
+        );
+        pack_into_memory(
+            &mut vm.segments.memory,
+            (1, 6),
+            (
+                &biguint_str!("598690933757802909079758930917732431123730945300852899996"),
+                &biguint_str!("5447542742921714301195686800590274179465290738470749283"),
+            ),
+        );
+        pack_into_memory(&mut vm.segments.memory, (1, 12), (dx_inv, &BigUint::zero()));
+        vm
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_slope_with_inv_ok() {
+        let dx = bigint_str!("598690933757802909079758930917732431123730945300852899996")
+            - bigint_str!("838083498911032969414721426845751663479194726707495046");
+        let dx_inv = div_mod(&BigInt::one(), &dx, &SECP_P).unwrap();
+        let mut vm = compute_slope_with_inv_vm(&dx_inv.to_biguint().unwrap());
+        let ids_data = non_continuous_ids_data![("point0", -18), ("point1", -12), ("dx_inv", -6)];
+        let mut exec_scopes = ExecutionScopes::new();
+
+        assert_matches!(
+            compute_slope_with_inv(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "slope",
+                    bigint_str!(
+                        "41419765295989780131385135514529906223027172305400087935755859001910844026631"
+                    )
+                ),
+                ("SECP_P", SECP_P.clone())
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_slope_with_inv_bad_inverse() {
+        let mut vm = compute_slope_with_inv_vm(&BigUint::from(3_u32));
+        let ids_data = non_continuous_ids_data![("point0", -18), ("point1", -12), ("dx_inv", -6)];
+        let dx = bigint_str!("598690933757802909079758930917732431123730945300852899996")
+            - bigint_str!("838083498911032969414721426845751663479194726707495046");
+
+        assert_matches!(
+            compute_slope_with_inv(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &ids_data,
+                &ApTracking::default()
+            ),
+            Err(HintError::SecpBadInverse(bx)) if *bx == (BigInt::from(3), dx)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_slope_collects_ec_points() {
+        let point0 = (
+            biguint_str!("838083498911032969414721426845751663479194726707495046"),
+            biguint_str!("4310143708685312414132851373791311001152018708061750480"),
+        );
+        let point1 = (
+            biguint_str!("598690933757802909079758930917732431123730945300852899996"),
+            biguint_str!("5447542742921714301195686800590274179465290738470749283"),
+        );
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+        pack_into_memory(&mut vm.segments.memory, (1, 0), (&point0.0, &point0.1));
+        pack_into_memory(&mut vm.segments.memory, (1, 6), (&point1.0, &point1.1));
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        let hint_data =
+            HintProcessorData::new_default(hint_code::COMPUTE_SLOPE_V1.to_string(), ids_data);
+        let mut hint_processor = BuiltinHintProcessor::new_empty().with_ec_point_collection();
+
+        assert_matches!(
+            hint_processor.execute_hint(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            hint_processor.collected_ec_points(),
+            Some(
+                &[
+                    PrivateInput::EcPoint(PrivateInputEcPoint {
+                        index: 0,
+                        point_x: point0.0.into(),
+                        point_y: point0.1.into(),
+                    }),
+                    PrivateInput::EcPoint(PrivateInputEcPoint {
+                        index: 1,
+                        point_x: point1.0.into(),
+                        point_y: point1.1.into(),
+                    }),
+                ][..]
+            )
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_double_records_ec_trace() {
+        let point = (
+            biguint_str!("838083498911032969414721426845751663479194726707495046"),
+            biguint_str!("4310143708685312414132851373791311001152018708061750480"),
+        );
+        let mut vm = VirtualMachineBuilder::default()
+            .with_run_context(0, 9, (0, 0).into())
+            .build();
+        pack_into_memory(&mut vm.segments.memory, (1, 0), (&point.0, &point.1));
+        let ids_data = HashMap::from([
+            ("point".to_string(), HintReference::new_simple(-9)),
+            ("slope".to_string(), HintReference::new_simple(-3)),
+        ]);
+        let mut hint_processor = BuiltinHintProcessor::new_empty().with_ec_trace();
+        let mut exec_scopes = ExecutionScopes::new();
+        let mut run = |vm: &mut VirtualMachine, exec_scopes: &mut ExecutionScopes, code: &str| {
+            let hint_data = HintProcessorData::new_default(code.to_string(), ids_data.clone());
+            hint_processor.execute_hint(vm, exec_scopes, &any_box!(hint_data), &HashMap::new())
+        };
+
+        assert_matches!(
+            run(&mut vm, &mut exec_scopes, hint_code::EC_DOUBLE_SLOPE_V1),
+            Ok(())
+        );
+        let slope: BigInt = exec_scopes.get("slope").unwrap();
+        // Write ids.slope as the cairo code would
+        for (i, limb) in bigint3_split(&slope.to_biguint().unwrap())
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            vm.insert_value((1, 6 + i).into(), Felt252::from(limb))
+                .unwrap();
+        }
+        assert_matches!(
+            run(
+                &mut vm,
+                &mut exec_scopes,
+                hint_code::EC_DOUBLE_ASSIGN_NEW_X_V1
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            run(&mut vm, &mut exec_scopes, hint_code::EC_DOUBLE_ASSIGN_NEW_Y),
+            Ok(())
+        );
+
+        let new_x: BigInt = exec_scopes.get("new_x").unwrap();
+        let new_y: BigInt = exec_scopes.get("new_y").unwrap();
+        assert_eq!(
+            exec_scopes.get_list::<BigInt>("ec_trace").unwrap(),
+            vec![slope, new_x, new_y]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ec_trace_disabled_by_default() {
+        let mut vm = VirtualMachineBuilder::default().build();
+        let mut exec_scopes = scope![
+            ("slope", BigInt::from(3)),
+            ("x", BigInt::from(1)),
+            ("new_x", BigInt::from(2)),
+            ("y", BigInt::from(1)),
+            ("SECP_P", (*SECP_P).clone())
+        ];
+        assert_matches!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::EC_DOUBLE_ASSIGN_NEW_Y,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert!(exec_scopes.get_list::<BigInt>("ec_trace").is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ec_point_collection_disabled_by_default() {
+        let mut vm = VirtualMachineBuilder::default()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+        pack_into_memory(
+            &mut vm.segments.memory,
+            (1, 0),
+            (&1_u32.into(), &2_u32.into()),
+        );
+        pack_into_memory(
+            &mut vm.segments.memory,
+            (1, 6),
+            (&3_u32.into(), &4_u32.into()),
+        );
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        let hint_data =
+            HintProcessorData::new_default(hint_code::COMPUTE_SLOPE_V1.to_string(), ids_data);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+
+        assert_matches!(
+            hint_processor.execute_hint(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+            ),
+            Ok(())
+        );
+        assert_eq!(hint_processor.collected_ec_points(), None);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_slope_reference_for_point0() {
+        let vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        let reference = get_reference_from_var_name("point0", &ids_data).unwrap();
+        assert_eq!(reference, &HintReference::new_simple(-14));
+        assert_matches!(
+            get_relocatable_from_var_name("point0", &vm, &ids_data, &ApTracking::new()),
+            Ok(x) if x == Relocatable::from((1, 0))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_slope_point_at_infinity() {
+        //Insert ids.point0 (the identity) and ids.point1 into memory
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 0),
+                ((1, 1), 0),
+                ((1, 2), 0),
+                ((1, 3), 0),
+                ((1, 4), 0),
+                ((1, 5), 0),
+                ((1, 6), 156),
+                ((1, 7), 6545),
+                ((1, 8), 100010),
+                ((1, 9), 1123),
+                ((1, 10), 1325),
+                ((1, 11), 910)
+            ])
+            .unwrap()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        // Left by a previous slope computation
+        let mut exec_scopes = scope![("value", BigInt::from(7)), ("slope", BigInt::from(7))];
+
+        //Execute the hint
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code::COMPUTE_SLOPE_V1, &mut exec_scopes),
+            Ok(())
+        );
+        check_scope!(&exec_scopes, [("has_infinity", Felt252::ONE)]);
+        assert!(exec_scopes.get::<BigInt>("value").is_err());
+        assert!(exec_scopes.get::<BigInt>("slope").is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_slope_point_at_infinity_skips_ec_trace() {
+        //Insert ids.point0 (the identity) and ids.point1 into memory
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 0),
+                ((1, 1), 0),
+                ((1, 2), 0),
+                ((1, 3), 0),
+                ((1, 4), 0),
+                ((1, 5), 0),
+                ((1, 6), 156),
+                ((1, 7), 6545),
+                ((1, 8), 100010),
+                ((1, 9), 1123),
+                ((1, 10), 1325),
+                ((1, 11), 910)
+            ])
+            .unwrap()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        let mut exec_scopes = ExecutionScopes::new();
+        let mut hint_processor = BuiltinHintProcessor::new_empty().with_ec_trace();
+        let hint_data =
+            HintProcessorData::new_default(hint_code::COMPUTE_SLOPE_V1.to_string(), ids_data);
+
+        //Execute the hint
+        assert_matches!(
+            hint_processor.execute_hint(
+                &mut vm,
+                &mut exec_scopes,
+                &any_box!(hint_data),
+                &HashMap::new()
+            ),
+            Ok(())
+        );
+        check_scope!(&exec_scopes, [("has_infinity", Felt252::ONE)]);
+        assert!(exec_scopes.get_list::<BigInt>("ec_trace").is_err());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_jacobian_to_affine_ok() {
+        //Insert ids.point, the secp256k1 generator in jacobian coordinates with Z = 2
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), ("68471462235074529071227441", 10)),
+                ((1, 1), ("50004704085362356901491420", 10)),
+                ((1, 2), ("17451978016815357627820056", 10)),
+                ((1, 3), ("51534242499477999666507106", 10)),
+                ((1, 4), ("76775092964208484435956944", 10)),
+                ((1, 5), ("4974666894430672520139389", 10)),
+                ((1, 6), 2),
+                ((1, 7), 0),
+                ((1, 8), 0)
+            ])
+            .unwrap()
+            .with_run_context(0, 15, (0, 0).into())
+            .build();
+
+        let ids_data = HashMap::from([
+            ("point".to_string(), HintReference::new_simple(-15)),
+            ("res".to_string(), HintReference::new_simple(-6)),
+        ]);
+
+        assert_matches!(
+            secp_jacobian_to_affine(&mut vm, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+
+        //Check ids.res holds the affine generator
+        check_memory![
+            vm.segments.memory,
+            ((1, 9), ("17117865558768631194064792", 10)),
+            ((1, 10), ("12501176021340589225372855", 10)),
+            ((1, 11), ("9198697782662356105779718", 10)),
+            ((1, 12), ("6441780312434748884571320", 10)),
+            ((1, 13), ("57953919405111227542741658", 10)),
+            ((1, 14), ("5457536640262350763842127", 10))
+        ];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_jacobian_to_affine_point_at_infinity() {
+        //Insert ids.point with Z = 0
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 1),
+                ((1, 1), 0),
+                ((1, 2), 0),
+                ((1, 3), 1),
+                ((1, 4), 0),
+                ((1, 5), 0),
+                ((1, 6), 0),
+                ((1, 7), 0),
+                ((1, 8), 0)
+            ])
+            .unwrap()
+            .with_run_context(0, 15, (0, 0).into())
+            .build();
+
+        let ids_data = HashMap::from([
+            ("point".to_string(), HintReference::new_simple(-15)),
+            ("res".to_string(), HintReference::new_simple(-6)),
+        ]);
+
+        assert_matches!(
+            secp_jacobian_to_affine(&mut vm, &ids_data, &ApTracking::default()),
+            Err(HintError::JacobianPointAtInfinity)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_slope_v2_ok() {
+        //Insert ids.point0 and ids.point1 into memory
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 512),
+                ((1, 1), 2412),
+                ((1, 2), 133),
+                ((1, 3), 64),
+                ((1, 4), 0),
+                ((1, 5), 6546),
+                ((1, 6), 7),
+                ((1, 7), 8),
+                ((1, 8), 123),
+                ((1, 9), 1),
+                ((1, 10), 7),
+                ((1, 11), 465)
+            ])
+            .unwrap()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+        // let point_1 = EcPoint(BigInt3(512,2412,133), BigInt3(64,0,6546));
+        // let point_2 = EcPoint(BigInt3(7,8,123), BigInt3(1,7,465));
+
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-14)),
+            ("point1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        let mut exec_scopes = ExecutionScopes::new();
+
+        //Execute the hint
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code::COMPUTE_SLOPE_V2, &mut exec_scopes),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "value",
+                    bigint_str!(
+                        "39376930140709393693483102164172662915882483986415749881375763965703119677959"
+                    )
+                ),
+                (
+                    "slope",
+                    bigint_str!(
+                        "39376930140709393693483102164172662915882483986415749881375763965703119677959"
+                    )
+                )
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_slope_wdivmod_ok() {
+        let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\nfrom starkware.python.math_utils import div_mod\n\n# Compute the slope.\nx0 = pack(ids.pt0.x, PRIME)\ny0 = pack(ids.pt0.y, PRIME)\nx1 = pack(ids.pt1.x, PRIME)\ny1 = pack(ids.pt1.y, PRIME)\nvalue = slope = div_mod(y0 - y1, x0 - x1, SECP_P)";
+
+        // Insert ids.pt0 and ids.pt1 into memory
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 134),
+                ((1, 1), 5123),
+                ((1, 2), 140),
+                ((1, 3), 1232),
+                ((1, 4), 4652),
+                ((1, 5), 720),
+                ((1, 6), 156),
+                ((1, 7), 6545),
+                ((1, 8), 100010),
+                ((1, 9), 1123),
+                ((1, 10), 1325),
+                ((1, 11), 910)
+            ])
+            .unwrap()
+            .with_run_context(0, 14, (0, 0).into())
+            .build();
+
+        // Initialize fp
+        let ids_data = HashMap::from([
+            ("pt0".to_string(), HintReference::new_simple(-14)),
+            ("pt1".to_string(), HintReference::new_simple(-8)),
+        ]);
+        let mut exec_scopes = ExecutionScopes::new();
+
+        // Execute the hint
+        assert_matches!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
         check_scope!(
             &exec_scopes,
             [
@@ -943,23 +1857,24 @@ mod tests {
     fn run_ec_double_assign_new_x_ok() {
         let hint_code = hint_code::EC_DOUBLE_ASSIGN_NEW_X_V1;
 
-        let mut vm = vm_with_range_check!();
-
         //Insert ids.point and ids.slope into memory
-        vm.segments = segments![
-            ((1, 0), 134),
-            ((1, 1), 5123),
-            ((1, 2), 140),
-            ((1, 3), 1232),
-            ((1, 4), 4652),
-            ((1, 5), 720),
-            ((1, 6), 44186171158942157784255469_i128),
-            ((1, 7), 54173758974262696047492534_i128),
-            ((1, 8), 8106299688661572814170174_i128)
-        ];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 134),
+                ((1, 1), 5123),
+                ((1, 2), 140),
+                ((1, 3), 1232),
+                ((1, 4), 4652),
+                ((1, 5), 720),
+                ((1, 6), 44186171158942157784255469_i128),
+                ((1, 7), 54173758974262696047492534_i128),
+                ((1, 8), 8106299688661572814170174_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 10, (0, 0).into())
+            .build();
 
-        //Initialize fp
-        vm.run_context.fp = 10;
         let ids_data = HashMap::from([
             ("point".to_string(), HintReference::new_simple(-10)),
             ("slope".to_string(), HintReference::new_simple(-4)),
@@ -1007,23 +1922,24 @@ mod tests {
     fn run_ec_double_assign_new_x_v2_ok() {
         let hint_code = hint_code::EC_DOUBLE_ASSIGN_NEW_X_V2;
 
-        let mut vm = vm_with_range_check!();
-
         //Insert ids.point and ids.slope into memory
-        vm.segments = segments![
-            ((1, 0), 134),
-            ((1, 1), 5123),
-            ((1, 2), 140),
-            ((1, 3), 1232),
-            ((1, 4), 4652),
-            ((1, 5), 720),
-            ((1, 6), 44186171158942157784255469_i128),
-            ((1, 7), 54173758974262696047492534_i128),
-            ((1, 8), 8106299688661572814170174_i128)
-        ];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), 134),
+                ((1, 1), 5123),
+                ((1, 2), 140),
+                ((1, 3), 1232),
+                ((1, 4), 4652),
+                ((1, 5), 720),
+                ((1, 6), 44186171158942157784255469_i128),
+                ((1, 7), 54173758974262696047492534_i128),
+                ((1, 8), 8106299688661572814170174_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 10, (0, 0).into())
+            .build();
 
-        //Initialize fp
-        vm.run_context.fp = 10;
         let ids_data = HashMap::from([
             ("point".to_string(), HintReference::new_simple(-10)),
             ("slope".to_string(), HintReference::new_simple(-4)),
@@ -1071,7 +1987,10 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_ec_double_assign_new_y_ok() {
         let hint_code = "value = new_y = (slope * (x - new_x) - y) % SECP_P";
-        let mut vm = vm_with_range_check!();
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .build();
+
         let mut exec_scopes = scope![
             (
                 "slope",
@@ -1124,26 +2043,29 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_fast_ec_add_assign_new_x_ok() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack\n\nslope = pack(ids.slope, PRIME)\nx0 = pack(ids.point0.x, PRIME)\nx1 = pack(ids.point1.x, PRIME)\ny0 = pack(ids.point0.y, PRIME)\n\nvalue = new_x = (pow(slope, 2, SECP_P) - x0 - x1) % SECP_P";
-        let mut vm = vm_with_range_check!();
 
         //Insert ids.point0, ids.point1.x and ids.slope into memory
-        vm.segments = segments![
-            //ids.point0
-            ((1, 0), 89712),
-            ((1, 1), 56),
-            ((1, 2), 1233409),
-            ((1, 3), 980126),
-            ((1, 4), 10),
-            ((1, 5), 8793),
-            //ids.point0.x
-            ((1, 6), 1235216451),
-            ((1, 7), 5967),
-            ((1, 8), 2171381),
-            //ids.slope
-            ((1, 9), 67470097831679799377177424_i128),
-            ((1, 10), 43370026683122492246392730_i128),
-            ((1, 11), 16032182557092050689870202_i128)
-        ];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                //ids.point0
+                ((1, 0), 89712),
+                ((1, 1), 56),
+                ((1, 2), 1233409),
+                ((1, 3), 980126),
+                ((1, 4), 10),
+                ((1, 5), 8793),
+                //ids.point0.x
+                ((1, 6), 1235216451),
+                ((1, 7), 5967),
+                ((1, 8), 2171381),
+                //ids.slope
+                ((1, 9), 67470097831679799377177424_i128),
+                ((1, 10), 43370026683122492246392730_i128),
+                ((1, 11), 16032182557092050689870202_i128)
+            ])
+            .unwrap()
+            .build();
 
         //Initialize run_context
         run_context!(vm, 0, 20, 15);
@@ -1181,7 +2103,9 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_fast_ec_add_assign_new_y_ok() {
         let hint_code = "value = new_y = (slope * (x0 - new_x) - y0) % SECP_P";
-        let mut vm = vm_with_range_check!();
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .build();
 
         let mut exec_scopes = scope![
             (
@@ -1236,11 +2160,14 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_ec_mul_inner_ok() {
         let hint_code = "memory[ap] = (ids.scalar % PRIME) % 2";
-        let mut vm = vm_with_range_check!();
 
         let scalar = 89712_i32;
         //Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![((1, 0), scalar)])
+            .unwrap()
+            .build();
 
         //Initialize RunContext
         run_context!(vm, 0, 2, 1);
@@ -1254,29 +2181,169 @@ mod tests {
         check_memory![vm.segments.memory, ((1, 2), 0)];
     }
 
-    #[test]
-    fn get_ec_point_from_var_name_ok() {
-        /*EcPoint {
-            x: (1,2,3)
-            y: (4,5,6)
-        }*/
+    // Runs the bits loop of ec_mul over scalar, each iteration in a new frame, returning the bits
+    // written by the hint
+    fn run_ec_mul_inner_loop(scalar: Felt252, cached: bool) -> Vec<Felt252> {
         let mut vm = vm!();
-        vm.set_fp(1);
-        vm.segments = segments![
-            ((1, 0), 1),
-            ((1, 1), 2),
-            ((1, 2), 3),
-            ((1, 3), 4),
-            ((1, 4), 5),
-            ((1, 5), 6)
-        ];
-        let ids_data = ids_data!["e"];
-        let ap_tracking = ApTracking::default();
-        let e = EcPoint::from_var_name("e", &vm, &ids_data, &ap_tracking).unwrap();
-        assert_eq!(e.x.limbs[0].as_ref(), &Felt252::ONE);
-        assert_eq!(e.x.limbs[1].as_ref(), &Felt252::from(2));
-        assert_eq!(e.x.limbs[2].as_ref(), &Felt252::from(3));
-        assert_eq!(e.y.limbs[0].as_ref(), &Felt252::from(4));
+        add_segments!(vm, 2);
+        let mut exec_scopes = ExecutionScopes::new();
+        let ids_data = ids_data!["scalar"];
+        let mut scalar = scalar;
+        let mut bits = Vec::new();
+        for i in 0..252 {
+            let fp = 2 * i + 1;
+            vm.insert_value(relocatable!(1, fp - 1), scalar).unwrap();
+            vm.set_fp(fp);
+            vm.set_ap(fp);
+            if cached {
+                ec_mul_inner_cached(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::new())
+                    .unwrap();
+            } else {
+                ec_mul_inner(&mut vm, &ids_data, &ApTracking::new()).unwrap();
+            }
+            let bit = *vm.get_integer(relocatable!(1, fp)).unwrap();
+            scalar = (scalar - bit).field_div(&Felt252::TWO.try_into().unwrap());
+            bits.push(bit);
+        }
+        bits
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_mul_inner_cached_matches_uncached() {
+        for scalar in [
+            Felt252::ZERO,
+            Felt252::from(89712),
+            Felt252::MAX,
+            felt_str!(
+                "1882853720958234916155596432838219713434917012598389215425719563227316587325"
+            ),
+        ] {
+            let bits = run_ec_mul_inner_loop(scalar, false);
+            assert_eq!(run_ec_mul_inner_loop(scalar, true), bits);
+            // The bits are the binary representation of the scalar
+            assert_eq!(
+                bits.iter()
+                    .rev()
+                    .fold(Felt252::ZERO, |acc, bit| acc * Felt252::TWO + bit),
+                scalar
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_mul_inner_cached_new_scalar_resets_cache() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 6), ((1, 2), 5)];
+        let mut exec_scopes = ExecutionScopes::new();
+        let ids_data = ids_data!["scalar"];
+        run_context!(vm, 0, 1, 1);
+        assert_matches!(
+            ec_mul_inner_cached(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::new()),
+            Ok(())
+        );
+        // The next expected scalar is 3, but a new loop starts with 5
+        run_context!(vm, 0, 3, 3);
+        assert_matches!(
+            ec_mul_inner_cached(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::new()),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 1), 0), ((1, 3), 1)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_mul_inner_packed_felt_scalar() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 89713)];
+        run_context!(vm, 0, 2, 1);
+        let ids_data = ids_data!["scalar"];
+        assert_matches!(
+            ec_mul_inner_packed(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &ids_data,
+                &ApTracking::default()
+            ),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 2), 1)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_mul_inner_packed_bigint3_scalar() {
+        let hint_code = hint_code::EC_MUL_INNER;
+        let mut vm = vm!();
+        // scalar = 3 + 2 * 2**86 + 1 * 2**172
+        vm.segments = segments![((1, 0), 3), ((1, 1), 2), ((1, 2), 1)];
+        run_context!(vm, 0, 4, 3);
+        let mut reference = HintReference::new_simple(-3);
+        reference.cairo_type =
+            Some("starkware.cairo.common.cairo_secp.bigint3.BigInt3".to_string());
+        let ids_data = HashMap::from([("scalar".to_string(), reference)]);
+        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        check_memory![vm.segments.memory, ((1, 4), 1)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ec_mul_continue_nonzero_scalar() {
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![((1, 0), 89712)])
+            .unwrap()
+            .with_run_context(2, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["scalar"];
+        assert_matches!(
+            ec_mul_continue(&mut vm, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 2), 1)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ec_mul_continue_zero_scalar() {
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![((1, 0), 0)])
+            .unwrap()
+            .with_run_context(2, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["scalar"];
+        assert_matches!(
+            ec_mul_continue(&mut vm, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_memory![vm.segments.memory, ((1, 2), 0)];
+    }
+
+    #[test]
+    fn get_ec_point_from_var_name_ok() {
+        /*EcPoint {
+            x: (1,2,3)
+            y: (4,5,6)
+        }*/
+        let vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 1),
+                ((1, 1), 2),
+                ((1, 2), 3),
+                ((1, 3), 4),
+                ((1, 4), 5),
+                ((1, 5), 6)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["e"];
+        let ap_tracking = ApTracking::default();
+        let e = EcPoint::from_var_name("e", &vm, &ids_data, &ap_tracking).unwrap();
+        assert_eq!(e.x.limbs[0].as_ref(), &Felt252::ONE);
+        assert_eq!(e.x.limbs[1].as_ref(), &Felt252::from(2));
+        assert_eq!(e.x.limbs[2].as_ref(), &Felt252::from(3));
+        assert_eq!(e.y.limbs[0].as_ref(), &Felt252::from(4));
         assert_eq!(e.y.limbs[1].as_ref(), &Felt252::from(5));
         assert_eq!(e.y.limbs[2].as_ref(), &Felt252::from(6));
     }
@@ -1287,19 +2354,100 @@ mod tests {
             x: (1,2,3)
             y: (4,_,_)
         }*/
-        let mut vm = vm!();
-        vm.set_fp(1);
-        vm.segments = segments![((1, 0), 1), ((1, 1), 2), ((1, 2), 3), ((1, 3), 4)];
+        let vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 1),
+                ((1, 1), 2),
+                ((1, 2), 3),
+                ((1, 3), 4)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
         let ids_data = ids_data!["e"];
         let ap_tracking = ApTracking::default();
         let r = EcPoint::from_var_name("e", &vm, &ids_data, &ap_tracking);
         assert_matches!(r, Err(HintError::IdentifierHasNoMember(bx)) if *bx == ("e.y".to_string(), "d1".to_string()))
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ec_point_from_var_name_relocatable_member() {
+        /*EcPoint {
+            x: (1,2,(2,0))
+            y: (4,5,6)
+        }*/
+        let vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 1),
+                ((1, 1), 2),
+                ((1, 2), (2, 0)),
+                ((1, 3), 4),
+                ((1, 4), 5),
+                ((1, 5), 6)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        let ap_tracking = ApTracking::default();
+        let r = EcPoint::from_var_name("point", &vm, &ids_data, &ap_tracking);
+        assert_matches!(r, Err(e) if e.to_string() == "ids.point.x has no member d2 or it is of incorrect type")
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ec_point_pack86() {
+        let vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 1),
+                ((1, 1), 2),
+                ((1, 2), 3),
+                ((1, 3), 4),
+                ((1, 4), 5),
+                ((1, 5), 6)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        let ap_tracking = ApTracking::default();
+        let point = || EcPoint::from_var_name("point", &vm, &ids_data, &ap_tracking).unwrap();
+        let expected = (
+            BigInt::from(1) + (BigInt::from(2) << 86) + (BigInt::from(3) << 172),
+            BigInt::from(4) + (BigInt::from(5) << 86) + (BigInt::from(6) << 172),
+        );
+        assert_eq!(point().pack86(), expected);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_missing_limb() {
+        let hint_code = hint_code::EC_DOUBLE_SLOPE_V1;
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 614323u64),
+                ((1, 1), 5456867u64),
+                ((1, 2), 101208u64),
+                ((1, 3), 773712524u64),
+                ((1, 5), 5298795u64)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::IdentifierHasNoMember(bx)) if *bx == ("point.y".to_string(), "d1".to_string())
+        );
+    }
+
     #[test]
     fn get_ec_point_from_var_name_invalid_reference() {
-        let mut vm = vm!();
-        vm.segments = segments![((1, 0), 1), ((1, 1), 2)];
+        let vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![((1, 0), 1), ((1, 1), 2)])
+            .unwrap()
+            .build();
         let ids_data = ids_data!["e"];
         let ap_tracking = ApTracking::default();
         let r = EcPoint::from_var_name("e", &vm, &ids_data, &ap_tracking);
@@ -1309,13 +2457,20 @@ mod tests {
     #[test]
     fn run_quad_bit_ok() {
         let hint_code = hint_code::QUAD_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 89712;
         let scalar_v = 1478396;
         let m = 4;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1332,13 +2487,20 @@ mod tests {
     #[test]
     fn run_quad_bit_for_m_1_ok() {
         let hint_code = hint_code::QUAD_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 89712;
         let scalar_v = 1478396;
         let m = 1;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1355,13 +2517,20 @@ mod tests {
     #[test]
     fn run_quad_bit_for_m_0() {
         let hint_code = hint_code::QUAD_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 0b1010101;
         let scalar_v = 0b1010101;
         let m = 0;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1378,14 +2547,21 @@ mod tests {
     #[test]
     fn run_quad_bit_with_max_m_ok() {
         let hint_code = hint_code::QUAD_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 89712;
         let scalar_v = 1478396;
         // Value is so high the result will always be zero
         let m = i128::MAX;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1402,13 +2578,20 @@ mod tests {
     #[test]
     fn run_di_bit_ok() {
         let hint_code = hint_code::DI_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 0b10101111001110000;
         let scalar_v = 0b101101000111011111100;
         let m = 3;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1425,14 +2608,21 @@ mod tests {
     #[test]
     fn run_di_bit_with_max_m_ok() {
         let hint_code = hint_code::DI_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 89712;
         let scalar_v = 1478396;
         // Value is so high the result will always be zero
         let m = i128::MAX;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1449,13 +2639,20 @@ mod tests {
     #[test]
     fn run_di_bit_m_zero_ok() {
         let hint_code = hint_code::DI_BIT;
-        let mut vm = vm_with_range_check!();
 
         let scalar_u = 0b00;
         let scalar_v = 0b01;
         let m = 0;
         // Insert ids.scalar into memory
-        vm.segments = segments![((1, 0), scalar_u), ((1, 1), scalar_v), ((1, 2), m)];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                ((1, 0), scalar_u),
+                ((1, 1), scalar_v),
+                ((1, 2), m)
+            ])
+            .unwrap()
+            .build();
 
         // Initialize RunContext
         run_context!(vm, 0, 4, 4);
@@ -1473,10 +2670,11 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_import_secp256r1_alpha() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_ALPHA as ALPHA";
-        let mut vm = vm_with_range_check!();
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
 
-        //Initialize fp
-        vm.run_context.fp = 1;
         //Create hint_data
         let ids_data = ids_data!["point"];
         let mut exec_scopes = ExecutionScopes::new();
@@ -1495,26 +2693,29 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_square_slope_minus_xs_ok() {
         let hint_code = "from starkware.cairo.common.cairo_secp.secp_utils import pack\n\nslope = pack(ids.slope, PRIME)\nx0 = pack(ids.point0.x, PRIME)\nx1 = pack(ids.point1.x, PRIME)\ny0 = pack(ids.point0.y, PRIME)\n\nvalue = new_x = (pow(slope, 2, SECP_P) - x0 - x1) % SECP_P";
-        let mut vm = vm_with_range_check!();
 
         //Insert ids.point0, ids.point1.x and ids.slope into memory
-        vm.segments = segments![
-            //ids.point0
-            ((1, 0), 89712),
-            ((1, 1), 56),
-            ((1, 2), 1233409),
-            ((1, 3), 980126),
-            ((1, 4), 10),
-            ((1, 5), 8793),
-            //ids.point0.x
-            ((1, 6), 1235216451),
-            ((1, 7), 5967),
-            ((1, 8), 2171381),
-            //ids.slope
-            ((1, 9), 67470097831679799377177424_i128),
-            ((1, 10), 43370026683122492246392730_i128),
-            ((1, 11), 16032182557092050689870202_i128)
-        ];
+        let mut vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![
+                //ids.point0
+                ((1, 0), 89712),
+                ((1, 1), 56),
+                ((1, 2), 1233409),
+                ((1, 3), 980126),
+                ((1, 4), 10),
+                ((1, 5), 8793),
+                //ids.point0.x
+                ((1, 6), 1235216451),
+                ((1, 7), 5967),
+                ((1, 8), 2171381),
+                //ids.slope
+                ((1, 9), 67470097831679799377177424_i128),
+                ((1, 10), 43370026683122492246392730_i128),
+                ((1, 11), 16032182557092050689870202_i128)
+            ])
+            .unwrap()
+            .build();
 
         //Initialize run_context
         run_context!(vm, 0, 20, 15);
@@ -1548,4 +2749,548 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_negate_secp256r1_ok() {
+        // ids.point is the P-256 generator
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 3), 59685082318776612195095029_i128),
+                ((1, 4), 54599710628478995760242092_i128),
+                ((1, 5), 6036146923926000695307902_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_NEGATE_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "value",
+                    bigint_str!(
+                        "79657838253606452964112319029819691573475036742305299123656433055298683448842"
+                    )
+                ),
+                ("SECP_P", SECP256R1_P.clone())
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_secp256r1_ok() {
+        // ids.point is the P-256 generator
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 52227620040540588600771222_i128),
+                ((1, 1), 33347259622618539004134583_i128),
+                ((1, 2), 8091721874918813684698062_i128),
+                ((1, 3), 59685082318776612195095029_i128),
+                ((1, 4), 54599710628478995760242092_i128),
+                ((1, 5), 6036146923926000695307902_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_SLOPE_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "value",
+                    bigint_str!(
+                        "73404963663004311880882944372748989162084677934852963787452504780932599885725"
+                    )
+                ),
+                (
+                    "slope",
+                    bigint_str!(
+                        "73404963663004311880882944372748989162084677934852963787452504780932599885725"
+                    )
+                ),
+                ("SECP_P", SECP256R1_P.clone()),
+                ("ALPHA", SECP256R1_ALPHA.clone())
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_curve_selected_from_scope() {
+        // ids.point is the P-256 generator
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 52227620040540588600771222_i128),
+                ((1, 1), 33347259622618539004134583_i128),
+                ((1, 2), 8091721874918813684698062_i128),
+                ((1, 3), 59685082318776612195095029_i128),
+                ((1, 4), 54599710628478995760242092_i128),
+                ((1, 5), 6036146923926000695307902_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("CURVE", "secp256r1".to_string());
+        // The hint taking SECP_P and ALPHA from the scope picks up secp256r1's prime and alpha
+        exec_scopes.insert_value("SECP_P", SECP_P.clone());
+        exec_scopes.insert_value("ALPHA", BigInt::zero());
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [(
+                "slope",
+                bigint_str!(
+                    "73404963663004311880882944372748989162084677934852963787452504780932599885725"
+                )
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_hardcoded_curve_ignores_scope() {
+        // ids.point is the P-256 generator
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 52227620040540588600771222_i128),
+                ((1, 1), 33347259622618539004134583_i128),
+                ((1, 2), 8091721874918813684698062_i128),
+                ((1, 3), 59685082318776612195095029_i128),
+                ((1, 4), 54599710628478995760242092_i128),
+                ((1, 5), 6036146923926000695307902_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        let mut exec_scopes = ExecutionScopes::new();
+        // Left over by an earlier hint, the secp256r1 hint must not compute on secp256k1
+        exec_scopes.insert_value("CURVE", "secp256k1".to_string());
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_SLOPE_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "slope",
+                    bigint_str!(
+                        "73404963663004311880882944372748989162084677934852963787452504780932599885725"
+                    )
+                ),
+                ("SECP_P", SECP256R1_P.clone())
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_negate_hardcoded_curve_ignores_scope() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 3), 2), ((1, 4), 0), ((1, 5), 0)];
+        vm.run_context.fp = 1;
+        let ids_data = non_continuous_ids_data![("point", -1)];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("CURVE", "secp256r1".to_string());
+        // The hint importing SECP_P always negates on secp256k1
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code::EC_NEGATE, &mut exec_scopes),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [("value", SECP_P.clone() - 2), ("SECP_P", SECP_P.clone())]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_unknown_curve() {
+        let mut vm = vm!();
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), 0),
+            ((1, 2), 0),
+            ((1, 3), 1),
+            ((1, 4), 0),
+            ((1, 5), 0)
+        ];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["point"];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("CURVE", "secp384r1".to_string());
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_SLOPE_EXTERNAL_CONSTS,
+                &mut exec_scopes
+            ),
+            Err(HintError::UnknownCurve(bx)) if bx.as_ref() == "secp384r1"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_double_assign_new_x_secp256r1_ok() {
+        // ids.point is the P-256 generator and ids.slope its doubling slope
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 52227620040540588600771222_i128),
+                ((1, 1), 33347259622618539004134583_i128),
+                ((1, 2), 8091721874918813684698062_i128),
+                ((1, 3), 59685082318776612195095029_i128),
+                ((1, 4), 54599710628478995760242092_i128),
+                ((1, 5), 6036146923926000695307902_i128),
+                ((1, 6), 18381657395733761514123165_i128),
+                ((1, 7), 60250936249481871670557728_i128),
+                ((1, 8), 12262137276504867214243183_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 10, (0, 0).into())
+            .build();
+        let ids_data = HashMap::from([
+            ("point".to_string(), HintReference::new_simple(-10)),
+            ("slope".to_string(), HintReference::new_simple(-4)),
+        ]);
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_ASSIGN_NEW_X_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert_matches!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::EC_DOUBLE_ASSIGN_NEW_Y,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        // 2G on P-256
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "new_x",
+                    bigint_str!(
+"56515219790691171413109057904011688695424810155802929973526481321309856242040"
+)
+                ),
+                (
+                    "new_y",
+                    bigint_str!(
+"3377031843712258259223711451491452598088675519751548567112458094635497583569"
+)
+                )
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_secp256r1_double_and_add_slopes() {
+        // ids.point / ids.point1 is G and ids.point0 is 2G on P-256
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 52227620040540588600771222_i128),
+                ((1, 1), 33347259622618539004134583_i128),
+                ((1, 2), 8091721874918813684698062_i128),
+                ((1, 3), 59685082318776612195095029_i128),
+                ((1, 4), 54599710628478995760242092_i128),
+                ((1, 5), 6036146923926000695307902_i128),
+                ((1, 6), 60574784517941929169033592_i128),
+                ((1, 7), 38742641973200156549941727_i128),
+                ((1, 8), 9440742814978962916680995_i128),
+                ((1, 9), 50180633949907515547874257_i128),
+                ((1, 10), 52108912657982010475124979_i128),
+                ((1, 11), 564125721045731681407961_i128)
+            ])
+            .unwrap()
+            .with_run_context(0, 12, (0, 0).into())
+            .build();
+        let mut exec_scopes = ExecutionScopes::new();
+
+        let ids_data = HashMap::from([("point".to_string(), HintReference::new_simple(-12))]);
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_SLOPE_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [(
+                "slope",
+                bigint_str!(
+                    "73404963663004311880882944372748989162084677934852963787452504780932599885725"
+                )
+            )]
+        );
+
+        // compute_slope takes SECP_P from the scope set by the doubling hint
+        let ids_data = HashMap::from([
+            ("point0".to_string(), HintReference::new_simple(-6)),
+            ("point1".to_string(), HintReference::new_simple(-12)),
+        ]);
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::COMPUTE_SLOPE_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "value",
+                    bigint_str!(
+                        "18455939157588970446784565569990294768680858463148985486203132144235521409158"
+                    )
+                ),
+                (
+                    "slope",
+                    bigint_str!(
+                        "18455939157588970446784565569990294768680858463148985486203132144235521409158"
+                    )
+                )
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_secp256r1_zero_y() {
+        let mut vm = VirtualMachineBuilder::default()
+            .with_memory(memory_entries![
+                ((1, 0), 3),
+                ((1, 1), 0),
+                ((1, 2), 0),
+                ((1, 3), 0),
+                ((1, 4), 0),
+                ((1, 5), 0)
+            ])
+            .unwrap()
+            .with_run_context(0, 1, (0, 0).into())
+            .build();
+        let ids_data = ids_data!["point"];
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_SLOPE_SECP256R1,
+                &mut ExecutionScopes::new()
+            ),
+            Err(HintError::Math(MathError::EcDoubleSlopeZeroY(bx)))
+                if *bx == (BigInt::from(3), BigInt::zero(), SECP256R1_P.clone())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_doubling_slope_limb_out_of_range() {
+        // Limbs out of the 86-bit range are packed as is, like the python vm does
+        let run = |d1: i128, d2: i128| {
+            let mut vm = VirtualMachineBuilder::default()
+                .with_memory(memory_entries![
+                    ((1, 0), 614323u64),
+                    ((1, 1), d1),
+                    ((1, 2), d2),
+                    ((1, 3), 773712524u64),
+                    ((1, 4), 77371252u64),
+                    ((1, 5), 5298795u64)
+                ])
+                .unwrap()
+                .with_run_context(0, 1, (0, 0).into())
+                .build();
+            let ids_data = ids_data!["point"];
+            let mut exec_scopes = ExecutionScopes::new();
+            assert_matches!(
+                run_hint!(
+                    vm,
+                    ids_data,
+                    hint_code::EC_DOUBLE_SLOPE_V1,
+                    &mut exec_scopes
+                ),
+                Ok(())
+            );
+            exec_scopes.get::<BigInt>("value").unwrap()
+        };
+        // BASE
+        assert_eq!(run(77371252455336267181195264, 101208), run(0, 101209));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_compute_slope_limb_out_of_range() {
+        // Limbs out of the 86-bit range are packed as is, like the python vm does
+        let run = |d1: i128, d2: i128| {
+            let mut vm = VirtualMachineBuilder::default()
+                .with_memory(memory_entries![
+                    ((1, 0), 134),
+                    ((1, 1), 5123),
+                    ((1, 2), 140),
+                    ((1, 3), 1232),
+                    ((1, 4), 4652),
+                    ((1, 5), 720),
+                    ((1, 6), 156),
+                    ((1, 7), 6545),
+                    ((1, 8), 100010),
+                    ((1, 9), 1123),
+                    ((1, 10), d1),
+                    ((1, 11), d2)
+                ])
+                .unwrap()
+                .with_run_context(0, 14, (0, 0).into())
+                .build();
+            let ids_data = HashMap::from([
+                ("point0".to_string(), HintReference::new_simple(-14)),
+                ("point1".to_string(), HintReference::new_simple(-8)),
+            ]);
+            let mut exec_scopes = ExecutionScopes::new();
+            assert_matches!(
+                run_hint!(vm, ids_data, hint_code::COMPUTE_SLOPE_V1, &mut exec_scopes),
+                Ok(())
+            );
+            exec_scopes.get::<BigInt>("value").unwrap()
+        };
+        // -BASE
+        assert_eq!(run(-77371252455336267181195264, 911), run(0, 910));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_ec_double_assign_new_x_limb_out_of_range() {
+        // Limbs out of the 86-bit range are packed as is, like the python vm does
+        let run = |d1: i128, d2: i128| {
+            let mut vm = VirtualMachineBuilder::default()
+                .with_memory(memory_entries![
+                    ((1, 0), 134),
+                    ((1, 1), 5123),
+                    ((1, 2), 140),
+                    ((1, 3), 1232),
+                    ((1, 4), 4652),
+                    ((1, 5), 720),
+                    ((1, 6), 44186171158942157784255469_i128),
+                    ((1, 7), d1),
+                    ((1, 8), d2)
+                ])
+                .unwrap()
+                .with_run_context(0, 10, (0, 0).into())
+                .build();
+            let ids_data = HashMap::from([
+                ("point".to_string(), HintReference::new_simple(-10)),
+                ("slope".to_string(), HintReference::new_simple(-4)),
+            ]);
+            let mut exec_scopes = ExecutionScopes::new();
+            assert_matches!(
+                run_hint!(
+                    vm,
+                    ids_data,
+                    hint_code::EC_DOUBLE_ASSIGN_NEW_X_V1,
+                    &mut exec_scopes
+                ),
+                Ok(())
+            );
+            exec_scopes.get::<BigInt>("value").unwrap()
+        };
+        // BASE + 1
+        assert_eq!(run(77371252455336267181195265, 12), run(1, 13));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_fast_ec_add_assign_new_x_limb_out_of_range() {
+        // Limbs out of the 86-bit range are packed as is, like the python vm does
+        let run = |d1: i128, d2: i128| {
+            let mut vm = VirtualMachineBuilder::default()
+                .with_memory(memory_entries![
+                    //ids.point0
+                    ((1, 0), 89712),
+                    ((1, 1), 56),
+                    ((1, 2), 1233409),
+                    ((1, 3), 980126),
+                    ((1, 4), 10),
+                    ((1, 5), 8793),
+                    //ids.point1.x
+                    ((1, 6), 1235216451),
+                    ((1, 7), d1),
+                    ((1, 8), d2),
+                    //ids.slope
+                    ((1, 9), 67470097831679799377177424_i128),
+                    ((1, 10), 43370026683122492246392730_i128),
+                    ((1, 11), 16032182557092050689870202_i128)
+                ])
+                .unwrap()
+                .with_run_context(20, 15, (0, 0).into())
+                .build();
+            let ids_data = HashMap::from([
+                ("point0".to_string(), HintReference::new_simple(-15)),
+                ("point1".to_string(), HintReference::new_simple(-9)),
+                ("slope".to_string(), HintReference::new_simple(-6)),
+            ]);
+            let mut exec_scopes = ExecutionScopes::new();
+            assert_matches!(
+                run_hint!(
+                    vm,
+                    ids_data,
+                    hint_code::FAST_EC_ADD_ASSIGN_NEW_X,
+                    &mut exec_scopes
+                ),
+                Ok(())
+            );
+            exec_scopes.get::<BigInt>("value").unwrap()
+        };
+        // BASE + 5967
+        assert_eq!(run(77371252455336267181201231, 0), run(5967, 1));
+    }
 }
@@ -2,20 +2,28 @@ use crate::Felt252;
 use crate::{
     hint_processor::{
         builtin_hint_processor::{
-            hint_utils::{insert_value_from_var_name, insert_value_into_ap},
-            secp::{bigint_utils::Uint384, secp_utils::SECP_P},
+            hint_utils::{
+                get_integer_from_var_name, get_ptr_from_var_name, get_relocatable_from_var_name,
+                insert_value_from_var_name, insert_value_into_ap,
+            },
+            secp::{
+                bigint_utils::{BigInt3, Uint384},
+                secp_utils::{bigint3_split, SECP_P},
+            },
         },
         hint_processor_definition::HintReference,
     },
-    math_utils::div_mod,
+    math_utils::{div_mod, is_quad_residue},
     serde::deserialize_program::ApTracking,
     stdlib::{boxed::Box, collections::HashMap, prelude::*},
-    types::exec_scope::ExecutionScopes,
+    types::{
+        errors::math_errors::MathError, exec_scope::ExecutionScopes, relocatable::MaybeRelocatable,
+    },
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use num_bigint::BigInt;
 use num_integer::Integer;
-use num_traits::{One, Zero};
+use num_traits::{One, ToPrimitive, Zero};
 
 /*
 Implements hint:
@@ -207,10 +215,219 @@ pub fn is_zero_assign_scope_variables_external_const(
     Ok(())
 }
 
+/*
+Helper setting up a batch inversion of the ids.n secp values at ids.denoms_ptr, storing their
+prefix products and the inverse of their product in scope. Cairo-lang has no hint for it, it
+mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack
+    from starkware.python.math_utils import div_mod
+
+    prefix_products = []
+    acc = 1
+    for i in range(ids.n):
+        acc = acc * pack(ids.denoms_ptr[i], PRIME) % SECP_P
+        prefix_products.append(acc)
+    total_inv = div_mod(1, acc, SECP_P)
+%}
+*/
+pub fn secp_batch_inv_setup(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    exec_scopes.insert_value("SECP_P", SECP_P.clone());
+    let denoms_ptr = get_ptr_from_var_name("denoms_ptr", vm, ids_data, ap_tracking)?;
+    let n = get_integer_from_var_name("n", vm, ids_data, ap_tracking)?;
+    let n = n
+        .to_usize()
+        .ok_or_else(|| MathError::Felt252ToUsizeConversion(Box::new(n.into_owned())))?;
+
+    let mut prefix_products = Vec::with_capacity(n);
+    let mut acc = BigInt::one();
+    for i in 0..n {
+        let denom = BigInt3::from_base_addr((denoms_ptr + i * 3)?, "denoms_ptr", vm)?.pack86();
+        acc = (acc * denom).mod_floor(&SECP_P);
+        prefix_products.push(acc.clone());
+    }
+    let total_inv = div_mod(&BigInt::one(), &acc, &SECP_P)?;
+
+    exec_scopes.insert_value("prefix_products", prefix_products);
+    exec_scopes.insert_value("total_inv", total_inv);
+    Ok(())
+}
+
+/*
+Helper writing whether ids.x is a quadratic residue mod the SECP_P scope variable to
+ids.is_residue, so the (p - 1) / 2 exponent of the Euler criterion follows whichever curve the
+program selected. Cairo-lang has no hint for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import pack
+    from starkware.python.math_utils import is_quad_residue
+
+    x = pack(ids.x, PRIME) % SECP_P
+    ids.is_residue = 1 if is_quad_residue(x, SECP_P) else 0
+%}
+*/
+pub fn secp_is_quad_residue(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let secp_p = exec_scopes.get_ref::<BigInt>("SECP_P")?;
+    if secp_p.is_zero() {
+        return Err(MathError::IsQuadResidueZeroPrime.into());
+    }
+    let x = BigInt3::from_var_name("x", vm, ids_data, ap_tracking)?
+        .pack86()
+        .mod_floor(secp_p);
+    let is_residue = is_quad_residue(x.magnitude(), secp_p.magnitude())?;
+    insert_value_from_var_name(
+        "is_residue",
+        Felt252::from(is_residue as u8),
+        vm,
+        ids_data,
+        ap_tracking,
+    )
+}
+
+/*
+Helper reducing ids.x mod SECP_P while keeping the quotient, so that Cairo can assert
+x == q * SECP_P + r. Both q and r are stored in scope, and written to ids.q and ids.r when they
+are referenced. Cairo-lang has no hint for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack, split
+
+    q, r = divmod(pack(ids.x, PRIME), SECP_P)
+    ids.q = q % PRIME
+    segments.write_arg(ids.r.address_, split(r))
+%}
+*/
+pub fn secp_reduce_with_quotient(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    exec_scopes.insert_value("SECP_P", SECP_P.clone());
+    let x = BigInt3::from_var_name("x", vm, ids_data, ap_tracking)?.pack86();
+    let (q, r) = x.div_mod_floor(&SECP_P);
+    if ids_data.contains_key("q") {
+        insert_value_from_var_name("q", Felt252::from(&q), vm, ids_data, ap_tracking)?;
+    }
+    if ids_data.contains_key("r") {
+        write_bigint3_split("r", &r, vm, ids_data, ap_tracking)?;
+    }
+    exec_scopes.insert_value("q", q);
+    exec_scopes.insert_value("r", r);
+    Ok(())
+}
+
+// Writes the 86-bit limbs of `value` to the BigInt3 ids variable `var_name`
+fn write_bigint3_split(
+    var_name: &str,
+    value: &BigInt,
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let addr = get_relocatable_from_var_name(var_name, vm, ids_data, ap_tracking)?;
+    let limbs = value.to_biguint().ok_or(HintError::BigIntToBigUintFail)?;
+    let arg: Vec<MaybeRelocatable> = bigint3_split(&limbs)?
+        .iter()
+        .map(|n| Felt252::from(n).into())
+        .collect();
+    vm.write_arg(addr, &arg)?;
+    Ok(())
+}
+
+// Stores `op(pack(ids.a), pack(ids.b)) % SECP_P` in scope as `value`, also writing it to ids.res
+// if `write_res` is set
+fn secp_binary_op(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    write_res: bool,
+    op: impl FnOnce(BigInt, BigInt) -> BigInt,
+) -> Result<(), HintError> {
+    exec_scopes.insert_value("SECP_P", SECP_P.clone());
+    let a = BigInt3::from_var_name("a", vm, ids_data, ap_tracking)?.pack86();
+    let b = BigInt3::from_var_name("b", vm, ids_data, ap_tracking)?.pack86();
+    // mod_floor keeps the result nonnegative, as python's % does
+    let value = op(a, b).mod_floor(&SECP_P);
+    if write_res {
+        write_bigint3_split("res", &value, vm, ids_data, ap_tracking)?;
+    }
+    exec_scopes.insert_value("value", value);
+    Ok(())
+}
+
+/*
+Helper storing the sum of ids.a and ids.b mod SECP_P in scope as `value`, also writing its limbs
+to ids.res if `write_res` is set. Cairo-lang has no hint for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack, split
+
+    value = (pack(ids.a, PRIME) + pack(ids.b, PRIME)) % SECP_P
+    segments.write_arg(ids.res.address_, split(value))
+%}
+*/
+pub fn secp_add(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    write_res: bool,
+) -> Result<(), HintError> {
+    secp_binary_op(vm, exec_scopes, ids_data, ap_tracking, write_res, |a, b| {
+        a + b
+    })
+}
+
+/*
+Helper storing the difference of ids.a and ids.b mod SECP_P in scope as `value`. Cairo-lang has no
+hint for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack
+
+    value = (pack(ids.a, PRIME) - pack(ids.b, PRIME)) % SECP_P
+%}
+*/
+pub fn secp_sub(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    secp_binary_op(vm, exec_scopes, ids_data, ap_tracking, false, |a, b| a - b)
+}
+
+/*
+Helper storing the product of ids.a and ids.b mod SECP_P in scope as `value`. Cairo-lang has no
+hint for it, it mirrors:
+%{
+    from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack
+
+    value = (pack(ids.a, PRIME) * pack(ids.b, PRIME)) % SECP_P
+%}
+*/
+pub fn secp_mul(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    secp_binary_op(vm, exec_scopes, ids_data, ap_tracking, false, |a, b| a * b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::hint_processor::builtin_hint_processor::hint_code::{self, REDUCE_V2};
+    use crate::hint_processor::builtin_hint_processor::secp::secp_utils::SECP256R1_P;
     use crate::stdlib::string::ToString;
 
     use crate::{
@@ -221,6 +438,7 @@ mod tests {
             },
             hint_processor_definition::HintProcessorLogic,
         },
+        relocatable,
         types::{
             exec_scope::ExecutionScopes,
             relocatable::{MaybeRelocatable, Relocatable},
@@ -234,6 +452,41 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    // Writes the 86-bit limbs of each of `values` one after the other from (1, 0)
+    fn insert_bigint3_values(vm: &mut VirtualMachine, values: &[&BigInt]) {
+        add_segments!(vm, 2);
+        let limbs = values
+            .iter()
+            .flat_map(|value| bigint3_split(&value.to_biguint().unwrap()).unwrap());
+        for (i, limb) in limbs.enumerate() {
+            vm.insert_value(relocatable!(1, i), Felt252::from(&limb))
+                .unwrap();
+        }
+    }
+
+    // Runs a helper operating on ids.a and ids.b, with an unwritten ids.res right after them
+    fn run_secp_binary_op(
+        op: impl FnOnce(
+            &mut VirtualMachine,
+            &mut ExecutionScopes,
+            &HashMap<String, HintReference>,
+            &ApTracking,
+        ) -> Result<(), HintError>,
+        a: &BigInt,
+        b: &BigInt,
+    ) -> (VirtualMachine, ExecutionScopes) {
+        let mut vm = vm!();
+        insert_bigint3_values(&mut vm, &[a, b]);
+        vm.run_context.fp = 9;
+        let ids_data = non_continuous_ids_data![("a", -9), ("b", -6), ("res", -3)];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            op(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        (vm, exec_scopes)
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_verify_zero_ok() {
@@ -257,6 +510,59 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_verify_zero_negative_multiple_of_secp_p() {
+        let mut vm = vm_with_range_check!();
+        //Initialize run_context
+        run_context!(vm, 0, 9, 9);
+        //Create hint data
+        let ids_data = non_continuous_ids_data![("val", -5), ("q", 0)];
+        // ids.val = -2 * SECP_P, with negative limbs
+        vm.segments = segments![
+            ((1, 4), (-154742504910672525772453982_i128)),
+            ((1, 5), (-154742504910672534362390526_i128)),
+            ((1, 6), (-38685626227668133590597630_i128))
+        ];
+        //Execute the hint
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code::VERIFY_ZERO_V1, exec_scopes_ref!()),
+            Ok(())
+        );
+        //ids.q = -2 % PRIME
+        check_memory![vm.segments.memory, ((1, 9), (-2))];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_verify_zero_with_external_const_secp256r1_negative_q_offset() {
+        let mut vm = vm_with_range_check!();
+        //Initialize run_context
+        run_context!(vm, 0, 10, 10);
+        //Create hint data, ids.q is below fp
+        let ids_data = non_continuous_ids_data![("val", -8), ("q", -1)];
+        // ids.val = 3 * SECP256R1_P
+        vm.segments = segments![
+            ((1, 2), ("77371252455336267181195261", 10)),
+            ((1, 3), 3071),
+            ((1, 4), ("58028439327991401506930688", 10))
+        ];
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("SECP_P", SECP256R1_P.clone());
+        //Execute the hint
+        assert_matches!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::VERIFY_ZERO_EXTERNAL_SECP,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        //ids.q
+        check_memory![vm.segments.memory, ((1, 9), 3)];
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_verify_zero_v3_ok() {
@@ -388,6 +694,32 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_reduce_unreduced_limbs_above_secp_p() {
+        let hint_code = hint_code::REDUCE_V1;
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Create hint data
+        let ids_data = non_continuous_ids_data![("x", -3)];
+        // d0 and d2 exceed the 86-bit limb bound, the packed value
+        // d0 + d1 * 2**86 + d2 * 2**172 = 2**258 + 3 * 2**86 + 2**86 + 5 is larger than SECP_P
+        vm.segments = segments![
+            ((1, 0), ("77371252455336267181195269", 10)),
+            ((1, 1), 3),
+            ((1, 2), ("77371252455336267181195264", 10))
+        ];
+        let mut exec_scopes = ExecutionScopes::new();
+        //Execute the hint
+        assert_matches!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        //The reduction is applied to the fully packed value
+        check_scope!(
+            &exec_scopes,
+            [("value", bigint_str!("309485009821345085904654153"))]
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_reduce_error() {
@@ -681,6 +1013,11 @@ mod tests {
                     "19429627790501903254364315669614485084365347064625983303617500144471999752609"
                 )
             );
+
+            //Check x_inv * x == 1 (mod SECP_P)
+            let x = exec_scopes.get::<BigInt>("x").unwrap();
+            let x_inv = exec_scopes.get::<BigInt>("x_inv").unwrap();
+            assert_eq!((x_inv * x).mod_floor(&SECP_P), BigInt::one());
         }
     }
 
@@ -701,4 +1038,238 @@ mod tests {
             Err(HintError::VariableNotInScopeError(bx)) if bx.as_ref() == "x"
         );
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_batch_inv_setup_ok() {
+        let mut vm = vm!();
+        vm.run_context.fp = 2;
+        //Store ids.denoms_ptr, ids.n and the denominators 2, 3 + 2**86 and 5 + 2**172
+        vm.segments = segments![
+            ((1, 0), (2, 0)),
+            ((1, 1), 3),
+            ((2, 0), 2),
+            ((2, 1), 0),
+            ((2, 2), 0),
+            ((2, 3), 3),
+            ((2, 4), 1),
+            ((2, 5), 0),
+            ((2, 6), 5),
+            ((2, 7), 0),
+            ((2, 8), 1)
+        ];
+        let ids_data = ids_data!["denoms_ptr", "n"];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            secp_batch_inv_setup(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+
+        let denoms = [
+            BigInt::from(2),
+            BigInt::from(3) + (BigInt::one() << 86),
+            BigInt::from(5) + (BigInt::one() << 172),
+        ];
+        let prefix_products = exec_scopes.get::<Vec<BigInt>>("prefix_products").unwrap();
+        assert_eq!(
+            prefix_products,
+            vec![
+                denoms[0].clone(),
+                (&denoms[0] * &denoms[1]).mod_floor(&SECP_P),
+                (&denoms[0] * &denoms[1] * &denoms[2]).mod_floor(&SECP_P),
+            ]
+        );
+        let total_inv = exec_scopes.get::<BigInt>("total_inv").unwrap();
+        assert!((total_inv * &prefix_products[2])
+            .mod_floor(&SECP_P)
+            .is_one());
+        // Back-substituting from the total inverse recovers each denominator's inverse
+        let last_inv = (exec_scopes.get::<BigInt>("total_inv").unwrap() * &prefix_products[1])
+            .mod_floor(&SECP_P);
+        assert!((last_inv * &denoms[2]).mod_floor(&SECP_P).is_one());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_batch_inv_setup_zero_denominator() {
+        let mut vm = vm!();
+        vm.run_context.fp = 2;
+        vm.segments = segments![
+            ((1, 0), (2, 0)),
+            ((1, 1), 2),
+            ((2, 0), 7),
+            ((2, 1), 0),
+            ((2, 2), 0),
+            ((2, 3), 0),
+            ((2, 4), 0),
+            ((2, 5), 0)
+        ];
+        let ids_data = ids_data!["denoms_ptr", "n"];
+        assert_matches!(
+            secp_batch_inv_setup(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &ids_data,
+                &ApTracking::default()
+            ),
+            Err(HintError::Math(MathError::DivModIgcdexNotZero(_)))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_is_quad_residue_small_prime_matches_brute_force() {
+        let p = 23_u32;
+        // Values above p are reduced before the check
+        for x in 0..2 * p {
+            let mut vm = vm!();
+            vm.run_context.fp = 4;
+            //Store ids.x as a BigInt3
+            vm.segments = segments![((1, 0), (x as usize)), ((1, 1), 0), ((1, 2), 0)];
+            let ids_data = non_continuous_ids_data![("x", -4), ("is_residue", -1)];
+            let mut exec_scopes = scope![("SECP_P", BigInt::from(p))];
+            assert_matches!(
+                secp_is_quad_residue(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+                Ok(())
+            );
+            let expected = (0..p).any(|y| (y * y) % p == x % p);
+            assert_eq!(
+                vm.get_integer((1, 3).into()).unwrap().as_ref(),
+                &Felt252::from(expected as u8),
+                "x = {x}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_is_quad_residue_no_prime_in_scope() {
+        let mut vm = vm!();
+        vm.run_context.fp = 4;
+        vm.segments = segments![((1, 0), 2), ((1, 1), 0), ((1, 2), 0)];
+        let ids_data = non_continuous_ids_data![("x", -4), ("is_residue", -1)];
+        assert_matches!(
+            secp_is_quad_residue(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &ids_data,
+                &ApTracking::default()
+            ),
+            Err(HintError::VariableNotInScopeError(bx)) if bx.as_ref() == "SECP_P"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_add_wraps_past_secp_p() {
+        let (vm, exec_scopes) = run_secp_binary_op(
+            |vm, exec_scopes, ids_data, ap_tracking| {
+                secp_add(vm, exec_scopes, ids_data, ap_tracking, false)
+            },
+            &(&*SECP_P - 1),
+            &((BigInt::one() << 86_u32) + 5),
+        );
+        check_scope!(&exec_scopes, [("value", (BigInt::one() << 86_u32) + 4)]);
+        // ids.res is left unwritten
+        assert_eq!(vm.segments.memory.data[1].len(), 6);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_add_writes_res() {
+        let (vm, exec_scopes) = run_secp_binary_op(
+            |vm, exec_scopes, ids_data, ap_tracking| {
+                secp_add(vm, exec_scopes, ids_data, ap_tracking, true)
+            },
+            &(&*SECP_P - 1),
+            &((BigInt::one() << 86_u32) + 5),
+        );
+        check_scope!(&exec_scopes, [("value", (BigInt::one() << 86_u32) + 4)]);
+        check_memory![vm.segments.memory, ((1, 6), 4), ((1, 7), 1), ((1, 8), 0)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_sub_a_less_than_b() {
+        let (_, exec_scopes) =
+            run_secp_binary_op(secp_sub, &BigInt::from(3), &((BigInt::one() << 86_u32) + 5));
+        let value: BigInt = &*SECP_P - (BigInt::one() << 86_u32) - 2;
+        check_scope!(&exec_scopes, [("value", value)]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_sub_a_greater_than_b() {
+        let (_, exec_scopes) =
+            run_secp_binary_op(secp_sub, &((BigInt::one() << 86_u32) + 5), &BigInt::from(3));
+        check_scope!(&exec_scopes, [("value", (BigInt::one() << 86_u32) + 2)]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_mul_large_values() {
+        let a: BigInt = &*SECP_P - 1;
+        let b = bigint_str!(
+            "89434994263544919358296742218469089375470361516434298958337437624286463197561"
+        );
+        let (_, exec_scopes) = run_secp_binary_op(secp_mul, &a, &b);
+        // (SECP_P - 1) * b = -b mod SECP_P
+        check_scope!(
+            &exec_scopes,
+            [(
+                "value",
+                bigint_str!(
+                    "26357094973771276065274242790218818477799623149206265081120146383622371474102"
+                )
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_reduce_with_quotient_writes_ids() {
+        // x spans several multiples of SECP_P
+        let x: BigInt = &*SECP_P * 3 + 12345;
+        let mut vm = vm!();
+        insert_bigint3_values(&mut vm, &[&x]);
+        vm.run_context.fp = 7;
+        let ids_data = non_continuous_ids_data![("x", -7), ("q", -4), ("r", -3)];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            secp_reduce_with_quotient(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        assert_eq!(exec_scopes.get::<BigInt>("q").unwrap(), BigInt::from(3));
+        assert_eq!(exec_scopes.get::<BigInt>("r").unwrap(), BigInt::from(12345));
+        check_memory![
+            vm.segments.memory,
+            ((1, 3), 3),
+            ((1, 4), 12345),
+            ((1, 5), 0),
+            ((1, 6), 0)
+        ];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_reduce_with_quotient_scope_only() {
+        let x: BigInt = &*SECP_P * 2 + 7;
+        let mut vm = vm!();
+        insert_bigint3_values(&mut vm, &[&x]);
+        vm.run_context.fp = 3;
+        let ids_data = non_continuous_ids_data![("x", -3)];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            secp_reduce_with_quotient(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        let (q, r) = (
+            exec_scopes.get::<BigInt>("q").unwrap(),
+            exec_scopes.get::<BigInt>("r").unwrap(),
+        );
+        assert_eq!((q.clone(), r.clone()), (BigInt::from(2), BigInt::from(7)));
+        assert_eq!(q * &*SECP_P + r, x);
+        // Nothing was written past ids.x
+        assert_eq!(vm.segments.memory.data[1].len(), 3);
+    }
 }
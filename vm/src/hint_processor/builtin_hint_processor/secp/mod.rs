@@ -1,5 +1,11 @@
 pub mod bigint_utils;
+#[cfg(feature = "hints-secp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-secp")))]
 pub mod ec_utils;
+#[cfg(feature = "hints-secp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-secp")))]
 pub mod field_utils;
 pub mod secp_utils;
+#[cfg(feature = "hints-secp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "hints-secp")))]
 pub mod signature;
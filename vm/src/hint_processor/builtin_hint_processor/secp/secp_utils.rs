@@ -2,11 +2,14 @@ use core::str::FromStr;
 
 use crate::stdlib::{boxed::Box, prelude::*};
 
+#[cfg(feature = "hints-secp")]
+use crate::types::exec_scope::ExecutionScopes;
 use crate::vm::errors::hint_errors::HintError;
 
 use lazy_static::lazy_static;
 use num_bigint::{BigInt, BigUint};
-use num_traits::Zero;
+use num_integer::Integer;
+use num_traits::{One, Zero};
 
 // Constants in package "starkware.cairo.common.cairo_secp.constants".
 pub const BASE_86: &str = "starkware.cairo.common.cairo_secp.constants.BASE";
@@ -68,6 +71,38 @@ lazy_static! {
     ).unwrap();
 }
 
+#[cfg(feature = "hints-secp")]
+/// Constants of a curve y^2 = x^3 + alpha * x + beta over the field of size p
+#[derive(Debug, PartialEq)]
+pub(crate) struct CurveConstants {
+    pub(crate) p: &'static BigInt,
+    pub(crate) alpha: &'static BigInt,
+}
+
+#[cfg(feature = "hints-secp")]
+/// Returns the constants of the curve named by the `CURVE` scope variable ("secp256k1" or
+/// "secp256r1"), or None if the variable is not set.
+/// Only hints that take the curve's prime from the scope consult it, hints that hardcode their
+/// curve always use it
+pub(crate) fn curve_constants_from_scope(
+    exec_scopes: &ExecutionScopes,
+) -> Result<Option<CurveConstants>, HintError> {
+    let Ok(curve) = exec_scopes.get_ref::<String>("CURVE") else {
+        return Ok(None);
+    };
+    match curve.as_str() {
+        "secp256k1" => Ok(Some(CurveConstants {
+            p: &SECP_P,
+            alpha: &ALPHA,
+        })),
+        "secp256r1" => Ok(Some(CurveConstants {
+            p: &SECP256R1_P,
+            alpha: &SECP256R1_ALPHA,
+        })),
+        _ => Err(HintError::UnknownCurve(curve.clone().into_boxed_str())),
+    }
+}
+
 /*
 Takes a 256-bit integer and returns its canonical representation as:
 d0 + BASE * d1 + BASE**2 * d2,
@@ -87,14 +122,77 @@ pub fn bigint3_split(integer: &num_bigint::BigUint) -> Result<[num_bigint::BigUi
     Ok(canonical_repr)
 }
 
+/// Packs `limbs` into `sum(limb_i * 2**(i * base_bits))`, interpreting each limb as a signed
+/// value modulo `prime` (limbs greater than `prime / 2` count as `limb - prime`).
+/// Generalization of `pack(z, prime)` from `cairo_secp.secp_utils` to any number of limbs and base.
+pub fn pack_n(limbs: &[BigInt], base_bits: u32, prime: &BigInt) -> BigInt {
+    let half_prime = prime >> 1_u32;
+    limbs
+        .iter()
+        .enumerate()
+        .map(|(i, limb)| {
+            let limb = if limb > &half_prime {
+                limb - prime
+            } else {
+                limb.clone()
+            };
+            limb << (i as u64 * base_bits as u64)
+        })
+        .sum()
+}
+
+#[cfg(feature = "hints-secp")]
+/// Checked variant of the three-limb `pack(z, prime)` with a base of 2**86.
+/// Fails with `SecpSplitOutOfRange` if the absolute value of any limb (interpreted as a signed
+/// value modulo `prime`) is not smaller than BASE, instead of packing it into a wrong value.
+pub fn pack_checked(
+    d0: &BigInt,
+    d1: &BigInt,
+    d2: &BigInt,
+    prime: &BigInt,
+) -> Result<BigInt, HintError> {
+    let half_prime = prime >> 1_u32;
+    for limb in [d0, d1, d2] {
+        let signed_limb = if limb > &half_prime {
+            limb - prime
+        } else {
+            limb.clone()
+        };
+        if signed_limb.magnitude() >= &*BASE {
+            return Err(HintError::SecpSplitOutOfRange(Box::new(
+                signed_limb.magnitude().clone(),
+            )));
+        }
+    }
+    Ok(pack_n(&[d0.clone(), d1.clone(), d2.clone()], 86, prime))
+}
+
+/// Inverse of `pack_n`: splits `value` into `n_limbs` non-negative limbs of `base_bits` bits,
+/// least significant limb first. Bits above `n_limbs * base_bits` are discarded.
+pub fn unpack(value: &BigInt, n_limbs: usize, base_bits: u32) -> Vec<BigInt> {
+    let base = BigInt::one() << base_bits;
+    let mut value = value.clone();
+    (0..n_limbs)
+        .map(|_| {
+            let limb = value.mod_floor(&base);
+            value >>= base_bits;
+            limb
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use crate::hint_processor::builtin_hint_processor::secp::bigint_utils::BigInt3;
     use crate::stdlib::{collections::HashMap, string::ToString};
     use crate::utils::test_utils::*;
+    use crate::utils::CAIRO_PRIME;
+    use crate::Felt252;
     use assert_matches::assert_matches;
-    use num_bigint::BigUint;
+    use num_bigint::{BigUint, ToBigInt};
+    use num_traits::Signed;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -166,4 +264,96 @@ mod tests {
 
         );
     }
+
+    #[test]
+    #[cfg(feature = "hints-secp")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn curve_constants_from_scope_not_set() {
+        let exec_scopes = ExecutionScopes::new();
+        assert_matches!(curve_constants_from_scope(&exec_scopes), Ok(None));
+    }
+
+    #[test]
+    #[cfg(feature = "hints-secp")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn curve_constants_from_scope_secp256r1() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("CURVE", "secp256r1".to_string());
+        assert_matches!(
+            curve_constants_from_scope(&exec_scopes),
+            Ok(Some(curve)) if curve == CurveConstants {
+                p: &SECP256R1_P,
+                alpha: &SECP256R1_ALPHA,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "hints-secp")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn curve_constants_from_scope_unknown_curve() {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("CURVE", "curve25519".to_string());
+        assert_matches!(
+            curve_constants_from_scope(&exec_scopes),
+            Err(HintError::UnknownCurve(bx)) if bx.as_ref() == "curve25519"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pack_n_unpack_round_trip() {
+        let value = SECP_P.clone() - 12345;
+        for (n_limbs, base_bits) in [(3, 86), (4, 64), (2, 128), (6, 43)] {
+            let limbs = unpack(&value, n_limbs, base_bits);
+            assert_eq!(limbs.len(), n_limbs);
+            assert!(limbs
+                .iter()
+                .all(|limb| !limb.is_negative() && limb.bits() <= base_bits as u64));
+            assert_eq!(pack_n(&limbs, base_bits, &SECP_P), value);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pack_n_negative_limbs() {
+        let prime = CAIRO_PRIME.to_bigint().unwrap();
+        // -1 + 2 * BASE
+        let limbs = [&prime - 1, BigInt::from(2), BigInt::zero()];
+        assert_eq!(pack_n(&limbs, 86, &prime), (BigInt::from(2) << 86_u32) - 1);
+        let felts = [Felt252::from(-1), Felt252::from(2), Felt252::ZERO];
+        assert_eq!(
+            BigInt3::from_values(felts).pack86(),
+            pack_n(&limbs, 86, &prime)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "hints-secp")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pack_checked_limb_ranges() {
+        let prime = CAIRO_PRIME.to_bigint().unwrap();
+        let base = BigInt::from(BASE.clone());
+        let max_limb = &base - 1;
+        let minus_max_limb = &prime - &max_limb;
+        assert_eq!(
+            pack_checked(&max_limb, &minus_max_limb, &BigInt::one(), &prime).unwrap(),
+            pack_n(
+                &[max_limb.clone(), minus_max_limb, BigInt::one()],
+                86,
+                &prime
+            )
+        );
+        assert_matches!(
+            pack_checked(&max_limb, &base, &BigInt::one(), &prime),
+            Err(HintError::SecpSplitOutOfRange(bx)) if *bx == *BASE
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn unpack_discards_high_bits() {
+        let value = (BigInt::one() << 20_u32) + 5;
+        assert_eq!(unpack(&value, 2, 8), vec![BigInt::from(5), BigInt::zero()]);
+    }
 }
@@ -2,7 +2,10 @@ use crate::Felt252;
 use crate::{
     any_box,
     hint_processor::{
-        builtin_hint_processor::{hint_utils::get_integer_from_var_name, secp::secp_utils::BETA},
+        builtin_hint_processor::{
+            hint_utils::{get_integer_from_var_name, insert_value_into_ap},
+            secp::secp_utils::BETA,
+        },
         hint_processor_definition::HintReference,
     },
     math_utils::{div_mod, safe_div_bigint},
@@ -86,6 +89,21 @@ pub fn div_mod_n_safe_div(
     Ok(())
 }
 
+// Same as `div_mod_n_safe_div`, but also stores the result under `k_alias`, as the hint assigns
+// it to `k` (or `k_plus_one` if `to_add` is 1) too
+pub fn div_mod_n_safe_div_with_k(
+    exec_scopes: &mut ExecutionScopes,
+    a_alias: &str,
+    b_alias: &str,
+    k_alias: &str,
+    to_add: u64,
+) -> Result<(), HintError> {
+    div_mod_n_safe_div(exec_scopes, a_alias, b_alias, to_add)?;
+    let value = exec_scopes.get::<BigInt>("value")?;
+    exec_scopes.insert_value(k_alias, value);
+    Ok(())
+}
+
 /* Implements hint:
 %{
     from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack
@@ -118,9 +136,14 @@ pub fn get_point_from_x(
     let x_cube_int = Uint384::from_var_name("x_cube", vm, ids_data, ap_tracking)?
         .pack86()
         .mod_floor(&SECP_P);
-    let y_cube_int = (x_cube_int + beta).mod_floor(&SECP_P);
-    // Divide by 4
-    let mut y = y_cube_int.modpow(&(&*SECP_P + 1_u32).shr(2_u32), &SECP_P);
+    exec_scopes.insert_value("x_cube_int", x_cube_int.clone());
+    let y_square_int = (x_cube_int + beta).mod_floor(&SECP_P);
+    // SECP_P = 3 mod 4, so y_square_int ** ((SECP_P + 1) / 4) is a square root of y_square_int
+    // if it has one
+    let mut y = y_square_int.modpow(&(&*SECP_P + 1_u32).shr(2_u32), &SECP_P);
+    if (&y * &y).mod_floor(&SECP_P) != y_square_int {
+        return Err(HintError::GetPointFromXNotOnCurve(Box::new(y_square_int)));
+    }
 
     #[allow(deprecated)]
     let v = get_integer_from_var_name("v", vm, ids_data, ap_tracking)?.to_bigint();
@@ -161,9 +184,33 @@ pub fn pack_modn_div_modn(
     Ok(())
 }
 
+/* Helper writing to memory[ap] whether the signature s-value ids.s is in the lower half of the
+   secp256k1 curve order. Cairo-lang has no hint for it, it mirrors:
+    from starkware.cairo.common.cairo_secp.secp_utils import N, pack
+
+    s = pack(ids.s, PRIME) % N
+    memory[ap] = 1 if s <= N // 2 else 0
+*/
+pub fn secp_assert_low_s(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let s = Uint384::from_var_name("s", vm, ids_data, ap_tracking)?
+        .pack86()
+        .mod_floor(&N);
+    let value = if s <= (&*N).shr(1_u32) {
+        Felt252::ONE
+    } else {
+        Felt252::ZERO
+    };
+    insert_value_into_ap(vm, value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hint_processor::builtin_hint_processor::secp::secp_utils::bigint3_split;
     use crate::stdlib::string::ToString;
     use crate::types::errors::math_errors::MathError;
 
@@ -176,6 +223,7 @@ mod tests {
             },
             hint_processor_definition::HintProcessorLogic,
         },
+        relocatable,
         types::exec_scope::ExecutionScopes,
         utils::test_utils::*,
     };
@@ -214,6 +262,18 @@ mod tests {
 
             assert_matches!(div_mod_n_safe_div(&mut exec_scopes, "a", "b", 0), Ok(()));
             assert_matches!(div_mod_n_safe_div(&mut exec_scopes, "a", "b", 1), Ok(()));
+
+            assert_matches!(
+                div_mod_n_safe_div_with_k(&mut exec_scopes, "a", "b", "k", 0),
+                Ok(())
+            );
+            let k: BigInt = exec_scopes.get("k").unwrap();
+            check_scope!(&exec_scopes, [("value", k.clone())]);
+            assert_matches!(
+                div_mod_n_safe_div_with_k(&mut exec_scopes, "a", "b", "k_plus_one", 1),
+                Ok(())
+            );
+            check_scope!(&exec_scopes, [("k_plus_one", &k + 1), ("value", k + 1)]);
         }
     }
 
@@ -239,6 +299,25 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn safe_div_with_k_bad_signature() {
+        // res * b - a isn't a multiple of N when res isn't a / b mod N
+        let mut exec_scopes = scope![
+            ("a", BigInt::from(15)),
+            ("b", BigInt::from(3)),
+            ("res", BigInt::from(4)),
+            ("N", N.clone())
+        ];
+        assert_matches!(
+            div_mod_n_safe_div_with_k(&mut exec_scopes, "a", "b", "k", 0),
+            Err(HintError::Math(MathError::SafeDivFailBigInt(bx)))
+                if *bx == (BigInt::from(-3), N.clone())
+        );
+        assert!(exec_scopes.get::<BigInt>("k").is_err());
+        assert!(exec_scopes.get::<BigInt>("value").is_err());
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_point_from_x_ok() {
@@ -296,6 +375,13 @@ mod tests {
             Ok(())
         );
 
+        check_scope!(
+            &exec_scopes,
+            [(
+                "x_cube_int",
+                bigint_str!("12855504348085611497828318551920507141114960359391260487712767")
+            )]
+        );
         check_scope!(
             &exec_scopes,
             [(
@@ -307,6 +393,59 @@ mod tests {
         );
     }
 
+    fn run_get_point_from_x(x_cube: &BigInt, v: u32) -> Result<BigInt, HintError> {
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        vm.run_context.fp = 1;
+        vm.insert_value(relocatable!(1, 0), Felt252::from(v))
+            .unwrap();
+        for (i, limb) in bigint3_split(&x_cube.to_biguint().unwrap())
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            vm.insert_value(relocatable!(1, i + 1), Felt252::from(limb))
+                .unwrap();
+        }
+        let ids_data = non_continuous_ids_data![("v", -1), ("x_cube", 0)];
+        let mut exec_scopes = ExecutionScopes::new();
+        run_hint!(
+            vm,
+            ids_data,
+            hint_code::GET_POINT_FROM_X,
+            &mut exec_scopes,
+            &[(BETA, Felt252::from(7))]
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect()
+        )?;
+        Ok(exec_scopes.get::<BigInt>("value").unwrap())
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_point_from_x_both_parities() {
+        // x = 1, so y^2 = 8
+        let x_cube = BigInt::one();
+        let even = run_get_point_from_x(&x_cube, 0).unwrap();
+        let odd = run_get_point_from_x(&x_cube, 1).unwrap();
+        assert!(even.is_even());
+        assert!(odd.is_odd());
+        assert_eq!(&even + &odd, *SECP_P);
+        assert_eq!((&even * &even).mod_floor(&SECP_P), BigInt::from(8));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_point_from_x_not_on_curve() {
+        // y^2 = -1, which has no square root as SECP_P = 3 mod 4
+        let x_cube: BigInt = &*SECP_P - 8;
+        assert_matches!(
+            run_get_point_from_x(&x_cube, 0),
+            Err(HintError::GetPointFromXNotOnCurve(bx)) if *bx == &*SECP_P - 1
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn pack_modn_div_modn_ok() {
@@ -325,6 +464,69 @@ mod tests {
         vm.run_context.fp = 3;
         let ids_data = non_continuous_ids_data![("x", -3), ("s", 0)];
         assert_matches!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
-        assert_matches!(div_mod_n_safe_div(&mut exec_scopes, "x", "s", 0), Ok(()));
+        assert_matches!(
+            div_mod_n_safe_div_with_k(&mut exec_scopes, "x", "s", "k", 0),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_assert_low_s_ok() {
+        let cases = [
+            // s = N // 2
+            (
+                [
+                    "43899669914813478955851936",
+                    "77371252455333472729943390",
+                    "9671406556917033397649407",
+                ],
+                1,
+            ),
+            // s = N // 2 + 1
+            (
+                [
+                    "43899669914813478955851937",
+                    "77371252455333472729943390",
+                    "9671406556917033397649407",
+                ],
+                0,
+            ),
+            // s = N + 1
+            (
+                [
+                    "10428087374290690730508610",
+                    "77371252455330678278691517",
+                    "19342813113834066795298815",
+                ],
+                1,
+            ),
+        ];
+        for ([d0, d1, d2], expected) in cases {
+            let mut vm = vm!();
+            vm.segments = segments![((1, 0), (d0, 10)), ((1, 1), (d1, 10)), ((1, 2), (d2, 10))];
+            vm.run_context.fp = 0;
+            vm.run_context.ap = 3;
+            let ids_data = non_continuous_ids_data![("s", 0)];
+            assert_matches!(
+                secp_assert_low_s(&mut vm, &ids_data, &ApTracking::default()),
+                Ok(())
+            );
+            check_memory![vm.segments.memory, ((1, 3), expected)];
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn secp_assert_low_s_missing_limb() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 1), ((1, 1), 0)];
+        vm.run_context.fp = 0;
+        vm.run_context.ap = 3;
+        let ids_data = non_continuous_ids_data![("s", 0)];
+        assert_matches!(
+            secp_assert_low_s(&mut vm, &ids_data, &ApTracking::default()),
+            Err(HintError::IdentifierHasNoMember(bx)) if *bx == ("s".to_string(), "d2".to_string())
+        );
     }
 }
@@ -38,12 +38,53 @@ pub fn temporary_array(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
-    let temp_segment = vm.add_temporary_segment();
+    let temp_segment = vm.try_add_temporary_segment()?;
     insert_value_from_var_name("temporary_array", temp_segment, vm, ids_data, ap_tracking)?;
 
     Ok(())
 }
 
+/*
+Implements the one-line allocation hints:
+%{ ids.<var_name> = segments.add() %}
+%{ ids.<var_name> = segments.add_temp_segment() %}
+*/
+pub fn add_segment_into_var(
+    var_name: &str,
+    temporary: bool,
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let segment = if temporary {
+        vm.try_add_temporary_segment()?
+    } else {
+        vm.try_add_memory_segment()?
+    };
+    insert_value_from_var_name(var_name, segment, vm, ids_data, ap_tracking)
+}
+
+/// Matches hints of the form `ids.<var_name> = segments.add()` or
+/// `ids.<var_name> = segments.add_temp_segment()`.
+/// Returns the name of the variable and whether the new segment is temporary.
+pub fn parse_add_segment_hint(code: &str) -> Option<(&str, bool)> {
+    let (lhs, rhs) = code.trim().split_once(" = ")?;
+    let temporary = match rhs {
+        "segments.add()" => false,
+        "segments.add_temp_segment()" => true,
+        _ => return None,
+    };
+    let var_name = lhs.strip_prefix("ids.")?;
+    if var_name.is_empty()
+        || !var_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    Some((var_name, temporary))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -59,7 +100,6 @@ mod tests {
         },
         types::exec_scope::ExecutionScopes,
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
     };
     use assert_matches::assert_matches;
 
@@ -107,4 +147,99 @@ mod tests {
         assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
         check_memory!(vm.segments.memory, ((1, 0), (-1, 0)));
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_add_segment_into_var() {
+        let hint_code = "ids.arr = segments.add()";
+        //Initialize vm
+        let mut vm = vm!();
+        vm.segments.add();
+        vm.segments.add();
+        //Initialize fp
+        vm.run_context.fp = 2;
+
+        //Create ids_data & hint_data
+        let ids_data = ids_data!["arr", "other_arr"];
+
+        //Execute the hint
+        assert_matches!(run_hint!(vm, ids_data.clone(), hint_code), Ok(()));
+        check_memory!(vm.segments.memory, ((1, 0), (2, 0)));
+        assert_eq!(vm.segments.num_segments(), 3);
+
+        //A second allocation gets the next segment
+        let hint_code = "ids.other_arr = segments.add()";
+        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        check_memory!(vm.segments.memory, ((1, 1), (3, 0)));
+        assert_eq!(vm.segments.num_segments(), 4);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_add_temp_segment_into_var() {
+        let hint_code = "ids.scratch = segments.add_temp_segment()";
+        //Initialize vm
+        let mut vm = vm!();
+        vm.segments.add();
+        vm.segments.add();
+        vm.add_temporary_segment();
+        //Initialize fp
+        vm.run_context.fp = 1;
+
+        //Create ids_data & hint_data
+        let ids_data = ids_data!["scratch"];
+
+        //Execute the hint
+        assert_matches!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        check_memory!(vm.segments.memory, ((1, 0), (-2, 0)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_add_segment_to_felt_or_relocatable() {
+        let hint_code = hint_code::ADD_SEGMENT_TO_FELT_OR_RELOCATABLE;
+        //Initialize vm
+        let mut vm = vm!();
+        vm.segments.add();
+        vm.segments.add();
+        //Initialize ap
+        vm.run_context.ap = 3;
+
+        //Execute the hint
+        assert_matches!(run_hint!(vm, HashMap::new(), hint_code), Ok(()));
+        check_memory!(vm.segments.memory, ((1, 3), (2, 0)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_add_segment_into_unknown_var() {
+        let hint_code = "ids.arr = segments.add()";
+        let mut vm = vm!();
+        vm.segments.add();
+        vm.segments.add();
+        vm.run_context.fp = 1;
+
+        let ids_data = ids_data!["other"];
+        assert_matches!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::UnknownIdentifier(bx)) if bx.as_ref() == "arr"
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_add_segment_hint_forms() {
+        assert_eq!(
+            parse_add_segment_hint("ids.arr = segments.add()"),
+            Some(("arr", false))
+        );
+        assert_eq!(
+            parse_add_segment_hint("ids.tmp_1 = segments.add_temp_segment()"),
+            Some(("tmp_1", true))
+        );
+        assert_eq!(parse_add_segment_hint("memory[ap] = segments.add()"), None);
+        assert_eq!(parse_add_segment_hint("ids.a.b = segments.add()"), None);
+        assert_eq!(parse_add_segment_hint("ids. = segments.add()"), None);
+        assert_eq!(parse_add_segment_hint("ids.arr = segments.add(1)"), None);
+    }
 }
@@ -314,7 +314,6 @@ mod tests {
         },
         types::exec_scope::ExecutionScopes,
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
     };
     use assert_matches::assert_matches;
 
@@ -1,8 +1,9 @@
 use crate::Felt252;
 use crate::{
+    any_box,
     hint_processor::builtin_hint_processor::hint_utils::{
-        get_integer_from_var_name, get_relocatable_from_var_name, insert_value_from_var_name,
-        insert_value_into_ap,
+        get_integer_from_var_name, get_ptr_from_var_name, get_relocatable_from_var_name,
+        insert_value_from_var_name, insert_value_into_ap,
     },
     hint_processor::hint_processor_definition::HintReference,
     math_utils::{isqrt, pow2_const, pow2_const_nz},
@@ -14,7 +15,9 @@ use crate::{
         ops::{Shl, Shr},
         prelude::*,
     },
-    types::{errors::math_errors::MathError, relocatable::Relocatable},
+    types::{
+        errors::math_errors::MathError, exec_scope::ExecutionScopes, relocatable::Relocatable,
+    },
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use num_bigint::BigUint;
@@ -324,6 +327,79 @@ pub fn uint256_signed_nn(
     insert_value_into_ap(vm, result)
 }
 
+/// Returns an error if any of the `limbs` of ids.`name` doesn't fit in 128 bits, as it would
+/// make the packed value differ from the one the Cairo code works with.
+pub(crate) fn assert_canonical_limbs<'a>(
+    name: &str,
+    limbs: impl IntoIterator<Item = (&'static str, &'a Felt252)>,
+) -> Result<(), HintError> {
+    for (limb_name, limb) in limbs {
+        if limb.bits() > 128 {
+            return Err(HintError::NonCanonicalLimb(Box::new((
+                format!("{name}.{limb_name}"),
+                *limb,
+            ))));
+        }
+    }
+    Ok(())
+}
+
+/*
+Helper applying the arc decomposition of assert_le_felt to a pair of Uint256, for embedders that
+check 256-bit ordering outside of the common library hints. Cairo-lang has no hint for it, it
+mirrors:
+%{
+    for name, value in (("a", ids.a), ("b", ids.b)):
+        for limb in ("low", "high"):
+            assert 0 <= getattr(value, limb) < 2 ** 128, f"ids.{name}.{limb} is not a 128-bit limb."
+    a = (ids.a.high << 128) + ids.a.low
+    b = (ids.b.high << 128) + ids.b.low
+    assert a <= b, f'a = {a} is not less than or equal to b = {b}.'
+
+    # Find the two shortest arcs among a, b - a and 2 ** 256 - 1 - b.
+    lengths_and_indices = [(a, 0), (b - a, 1), (2 ** 256 - 1 - b, 2)]
+    lengths_and_indices.sort()
+    excluded = lengths_and_indices[2][1]
+
+    memory[ids.range_check_ptr + 1], memory[ids.range_check_ptr + 0] = (
+        divmod(lengths_and_indices[0][0], 2 ** 128))
+    memory[ids.range_check_ptr + 3], memory[ids.range_check_ptr + 2] = (
+        divmod(lengths_and_indices[1][0], 2 ** 128))
+%}
+*/
+pub fn assert_uint256_le(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let a = Uint256::from_var_name("a", vm, ids_data, ap_tracking)?;
+    let b = Uint256::from_var_name("b", vm, ids_data, ap_tracking)?;
+    for (name, value) in [("a", &a), ("b", &b)] {
+        assert_canonical_limbs(name, [("low", &*value.low), ("high", &*value.high)])?;
+    }
+    let (a, b) = (a.pack(), b.pack());
+    if a > b {
+        return Err(HintError::NonLeUint256(Box::new((a, b))));
+    }
+    let range_check_ptr = get_ptr_from_var_name("range_check_ptr", vm, ids_data, ap_tracking)?;
+
+    let arc1 = &b - &a;
+    let arc2 = (BigUint::one() << 256_u32) - 1_u32 - &b;
+    let mut lengths_and_indices = [(&a, 0_i32), (&arc1, 1_i32), (&arc2, 2_i32)];
+    lengths_and_indices.sort();
+    let excluded = lengths_and_indices[2].1;
+    exec_scopes.assign_or_update_variable("excluded", any_box!(Felt252::from(excluded)));
+
+    let shortest = Uint256::split(lengths_and_indices[0].0);
+    let second = Uint256::split(lengths_and_indices[1].0);
+    vm.insert_value(range_check_ptr, shortest.low.into_owned())?;
+    vm.insert_value((range_check_ptr + 1_i32)?, shortest.high.into_owned())?;
+    vm.insert_value((range_check_ptr + 2_i32)?, second.low.into_owned())?;
+    vm.insert_value((range_check_ptr + 3_i32)?, second.high.into_owned())?;
+    Ok(())
+}
+
 /*
 Implements hint:
 %{
@@ -512,7 +588,7 @@ mod tests {
             relocatable::{MaybeRelocatable, Relocatable},
         },
         utils::test_utils::*,
-        vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+        vm::errors::memory_errors::MemoryError,
     };
     use assert_matches::assert_matches;
 
@@ -873,6 +949,119 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_uint256_le_equal_values() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 5;
+        let ids_data = ids_data!["a", "a_high", "b", "b_high", "range_check_ptr"];
+        // a = b = 2**128 + 5
+        vm.segments = segments![
+            ((1, 0), 5),
+            ((1, 1), 1),
+            ((1, 2), 5),
+            ((1, 3), 1),
+            ((1, 4), (2, 0))
+        ];
+        add_segments!(vm, 1);
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            assert_uint256_le(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_scope!(&exec_scopes, [("excluded", Felt252::from(2))]);
+        check_memory![
+            vm.segments.memory,
+            ((2, 0), 0),
+            ((2, 1), 0),
+            ((2, 2), 5),
+            ((2, 3), 1)
+        ];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_uint256_le_excludes_a() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 5;
+        let ids_data = ids_data!["a", "a_high", "b", "b_high", "range_check_ptr"];
+        // a = (2**128 - 1) << 128, b = 2**256 - 1
+        vm.segments = segments![
+            ((1, 0), 0),
+            ((1, 1), ("340282366920938463463374607431768211455", 10)),
+            ((1, 2), ("340282366920938463463374607431768211455", 10)),
+            ((1, 3), ("340282366920938463463374607431768211455", 10)),
+            ((1, 4), (2, 0))
+        ];
+        add_segments!(vm, 1);
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_matches!(
+            assert_uint256_le(&mut vm, &mut exec_scopes, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+        check_scope!(&exec_scopes, [("excluded", Felt252::ZERO)]);
+        check_memory![
+            vm.segments.memory,
+            ((2, 0), 0),
+            ((2, 1), 0),
+            ((2, 2), ("340282366920938463463374607431768211455", 10)),
+            ((2, 3), 0)
+        ];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_uint256_le_a_greater_than_b() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 5;
+        let ids_data = ids_data!["a", "a_high", "b", "b_high", "range_check_ptr"];
+        // a = 2**128, b = 2**128 - 1
+        vm.segments = segments![
+            ((1, 0), 0),
+            ((1, 1), 1),
+            ((1, 2), ("340282366920938463463374607431768211455", 10)),
+            ((1, 3), 0),
+            ((1, 4), (2, 0))
+        ];
+        assert_matches!(
+            assert_uint256_le(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &ids_data,
+                &ApTracking::default()
+            ),
+            Err(HintError::NonLeUint256(bx))
+                if *bx == (BigUint::one() << 128_u32, u128::MAX.into())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_uint256_le_non_canonical_limb() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 5;
+        let ids_data = ids_data!["a", "a_high", "b", "b_high", "range_check_ptr"];
+        // b.high = 2**128 would pack to a value greater than a, but isn't a valid limb
+        vm.segments = segments![
+            ((1, 0), 0),
+            ((1, 1), 1),
+            ((1, 2), 0),
+            ((1, 3), ("340282366920938463463374607431768211456", 10)),
+            ((1, 4), (2, 0))
+        ];
+        assert_matches!(
+            assert_uint256_le(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &ids_data,
+                &ApTracking::default()
+            ),
+            Err(HintError::NonCanonicalLimb(bx))
+                if *bx == ("b.high".to_string(), Felt252::from(u128::MAX) + 1)
+        );
+        assert!(vm.segments.memory.get(&Relocatable::from((2, 0))).is_none());
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_signed_nn_ok_result_one() {
@@ -18,6 +18,7 @@ use super::hint_utils::{
     insert_value_from_var_name, insert_value_into_ap,
 };
 use super::secp::bigint_utils::Uint384;
+use super::uint256_utils::assert_canonical_limbs;
 // Notes: Hints in this lib use the type Uint384, which is equal to common lib's BigInt3
 
 /* Implements Hint:
@@ -103,11 +104,12 @@ pub fn add_no_uint384_check(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
     constants: &HashMap<String, Felt252>,
+    accessible_scopes: &[String],
 ) -> Result<(), HintError> {
     let a = Uint384::from_var_name("a", vm, ids_data, ap_tracking)?;
     let b = Uint384::from_var_name("b", vm, ids_data, ap_tracking)?;
     // This hint is not from the cairo commonlib, and its lib can be found under different paths, so we cant rely on a full path name
-    let shift = get_constant_from_var_name("SHIFT", constants)?.to_biguint();
+    let shift = get_constant_from_var_name("SHIFT", constants, accessible_scopes)?.to_biguint();
 
     let sum_d0 = (a.limbs[0].as_ref().to_biguint()) + (b.limbs[0].as_ref().to_biguint());
     let carry_d0 = BigUint::from((sum_d0 >= shift) as usize);
@@ -177,6 +179,27 @@ pub fn uint384_sqrt(
     root_split.insert_from_var_name("root", vm, ids_data, ap_tracking)
 }
 
+/* Helper checking that every limb of a Uint384 is canonical. Cairo-lang has no hint for it, it
+mirrors:
+%{
+    for limb in ("d0", "d1", "d2"):
+        assert 0 <= getattr(ids.a, limb) < 2 ** 128, f"ids.a.{limb} is not a 128-bit limb."
+%}
+*/
+pub fn uint384_check(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let a = Uint384::from_var_name("a", vm, ids_data, ap_tracking)?;
+    assert_canonical_limbs(
+        "a",
+        ["d0", "d1", "d2"]
+            .into_iter()
+            .zip(a.limbs.iter().map(AsRef::as_ref)),
+    )
+}
+
 /* Implements Hint:
    memory[ap] = 1 if 0 <= (ids.a.d2 % PRIME) < 2 ** 127 else 0
 */
@@ -257,7 +280,7 @@ mod tests {
             relocatable::{MaybeRelocatable, Relocatable},
         },
         utils::test_utils::*,
-        vm::{errors::memory_errors::MemoryError, vm_core::VirtualMachine},
+        vm::errors::memory_errors::MemoryError,
     };
     use assert_matches::assert_matches;
 
@@ -599,6 +622,41 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn uint384_check_ok() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        let ids_data = non_continuous_ids_data![("a", -3)];
+        vm.segments = segments![
+            ((1, 0), ("340282366920938463463374607431768211455", 10)),
+            ((1, 1), 0),
+            ((1, 2), 1)
+        ];
+        assert_matches!(
+            uint384_check(&mut vm, &ids_data, &ApTracking::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn uint384_check_non_canonical_limb() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        let ids_data = non_continuous_ids_data![("a", -3)];
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), ("340282366920938463463374607431768211456", 10)),
+            ((1, 2), 1)
+        ];
+        assert_matches!(
+            uint384_check(&mut vm, &ids_data, &ApTracking::default()),
+            Err(HintError::NonCanonicalLimb(bx))
+                if *bx == ("a.d1".to_string(), Felt252::from(u128::MAX) + 1)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_signed_nn_ok_positive() {
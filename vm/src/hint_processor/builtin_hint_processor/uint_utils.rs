@@ -1,6 +1,9 @@
+use crate::math_utils::signed_felt;
+use crate::stdlib::boxed::Box;
+use crate::vm::errors::hint_errors::HintError;
 use crate::Felt252;
 use num_bigint::BigUint;
-use num_traits::One;
+use num_traits::{One, Signed};
 
 pub(crate) fn split<const N: usize>(num: &BigUint, num_bits_shift: u32) -> [Felt252; N] {
     let mut num = num.clone();
@@ -22,3 +25,52 @@ pub(crate) fn pack<const N: usize>(
         .map(|(i, limb)| limb.as_ref().to_biguint() << (i * num_bits_shift))
         .sum()
 }
+
+/// Strict variant of `pack` that fails with `NegativeLimb` if any limb looks negative
+/// (i.e. is greater than (PRIME - 1) / 2), instead of packing it as a huge positive value.
+/// Useful to catch sign-interpretation bugs in hint inputs.
+pub fn pack_non_negative<const N: usize>(
+    limbs: [impl AsRef<Felt252>; N],
+    num_bits_shift: usize,
+) -> Result<BigUint, HintError> {
+    if let Some(limb) = limbs
+        .iter()
+        .map(AsRef::as_ref)
+        .find(|limb| signed_felt(**limb).is_negative())
+    {
+        return Err(HintError::NegativeLimb(Box::new(*limb)));
+    }
+    Ok(pack(limbs, num_bits_shift))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pack_non_negative_ok() {
+        let limbs = [Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        assert_matches!(pack_non_negative(limbs, 86), Ok(x) if x == pack(limbs, 86));
+        assert_eq!(
+            pack(limbs, 86),
+            BigUint::from(1_u32) + (BigUint::from(2_u32) << 86) + (BigUint::from(3_u32) << 172)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn pack_non_negative_negative_limb() {
+        let limbs = [Felt252::from(1), Felt252::from(-5), Felt252::from(3)];
+        assert_matches!(
+            pack_non_negative(limbs, 86),
+            Err(HintError::NegativeLimb(bx)) if *bx == Felt252::from(-5)
+        );
+        // The unchecked version packs it as a huge positive value
+        assert!(pack(limbs, 86) > BigUint::one() << 250);
+    }
+}
@@ -62,8 +62,8 @@ pub fn usort_body(
         multiplicities.push(positions_dict[k].len());
     }
     exec_scopes.insert_value("positions_dict", positions_dict);
-    let output_base = vm.add_memory_segment();
-    let multiplicities_base = vm.add_memory_segment();
+    let output_base = vm.try_add_memory_segment()?;
+    let multiplicities_base = vm.try_add_memory_segment()?;
     let output_len = output.len();
 
     for (i, sorted_element) in output.into_iter().enumerate() {
@@ -148,7 +148,6 @@ mod tests {
         },
         types::exec_scope::ExecutionScopes,
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
     };
     use assert_matches::assert_matches;
 
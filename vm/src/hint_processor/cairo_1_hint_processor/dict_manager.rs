@@ -39,7 +39,7 @@ impl DictManagerExecScope {
 
     /// Allocates a new segment for a new dictionary and return the start of the segment.
     pub fn new_default_dict(&mut self, vm: &mut VirtualMachine) -> Result<Relocatable, HintError> {
-        let dict_segment = vm.add_memory_segment();
+        let dict_segment = vm.try_add_memory_segment()?;
         if self
             .trackers
             .insert(
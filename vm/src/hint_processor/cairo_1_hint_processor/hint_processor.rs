@@ -153,6 +153,23 @@ impl Cairo1HintProcessor {
                 n_used_accesses,
             })) => self.assert_all_accesses_used(vm, exec_scopes, n_used_accesses),
 
+            Hint::Core(CoreHintBase::Deprecated(
+                DeprecatedHint::AssertCurrentAccessIndicesIsEmpty,
+            )) => self.assert_current_access_indices_is_empty(exec_scopes),
+
+            Hint::Core(CoreHintBase::Deprecated(DeprecatedHint::AssertAllKeysUsed)) => {
+                self.assert_all_keys_used(exec_scopes)
+            }
+
+            Hint::Core(CoreHintBase::Deprecated(
+                DeprecatedHint::AssertLeAssertThirdArcExcluded,
+            )) => self.assert_le_assert_third_arc_excluded(exec_scopes),
+
+            Hint::Core(CoreHintBase::Deprecated(DeprecatedHint::AssertLtAssertValidInput {
+                a,
+                b,
+            })) => self.assert_lt_assert_valid_input(vm, a, b),
+
             Hint::Core(CoreHintBase::Core(CoreHint::AssertLeIsSecondArcExcluded {
                 skip_exclude_b_minus_a,
             })) => self.assert_le_is_second_excluded(vm, skip_exclude_b_minus_a, exec_scopes),
@@ -250,7 +267,7 @@ impl Cairo1HintProcessor {
     }
 
     fn alloc_segment(&self, vm: &mut VirtualMachine, dst: &CellRef) -> Result<(), HintError> {
-        let segment = vm.add_memory_segment();
+        let segment = vm.try_add_memory_segment()?;
         vm.insert_value(cell_ref_to_relocatable(dst, vm)?, segment)
             .map_err(HintError::from)
     }
@@ -794,6 +811,60 @@ impl Cairo1HintProcessor {
         Ok(())
     }
 
+    /// Asserts that every access index of the current key was consumed by the squash loop.
+    /// As `get_current_access_delta` keeps the last processed index at the end of the list
+    /// until `get_next_dict_key` pops the key, at most that one index may remain.
+    fn assert_current_access_indices_is_empty(
+        &self,
+        exec_scopes: &mut ExecutionScopes,
+    ) -> Result<(), HintError> {
+        let dict_squash_exec_scope: &mut DictSquashExecScope =
+            exec_scopes.get_mut_ref("dict_squash_exec_scope")?;
+        let current_access_indices = dict_squash_exec_scope
+            .current_access_indices()
+            .ok_or(HintError::EmptyCurrentAccessIndices)?;
+        if current_access_indices.len() > 1 {
+            return Err(HintError::CurrentAccessIndicesNotEmpty);
+        }
+        Ok(())
+    }
+
+    fn assert_all_keys_used(&self, exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
+        let dict_squash_exec_scope: &DictSquashExecScope =
+            exec_scopes.get_ref("dict_squash_exec_scope")?;
+        if !dict_squash_exec_scope.keys.is_empty() {
+            return Err(HintError::KeysNotEmpty);
+        }
+        Ok(())
+    }
+
+    fn assert_le_assert_third_arc_excluded(
+        &self,
+        exec_scopes: &mut ExecutionScopes,
+    ) -> Result<(), HintError> {
+        let excluded_arc: i32 = exec_scopes.get("excluded_arc")?;
+        if excluded_arc != 2 {
+            return Err(HintError::ExcludedNot2(Box::new(Felt252::from(
+                excluded_arc,
+            ))));
+        }
+        Ok(())
+    }
+
+    fn assert_lt_assert_valid_input(
+        &self,
+        vm: &mut VirtualMachine,
+        a: &ResOperand,
+        b: &ResOperand,
+    ) -> Result<(), HintError> {
+        let a_val = res_operand_get_val(vm, a)?;
+        let b_val = res_operand_get_val(vm, b)?;
+        if a_val >= b_val {
+            return Err(HintError::AssertLtFelt252(Box::new((a_val, b_val))));
+        }
+        Ok(())
+    }
+
     fn should_skip_squash_loop(
         &self,
         vm: &mut VirtualMachine,
@@ -969,7 +1040,7 @@ impl Cairo1HintProcessor {
                     exec_scopes.assign_or_update_variable(
                         "memory_exec_scope",
                         Box::new(MemoryExecScope {
-                            next_address: vm.add_memory_segment(),
+                            next_address: vm.try_add_memory_segment()?,
                         }),
                     );
                     exec_scopes.get_mut_ref::<MemoryExecScope>("memory_exec_scope")?
@@ -1089,6 +1160,8 @@ impl HintProcessorLogic for Cairo1HintProcessor {
         _reference_ids: &HashMap<String, usize>,
         //List of all references (key corresponds to element of the previous dictionary)
         _references: &[HintReference],
+        //Scopes accessible from the hint
+        _accessible_scopes: &[String],
     ) -> Result<Box<dyn Any>, VirtualMachineError> {
         let data = hint_code.parse().ok().and_then(|x: usize| self.hints.get(&x).cloned())
         .ok_or_else(|| VirtualMachineError::CompileHintFail(
@@ -1136,3 +1209,192 @@ impl ResourceTracker for Cairo1HintProcessor {
         &self.run_resources
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use assert_matches::assert_matches;
+    use cairo_lang_casm::operand::Register;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn hint_processor() -> Cairo1HintProcessor {
+        Cairo1HintProcessor::new(&[], RunResources::default())
+    }
+
+    fn deprecated_hint(hint: DeprecatedHint) -> Hint {
+        Hint::Core(CoreHintBase::Deprecated(hint))
+    }
+
+    fn fp_cell(offset: i16) -> ResOperand {
+        ResOperand::Deref(CellRef {
+            register: Register::FP,
+            offset,
+        })
+    }
+
+    fn dict_squash_scope(keys: &[u64], access_indices: &[(u64, &[u64])]) -> ExecutionScopes {
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value(
+            "dict_squash_exec_scope",
+            DictSquashExecScope {
+                access_indices: access_indices
+                    .iter()
+                    .map(|(key, indices)| {
+                        (
+                            Felt252::from(*key),
+                            indices.iter().map(|index| Felt252::from(*index)).collect(),
+                        )
+                    })
+                    .collect(),
+                keys: keys.iter().map(|key| Felt252::from(*key)).collect(),
+            },
+        );
+        exec_scopes
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_current_access_indices_is_empty_ok() {
+        let mut vm = vm!();
+        let mut exec_scopes = dict_squash_scope(&[5], &[(5, &[3])]);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertCurrentAccessIndicesIsEmpty)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_current_access_indices_is_empty_not_empty() {
+        let mut vm = vm!();
+        let mut exec_scopes = dict_squash_scope(&[5], &[(5, &[4, 3])]);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertCurrentAccessIndicesIsEmpty)
+            ),
+            Err(HintError::CurrentAccessIndicesNotEmpty)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_current_access_indices_is_empty_no_current_key() {
+        let mut vm = vm!();
+        let mut exec_scopes = dict_squash_scope(&[], &[]);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertCurrentAccessIndicesIsEmpty)
+            ),
+            Err(HintError::EmptyCurrentAccessIndices)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_all_keys_used_ok() {
+        let mut vm = vm!();
+        let mut exec_scopes = dict_squash_scope(&[], &[]);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertAllKeysUsed)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_all_keys_used_keys_left() {
+        let mut vm = vm!();
+        let mut exec_scopes = dict_squash_scope(&[5], &[(5, &[3])]);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertAllKeysUsed)
+            ),
+            Err(HintError::KeysNotEmpty)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_le_assert_third_arc_excluded_ok() {
+        let mut vm = vm!();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("excluded_arc", 2_i32);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertLeAssertThirdArcExcluded)
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_le_assert_third_arc_excluded_other_arc() {
+        let mut vm = vm!();
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("excluded_arc", 1_i32);
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut exec_scopes,
+                &deprecated_hint(DeprecatedHint::AssertLeAssertThirdArcExcluded)
+            ),
+            Err(HintError::ExcludedNot2(bx)) if *bx == Felt252::ONE
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_lt_assert_valid_input_ok() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 3), ((1, 1), 4)];
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &deprecated_hint(DeprecatedHint::AssertLtAssertValidInput {
+                    a: fp_cell(0),
+                    b: fp_cell(1),
+                })
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn assert_lt_assert_valid_input_not_lower() {
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 4), ((1, 1), 4)];
+        assert_matches!(
+            hint_processor().execute(
+                &mut vm,
+                &mut ExecutionScopes::new(),
+                &deprecated_hint(DeprecatedHint::AssertLtAssertValidInput {
+                    a: fp_cell(0),
+                    b: fp_cell(1),
+                })
+            ),
+            Err(HintError::AssertLtFelt252(bx)) if *bx == (Felt252::from(4), Felt252::from(4))
+        );
+    }
+}
@@ -43,11 +43,14 @@ pub trait HintProcessorLogic {
         reference_ids: &HashMap<String, usize>,
         //List of all references (key corresponds to element of the previous dictionary)
         references: &[HintReference],
+        //Scopes accessible from the hint, from outermost to innermost
+        accessible_scopes: &[String],
     ) -> Result<Box<dyn Any>, VirtualMachineError> {
         Ok(any_box!(HintProcessorData {
             code: hint_code.to_string(),
             ap_tracking: ap_tracking_data.clone(),
-            ids_data: get_ids_data(reference_ids, references)?,
+            ids_data: get_ids_data(reference_ids, references, accessible_scopes)?,
+            accessible_scopes: accessible_scopes.to_vec(),
         }))
     }
 
@@ -77,20 +80,48 @@ pub type HintExtension = HashMap<Relocatable, Vec<Box<dyn Any>>>;
 pub trait HintProcessor: HintProcessorLogic + ResourceTracker {}
 impl<T> HintProcessor for T where T: HintProcessorLogic + ResourceTracker {}
 
-fn get_ids_data(
+/// Maps the name of each of the hint's references to its `HintReference`.
+/// The keys of `reference_ids` are full paths (ie "__main__.main.x"), when more than one of them
+/// share the same name, the one defined in the innermost of the `accessible_scopes` is used.
+pub(crate) fn get_ids_data(
     reference_ids: &HashMap<String, usize>,
     references: &[HintReference],
+    accessible_scopes: &[String],
 ) -> Result<HashMap<String, HintReference>, VirtualMachineError> {
-    let mut ids_data = HashMap::<String, HintReference>::new();
-    for (path, ref_id) in reference_ids {
+    let mut paths_by_name = HashMap::<&str, Vec<&str>>::new();
+    for path in reference_ids.keys() {
         let name = path
             .rsplit('.')
             .next()
             .ok_or(VirtualMachineError::Unexpected)?;
+        paths_by_name.entry(name).or_default().push(path);
+    }
+    let mut ids_data = HashMap::<String, HintReference>::new();
+    for (name, mut paths) in paths_by_name {
+        let path = match paths.as_slice() {
+            [path] => *path,
+            _ => accessible_scopes
+                .iter()
+                .rev()
+                .find_map(|scope| {
+                    paths.iter().copied().find(|path| {
+                        path.strip_suffix(name)
+                            .and_then(|prefix| prefix.strip_suffix('.'))
+                            == Some(scope.as_str())
+                    })
+                })
+                .ok_or_else(|| {
+                    paths.sort_unstable();
+                    VirtualMachineError::AmbiguousReference(Box::new((
+                        name.to_string(),
+                        paths.iter().map(|path| path.to_string()).collect(),
+                    )))
+                })?,
+        };
         ids_data.insert(
             name.to_string(),
             references
-                .get(*ref_id)
+                .get(reference_ids[path])
                 .ok_or(VirtualMachineError::Unexpected)?
                 .clone(),
         );
@@ -151,3 +182,78 @@ impl From<Reference> for HintReference {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ids_data_unique_names() {
+        let reference_ids = HashMap::from([
+            ("__main__.main.a".to_string(), 0),
+            ("__main__.main.b".to_string(), 1),
+        ]);
+        let references = [HintReference::new_simple(-3), HintReference::new_simple(-4)];
+        assert_matches!(
+            get_ids_data(&reference_ids, &references, &[]),
+            Ok(ids_data) if ids_data == HashMap::from([
+                ("a".to_string(), HintReference::new_simple(-3)),
+                ("b".to_string(), HintReference::new_simple(-4)),
+            ])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ids_data_shared_name_uses_innermost_scope() {
+        let reference_ids = HashMap::from([
+            ("__main__.main.x".to_string(), 0),
+            ("__main__.main.inner.x".to_string(), 1),
+        ]);
+        let references = [HintReference::new_simple(-3), HintReference::new_simple(-4)];
+        let outer_scopes = ["__main__".to_string(), "__main__.main".to_string()];
+        assert_matches!(
+            get_ids_data(&reference_ids, &references, &outer_scopes),
+            Ok(ids_data) if ids_data == HashMap::from([(
+                "x".to_string(),
+                HintReference::new_simple(-3)
+            )])
+        );
+        let inner_scopes = [
+            "__main__".to_string(),
+            "__main__.main".to_string(),
+            "__main__.main.inner".to_string(),
+        ];
+        assert_matches!(
+            get_ids_data(&reference_ids, &references, &inner_scopes),
+            Ok(ids_data) if ids_data == HashMap::from([(
+                "x".to_string(),
+                HintReference::new_simple(-4)
+            )])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_ids_data_ambiguous_name() {
+        let reference_ids = HashMap::from([("pkg.a.x".to_string(), 0), ("pkg.b.x".to_string(), 1)]);
+        let references = [HintReference::new_simple(-3), HintReference::new_simple(-4)];
+        let error = get_ids_data(&reference_ids, &references, &["pkg".to_string()]);
+        assert_matches!(
+            error,
+            Err(VirtualMachineError::AmbiguousReference(bx))
+                if *bx == ("x".to_string(), vec!["pkg.a.x".to_string(), "pkg.b.x".to_string()])
+        );
+        assert_eq!(
+            get_ids_data(&reference_ids, &references, &[])
+                .unwrap_err()
+                .to_string(),
+            "Reference x is ambiguous in the hint's accessible scopes, candidates: pkg.a.x, pkg.b.x"
+        );
+    }
+}
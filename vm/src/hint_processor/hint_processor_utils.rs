@@ -22,6 +22,7 @@ pub fn insert_value_from_reference(
     hint_reference: &HintReference,
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
+    check_ap_tracking_group(hint_reference, ap_tracking)?;
     let var_addr = compute_addr_from_reference(hint_reference, vm, ap_tracking)
         .ok_or(HintError::UnknownIdentifierInternal)?;
     vm.insert_value(var_addr, value).map_err(HintError::Memory)
@@ -41,6 +42,7 @@ pub fn get_integer_from_reference<'a>(
         return Ok(Cow::Borrowed(int_1));
     }
 
+    check_ap_tracking_group(hint_reference, ap_tracking)?;
     let var_addr = compute_addr_from_reference(hint_reference, vm, ap_tracking)
         .ok_or(HintError::UnknownIdentifierInternal)?;
     vm.get_integer(var_addr)
@@ -53,6 +55,7 @@ pub fn get_ptr_from_reference(
     hint_reference: &HintReference,
     ap_tracking: &ApTracking,
 ) -> Result<Relocatable, HintError> {
+    check_ap_tracking_group(hint_reference, ap_tracking)?;
     let var_addr = compute_addr_from_reference(hint_reference, vm, ap_tracking)
         .ok_or(HintError::UnknownIdentifierInternal)?;
     if hint_reference.dereference {
@@ -121,17 +124,54 @@ pub fn compute_addr_from_reference(
     }
 }
 
-fn apply_ap_tracking_correction(
-    ap: Relocatable,
+///Checks that the reference can be resolved from a hint with the given ap tracking.
+///References based on `ap` can only be corrected when they belong to the same ap tracking group
+///as the hint, as the distance between `ap` values of different groups is unknown, and when they
+///were created at or before the hint's ap tracking offset (see `apply_ap_tracking_correction`).
+///References based on `fp` don't carry ap tracking data and are always valid.
+pub fn check_ap_tracking_group(
+    hint_reference: &HintReference,
+    hint_ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    match &hint_reference.ap_tracking_data {
+        Some(ref_ap_tracking) => ap_tracking_diff(ref_ap_tracking, hint_ap_tracking).map(|_| ()),
+        None => Ok(()),
+    }
+}
+
+///Returns how much `ap` advanced between the creation of an `ap`-based reference and the hint.
+///Both must belong to the same ap tracking group, and the difference is the hint's offset minus
+///the reference's offset, which is only defined when the hint's offset is not smaller.
+///A hint placed before any `ap` change (offset 0) can only use references of offset 0.
+fn ap_tracking_diff(
     ref_ap_tracking: &ApTracking,
     hint_ap_tracking: &ApTracking,
-) -> Option<Relocatable> {
+) -> Result<usize, HintError> {
     // check that both groups are the same
     if ref_ap_tracking.group != hint_ap_tracking.group {
-        return None;
+        return Err(HintError::InvalidTrackingGroup(Box::new((
+            ref_ap_tracking.group,
+            hint_ap_tracking.group,
+        ))));
     }
-    let ap_diff = hint_ap_tracking.offset - ref_ap_tracking.offset;
-    (ap - ap_diff).ok()
+    hint_ap_tracking
+        .offset
+        .checked_sub(ref_ap_tracking.offset)
+        .ok_or_else(|| {
+            HintError::InvalidTrackingOffset(Box::new((
+                ref_ap_tracking.offset,
+                hint_ap_tracking.offset,
+            )))
+        })
+}
+
+fn apply_ap_tracking_correction(
+    ap: Relocatable,
+    ref_ap_tracking: &ApTracking,
+    hint_ap_tracking: &ApTracking,
+) -> Result<Relocatable, HintError> {
+    let ap_diff = ap_tracking_diff(ref_ap_tracking, hint_ap_tracking)?;
+    Ok((ap - ap_diff)?)
 }
 
 //Tries to convert a Felt252 value to usize
@@ -162,7 +202,7 @@ fn get_offset_value_reference(
     } else {
         let var_ap_trackig = hint_reference.ap_tracking_data.as_ref()?;
 
-        apply_ap_tracking_correction(vm.get_ap(), var_ap_trackig, hint_ap_tracking)?
+        apply_ap_tracking_correction(vm.get_ap(), var_ap_trackig, hint_ap_tracking).ok()?
     };
 
     if offset.is_negative() && base_addr.offset < offset.unsigned_abs() as usize {
@@ -180,11 +220,7 @@ fn get_offset_value_reference(
 mod tests {
     use super::*;
 
-    use crate::{
-        relocatable,
-        utils::test_utils::*,
-        vm::{vm_core::VirtualMachine, vm_memory::memory::Memory},
-    };
+    use crate::{relocatable, utils::test_utils::*, vm::vm_memory::memory::Memory};
     use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
@@ -316,7 +352,7 @@ mod tests {
 
         assert_matches!(
             apply_ap_tracking_correction(relocatable!(1, 0), &ref_ap_tracking, &hint_ap_tracking),
-            Some(relocatable!(1, 0))
+            Ok(relocatable!(1, 0))
         );
     }
 
@@ -328,12 +364,167 @@ mod tests {
         let mut hint_ap_tracking = ApTracking::new();
         hint_ap_tracking.group = 2;
 
-        assert!(apply_ap_tracking_correction(
-            relocatable!(1, 0),
-            &ref_ap_tracking,
-            &hint_ap_tracking
-        )
-        .is_none());
+        assert_matches!(
+            apply_ap_tracking_correction(relocatable!(1, 0), &ref_ap_tracking, &hint_ap_tracking),
+            Err(HintError::InvalidTrackingGroup(bx)) if *bx == (1, 2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tracking_correction_offset() {
+        let mut ref_ap_tracking = ApTracking::new();
+        ref_ap_tracking.group = 1;
+        ref_ap_tracking.offset = 2;
+        let mut hint_ap_tracking = ApTracking::new();
+        hint_ap_tracking.group = 1;
+        hint_ap_tracking.offset = 5;
+
+        assert_matches!(
+            apply_ap_tracking_correction(relocatable!(1, 7), &ref_ap_tracking, &hint_ap_tracking),
+            Ok(relocatable!(1, 4))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn tracking_correction_reference_after_hint() {
+        let ref_ap_tracking = ApTracking {
+            group: 1,
+            offset: 3,
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 1,
+            offset: 1,
+        };
+
+        assert_matches!(
+            apply_ap_tracking_correction(relocatable!(1, 7), &ref_ap_tracking, &hint_ap_tracking),
+            Err(HintError::InvalidTrackingOffset(bx)) if *bx == (3, 1)
+        );
+    }
+
+    /// Reference `[ap - 1]` with the given ap tracking data
+    fn ap_reference(group: usize, offset: usize) -> HintReference {
+        let mut hint_ref = HintReference::new(0, 0, false, true);
+        hint_ref.offset1 = OffsetValue::Reference(Register::AP, -1, false);
+        hint_ref.ap_tracking_data = Some(ApTracking { group, offset });
+        hint_ref
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_ap_reference_hint_before_first_ap_change() {
+        // A hint at the first instruction of a function (group 0, offset 0), before any ap++,
+        // using an argument referenced as [ap - 1]: no correction is applied.
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 3), ((1, 1), 7)];
+        vm.run_context.ap = 2;
+        let hint_ap_tracking = ApTracking {
+            group: 0,
+            offset: 0,
+        };
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &ap_reference(0, 0), &hint_ap_tracking)
+                .unwrap()
+                .as_ref(),
+            &Felt252::from(7)
+        );
+        // A reference created later in the same group can't be used by this hint
+        assert_matches!(
+            get_integer_from_reference(&vm, &ap_reference(0, 1), &hint_ap_tracking),
+            Err(HintError::InvalidTrackingOffset(bx)) if *bx == (1, 0)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_ap_reference_after_if_branch_merge() {
+        // After the branches of an if merge, ap tracking starts a new group (2) at offset 0.
+        // The hint runs after two ap++ in the new group.
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 3), ((1, 1), 7), ((1, 2), 11)];
+        vm.run_context.ap = 5;
+        let hint_ap_tracking = ApTracking {
+            group: 2,
+            offset: 2,
+        };
+
+        // [ap - 1] at the merge point (offset 0) is [(1, 2)]
+        assert_eq!(
+            get_integer_from_reference(&vm, &ap_reference(2, 0), &hint_ap_tracking)
+                .unwrap()
+                .as_ref(),
+            &Felt252::from(11)
+        );
+        // [ap - 1] after the first ap++ (offset 1) is [(1, 3)], which is unknown
+        assert_matches!(
+            get_integer_from_reference(&vm, &ap_reference(2, 1), &hint_ap_tracking),
+            Err(HintError::WrongIdentifierTypeInternal(bx)) if *bx == relocatable!(1, 3)
+        );
+        // References from a branch (group 1) can't be resolved after the merge
+        assert_matches!(
+            get_integer_from_reference(&vm, &ap_reference(1, 0), &hint_ap_tracking),
+            Err(HintError::InvalidTrackingGroup(bx)) if *bx == (1, 2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_ap_reference_other_group() {
+        // A reference declared in group 1, used by a hint in group 2 (e.g. an inlined body)
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 7)];
+        vm.run_context.ap = 1;
+        let mut hint_ref = HintReference::new(0, 0, false, true);
+        hint_ref.offset1 = OffsetValue::Reference(Register::AP, -1, false);
+        hint_ref.ap_tracking_data = Some(ApTracking {
+            group: 1,
+            offset: 0,
+        });
+
+        assert_matches!(
+            get_integer_from_reference(&vm, &hint_ref, &ApTracking { group: 2, offset: 0 }),
+            Err(HintError::InvalidTrackingGroup(bx)) if *bx == (1, 2)
+        );
+        assert_eq!(
+            get_integer_from_reference(
+                &vm,
+                &hint_ref,
+                &ApTracking {
+                    group: 1,
+                    offset: 0
+                }
+            )
+            .unwrap()
+            .as_ref(),
+            &Felt252::from(7)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_integer_from_fp_reference_ignores_ap_tracking_group() {
+        // fp based references (such as locals) don't depend on the hint's ap tracking
+        let mut vm = vm!();
+        vm.segments = segments![((1, 0), 7)];
+        vm.run_context.fp = 1;
+        let hint_ref = HintReference::new_simple(-1);
+
+        assert_eq!(
+            get_integer_from_reference(
+                &vm,
+                &hint_ref,
+                &ApTracking {
+                    group: 2,
+                    offset: 3
+                }
+            )
+            .unwrap()
+            .as_ref(),
+            &Felt252::from(7)
+        );
     }
 
     #[test]
@@ -236,13 +236,20 @@ pub fn ec_double(
 }
 /// Computes the slope of an elliptic curve with the equation y^2 = x^3 + alpha*x + beta mod p, at
 /// the given point.
-/// Assumes the point is given in affine form (x, y) and has y != 0.
+/// Assumes the point is given in affine form (x, y), failing with
+/// [`MathError::EcDoubleSlopeZeroY`] if y == 0 (mod p).
 pub fn ec_double_slope(
     point: &(BigInt, BigInt),
     alpha: &BigInt,
     prime: &BigInt,
 ) -> Result<BigInt, MathError> {
-    debug_assert!(!point.1.is_multiple_of(prime));
+    if point.1.is_multiple_of(prime) {
+        return Err(MathError::EcDoubleSlopeZeroY(Box::new((
+            point.0.clone(),
+            point.1.clone(),
+            prime.clone(),
+        ))));
+    }
     div_mod(
         &(3_i32 * &point.0 * &point.0 + alpha),
         &(2_i32 * &point.1),
@@ -560,6 +567,17 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_double_slope_zero_y() {
+        let prime = bigint!(23);
+        let point = (bigint!(5), bigint!(46));
+        assert_matches!(
+            ec_double_slope(&point, &bigint!(1), &prime),
+            Err(MathError::EcDoubleSlopeZeroY(bx)) if *bx == (bigint!(5), bigint!(46), bigint!(23))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn compute_double_slope_for_valid_point_b() {
@@ -0,0 +1,190 @@
+//! Computation of the program hash verified by the bootloader when running a task.
+//! Matches `compute_program_hash_chain` from cairo-lang's `starkware.cairo.bootloaders.hash_program`.
+
+use starknet_crypto::{pedersen_hash, FieldElement};
+use thiserror_no_std::Error;
+
+use crate::{
+    serde::deserialize_program::BuiltinName, stdlib::prelude::*, utils::felt_from_short_string,
+    vm::runners::cairo_pie::StrippedProgram, Felt252,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProgramHashError {
+    #[error("Can't compute the hash chain of an empty sequence")]
+    EmptyData,
+    #[error("Invalid program builtin: {0} can't be encoded as a felt")]
+    InvalidProgramBuiltin(&'static str),
+    #[error("Invalid program data: data contains relocatable values")]
+    InvalidProgramData,
+    #[error("Failed to convert a felt into a pedersen hash input")]
+    FeltConversion,
+}
+
+/// Encodes a builtin the way the program header does: its name, without the "_builtin" suffix,
+/// as a short string.
+fn builtin_to_felt(builtin: &BuiltinName) -> Result<Felt252, ProgramHashError> {
+    let name = builtin.name();
+    felt_from_short_string(name.strip_suffix("_builtin").unwrap_or(name))
+        .map_err(|_| ProgramHashError::InvalidProgramBuiltin(name))
+}
+
+/// Returns the sequence hashed into the program hash: its length, followed by the program header
+/// (bootloader version, main offset, number of builtins and builtin names) and the program data.
+pub fn program_hash_chain_data(
+    program: &StrippedProgram,
+    bootloader_version: usize,
+) -> Result<Vec<Felt252>, ProgramHashError> {
+    let data_chain_len = 3 + program.builtins.len() + program.data.len();
+    let mut data = Vec::with_capacity(data_chain_len + 1);
+    data.extend([
+        Felt252::from(data_chain_len),
+        Felt252::from(bootloader_version),
+        Felt252::from(program.main),
+        Felt252::from(program.builtins.len()),
+    ]);
+    for builtin in program.builtins.iter() {
+        data.push(builtin_to_felt(builtin)?);
+    }
+    for value in program.data.iter() {
+        data.push(
+            *value
+                .get_int_ref()
+                .ok_or(ProgramHashError::InvalidProgramData)?,
+        );
+    }
+    Ok(data)
+}
+
+/// Computes the pedersen hash chain of `data`:
+/// h(data[0], h(data[1], h(..., h(data[n-2], data[n-1])))).
+pub fn compute_hash_chain(data: &[Felt252]) -> Result<Felt252, ProgramHashError> {
+    let to_field_element = |felt: &Felt252| {
+        FieldElement::from_bytes_be(&felt.to_bytes_be())
+            .map_err(|_| ProgramHashError::FeltConversion)
+    };
+    let (last, rest) = data.split_last().ok_or(ProgramHashError::EmptyData)?;
+    let mut hash = to_field_element(last)?;
+    for value in rest.iter().rev() {
+        hash = pedersen_hash(&to_field_element(value)?, &hash);
+    }
+    Ok(Felt252::from_bytes_be(&hash.to_bytes_be()))
+}
+
+/// Computes the hash of `program` as the bootloader does when validating a task, so embedders can
+/// pre-compute the expected hash of the programs they submit.
+pub fn compute_program_hash_chain(
+    program: &StrippedProgram,
+    bootloader_version: usize,
+) -> Result<Felt252, ProgramHashError> {
+    compute_hash_chain(&program_hash_chain_data(program, bootloader_version)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{felt_hex, types::program::Program, types::relocatable::MaybeRelocatable};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_hash_chain_single_element() {
+        assert_eq!(
+            compute_hash_chain(&[Felt252::from(7)]),
+            Ok(Felt252::from(7))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_hash_chain_pair() {
+        // Test vector from starknet-crypto's pedersen_hash
+        assert_eq!(
+            compute_hash_chain(&[
+                felt_hex!("0x3d937c035c878245caf64531a5756109c53068da139362728feb561405371cb"),
+                felt_hex!("0x208a0a10250e382e1e4bbe2880906c2791bf6275695e02fbbc6aeff9cd8b31a"),
+            ]),
+            Ok(felt_hex!(
+                "0x30e480bed5fe53fa909cc0f8c4d99b8f9f2c016be4c41e13a4848797979c662"
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_hash_chain_folds_from_the_end() {
+        let data = [Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        let inner = compute_hash_chain(&data[1..]).unwrap();
+        assert_eq!(
+            compute_hash_chain(&data),
+            compute_hash_chain(&[data[0], inner])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_hash_chain_empty_data() {
+        assert_eq!(compute_hash_chain(&[]), Err(ProgramHashError::EmptyData));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_hash_chain_data_layout() {
+        let program = Program::from_bytes(
+            include_bytes!("../../cairo_programs/manually_compiled/proof_mode_output_only.json"),
+            Some("main"),
+        )
+        .unwrap()
+        .get_stripped_program()
+        .unwrap();
+        let data = program_hash_chain_data(&program, 1).unwrap();
+        assert_eq!(
+            data[..5],
+            [
+                Felt252::from(16),
+                Felt252::from(1),
+                Felt252::from(6),
+                Felt252::from(1),
+                // "output"
+                Felt252::from(0x6f7574707574_u64),
+            ]
+        );
+        assert_eq!(data.len(), 17);
+        assert_eq!(data[5], felt_hex!("0x40780017fff7fff"));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_program_hash_chain_depends_on_bootloader_version() {
+        let program = Program::from_bytes(
+            include_bytes!("../../cairo_programs/manually_compiled/proof_mode_output_only.json"),
+            Some("main"),
+        )
+        .unwrap()
+        .get_stripped_program()
+        .unwrap();
+        let hash_v0 = compute_program_hash_chain(&program, 0).unwrap();
+        assert_eq!(
+            Ok(hash_v0),
+            compute_hash_chain(&program_hash_chain_data(&program, 0).unwrap())
+        );
+        assert_ne!(Ok(hash_v0), compute_program_hash_chain(&program, 1));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_program_hash_chain_relocatable_data() {
+        let program = StrippedProgram {
+            data: vec![MaybeRelocatable::from((1, 0))],
+            builtins: vec![],
+            main: 0,
+            prime: (),
+        };
+        assert_eq!(
+            compute_program_hash_chain(&program, 0),
+            Err(ProgramHashError::InvalidProgramData)
+        );
+    }
+}
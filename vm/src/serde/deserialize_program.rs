@@ -139,6 +139,8 @@ pub struct Identifier {
     pub full_name: Option<String>,
     pub members: Option<HashMap<String, Member>>,
     pub cairo_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
 }
 
 #[cfg_attr(all(feature = "arbitrary", feature = "std"), derive(Arbitrary))]
@@ -364,7 +366,7 @@ impl<'de> de::Visitor<'de> for MaybeRelocatableVisitor {
     type Value = Vec<MaybeRelocatable>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("Could not deserialize array of hexadecimal")
+        formatter.write_str("Could not deserialize array of felts")
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
@@ -374,10 +376,10 @@ impl<'de> de::Visitor<'de> for MaybeRelocatableVisitor {
         let mut data: Vec<MaybeRelocatable> = vec![];
 
         while let Some(value) = seq.next_element::<String>()? {
-            // Add padding if necessary
-            let value = deserialize_utils::maybe_add_padding(value.to_string());
             data.push(MaybeRelocatable::Int(
-                Felt252::from_hex(&value).map_err(de::Error::custom)?,
+                deserialize_utils::parse_felt(&value).ok_or_else(|| {
+                    de::Error::custom(format!("Invalid felt in program data: {value}"))
+                })?,
             ));
         }
         Ok(data)
@@ -842,6 +844,52 @@ mod tests {
         assert_eq!(program_json.identifiers["__main__.main"].pc, Some(13));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deserialize_program_data_in_every_felt_format() {
+        let hex = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/manually_compiled/hintless_loop.json"),
+            Some("main"),
+        )
+        .unwrap();
+        for reader in [
+            &include_bytes!("../../../cairo_programs/manually_compiled/hintless_loop_decimal.json")
+                [..],
+            &include_bytes!(
+                "../../../cairo_programs/manually_compiled/hintless_loop_negative_decimal.json"
+            )[..],
+        ] {
+            assert_eq!(Program::from_bytes(reader, Some("main")).unwrap(), hex);
+        }
+        assert_eq!(
+            hex.shared_program_data.data[3],
+            MaybeRelocatable::from(Felt252::from(-1))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deserialize_program_data_felt_out_of_range() {
+        let program_json = r#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "builtins": [],
+                "data": [
+                    "0x800000000000011000000000000000000000000000000000000000000000001"
+                ],
+                "identifiers": {},
+                "hints": {},
+                "reference_manager": {
+                    "references": []
+                }
+            }"#;
+
+        let err = serde_json::from_str::<ProgramJson>(program_json).unwrap_err();
+        assert!(err.to_string().contains(
+            "Invalid felt in program data: 0x800000000000011000000000000000000000000000000000000000000000001"
+        ));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn deserialize_program_json_from_json_file_gives_error() {
@@ -1041,6 +1089,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1054,6 +1103,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1065,6 +1115,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: Some(String::from("starkware.cairo.common.math.unsigned_div_rem")),
             },
         );
         identifiers.insert(
@@ -1078,6 +1129,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1089,6 +1141,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1100,6 +1153,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1111,6 +1165,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
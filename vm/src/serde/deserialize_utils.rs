@@ -1,7 +1,7 @@
 //! # Deserialization utils
 //!
 //! This module contains some helper functions used in [`Program`](crate::types::program::Program) deserialization.
-//! Namely, [`maybe_add_padding`] and [`parse_value`].
+//! Namely, [`maybe_add_padding`], [`parse_felt`] and [`parse_value`].
 //!
 //! See [the docs](/docs/references_parsing/README.md) for context and grammar explanation.
 
@@ -9,6 +9,7 @@ use crate::stdlib::{prelude::*, str::FromStr};
 use crate::{
     serde::deserialize_program::{OffsetValue, ValueAddress},
     types::instruction::Register,
+    utils::PRIME_STR,
 };
 
 use crate::Felt252;
@@ -38,6 +39,47 @@ pub fn maybe_add_padding(mut hex: String) -> String {
     hex
 }
 
+// Decimal digits of the prime
+const PRIME_DEC_STR: &str =
+    "3618502788666131213697322783095070105623107215331596699973092056135872020481";
+
+// Checks that the number made of `digits` (in the same radix as `prime_digits`, without sign or
+// prefix) is lower than the prime, comparing the digits themselves to avoid parsing it
+fn is_lower_than_prime(digits: &str, prime_digits: &str) -> bool {
+    let digits = digits.trim_start_matches('0');
+    digits.len() < prime_digits.len()
+        || (digits.len() == prime_digits.len()
+            && digits
+                .bytes()
+                .map(|b| b.to_ascii_lowercase())
+                .lt(prime_digits.bytes()))
+}
+
+// Parses a felt of program data: hex with the '0x' prefix, decimal, or negative decimal, which is
+// reduced modulo the prime. Non-negative values must be lower than the prime.
+pub fn parse_felt(value: &str) -> Option<Felt252> {
+    if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        if hex.is_empty()
+            || !hex.bytes().all(|b| b.is_ascii_hexdigit())
+            || !is_lower_than_prime(hex, &PRIME_STR[2..])
+        {
+            return None;
+        }
+        return Felt252::from_hex(hex).ok();
+    }
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if digits.is_empty()
+        || !digits.bytes().all(|b| b.is_ascii_digit())
+        || (digits.len() == value.len() && !is_lower_than_prime(digits, PRIME_DEC_STR))
+    {
+        return None;
+    }
+    Felt252::from_dec_str(value).ok()
+}
+
 // -----------------------
 //       NOM PARSERS
 // -----------------------
@@ -282,6 +324,56 @@ mod tests {
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_felt_formats() {
+        let felt = Felt252::from(0x1234);
+        assert_eq!(parse_felt("0x1234"), Some(felt));
+        assert_eq!(parse_felt("0X1234"), Some(felt));
+        assert_eq!(parse_felt("0x01234"), Some(felt));
+        assert_eq!(parse_felt("4660"), Some(felt));
+        // Unprefixed numbers are always decimal
+        assert_eq!(parse_felt("1234"), Some(Felt252::from(1234)));
+        assert_eq!(parse_felt("-4660"), Some(-felt));
+        assert_eq!(parse_felt("-0"), Some(Felt252::ZERO));
+        assert_eq!(
+            parse_felt("0x800000000000011000000000000000000000000000000000000000000000000"),
+            Some(Felt252::MAX)
+        );
+        assert_eq!(
+            parse_felt("0x800000000000011000000000000000000000000000000000000000000000000"),
+            parse_felt(
+                "3618502788666131213697322783095070105623107215331596699973092056135872020480"
+            )
+        );
+        assert_eq!(
+            parse_felt(
+                "-3618502788666131213697322783095070105623107215331596699973092056135872020482"
+            ),
+            Some(Felt252::MAX)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn parse_felt_invalid() {
+        assert_eq!(parse_felt(""), None);
+        assert_eq!(parse_felt("0x"), None);
+        assert_eq!(parse_felt("-"), None);
+        assert_eq!(parse_felt("-0x1"), None);
+        assert_eq!(parse_felt("+1"), None);
+        assert_eq!(parse_felt("0x+1"), None);
+        assert_eq!(parse_felt("0xlambda"), None);
+        assert_eq!(parse_felt("abc"), None);
+        assert_eq!(parse_felt("1.5"), None);
+        assert_eq!(parse_felt(PRIME_DEC_STR), None);
+        assert_eq!(parse_felt(PRIME_STR), None);
+        assert_eq!(
+            parse_felt("0x0800000000000011000000000000000000000000000000000000000000000002"),
+            None
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn outer_brackets_test() {
@@ -124,6 +124,8 @@ pub(crate) struct IdentifierSerializer {
     pub full_name: Option<String>,
     pub members: Option<HashMap<String, Member>>,
     pub cairo_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
 }
 
 impl From<IdentifierSerializer> for Identifier {
@@ -135,6 +137,7 @@ impl From<IdentifierSerializer> for Identifier {
             full_name: identifier_serialer.full_name,
             members: identifier_serialer.members,
             cairo_type: identifier_serialer.cairo_type,
+            destination: identifier_serialer.destination,
         }
     }
 }
@@ -148,6 +151,7 @@ impl From<Identifier> for IdentifierSerializer {
             full_name: identifier_serialer.full_name,
             members: identifier_serialer.members,
             cairo_type: identifier_serialer.cairo_type,
+            destination: identifier_serialer.destination,
         }
     }
 }
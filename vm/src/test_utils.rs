@@ -0,0 +1,294 @@
+//! Helpers for writing tests and property tests against the VM: random values, memory and
+//! hint reference macros, and a builder for small synthetic programs.
+//!
+//! Available behind the `test_utils` feature. This module is meant for tests only, so
+//! semver applies loosely to it: its contents may change between minor releases.
+
+use crate::serde::deserialize_program::{
+    Attribute, BuiltinName, HintParams, Identifier, InstructionLocation, ReferenceManager,
+};
+use crate::stdlib::{
+    collections::{BTreeMap, HashMap},
+    prelude::*,
+    sync::Arc,
+};
+use crate::types::program::{HintsCollection, Program, SharedProgramData};
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::vm::errors::memory_errors::MemoryError;
+use crate::vm::vm_memory::memory::Memory;
+use crate::Felt252;
+use rand::Rng;
+
+pub use crate::{ids_data, mayberelocatable, memory, references, relocatable};
+
+/// Random generation of field elements
+pub trait RandomFelt {
+    /// Returns a uniformly distributed felt
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+impl RandomFelt for Felt252 {
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        // 64 random bytes reduced modulo PRIME, so the bias is negligible
+        let mut bytes = [0u8; 64];
+        rng.fill(&mut bytes[..]);
+        Felt252::from_bytes_be_slice(&bytes)
+    }
+}
+
+impl Relocatable {
+    /// Returns a relocatable in segment `segment_index` with an offset in `0..offset_bound`.
+    /// Panics if `offset_bound` is 0.
+    pub fn random_in_segment<R: Rng + ?Sized>(
+        rng: &mut R,
+        segment_index: isize,
+        offset_bound: usize,
+    ) -> Self {
+        Relocatable::from((segment_index, rng.gen_range(0..offset_bound)))
+    }
+}
+
+/// Inserts `value` at `key`, adding empty segments until `key`'s segment exists.
+/// Used by the [`memory!`] macro.
+/// Panics if the insertion fails for any other reason (e.g. an inconsistent write).
+#[doc(hidden)]
+pub fn insert_allocating(memory: &mut Memory, key: Relocatable, value: &MaybeRelocatable) {
+    loop {
+        match memory.insert(key, value) {
+            Ok(()) => return,
+            Err(MemoryError::UnallocatedSegment(_)) if key.segment_index < 0 => {
+                memory.temp_data.push(Vec::new())
+            }
+            Err(MemoryError::UnallocatedSegment(_)) => memory.data.push(Vec::new()),
+            Err(error) => panic!("failed to insert {value} at {key}: {error}"),
+        }
+    }
+}
+
+/// Creates a [`Memory`](crate::vm::vm_memory::memory::Memory) with the given cells,
+/// allocating the segments they belong to.
+/// Values can be integers, `(segment, offset)` pairs or `(string, radix)` pairs.
+#[macro_export]
+macro_rules! memory {
+    ( $( (($si:expr, $off:expr), $val:tt) ),* $(,)? ) => {
+        {
+            let mut memory = $crate::vm::vm_memory::memory::Memory::new();
+            $crate::memory_from_memory!(memory, ( $( (($si, $off), $val) ),* ));
+            memory
+        }
+    };
+}
+
+/// Inserts the given cells into an existing memory, see [`memory!`]
+#[macro_export]
+macro_rules! memory_from_memory {
+    ($mem: expr, ( $( (($si:expr, $off:expr), $val:tt) ),* )) => {
+        {
+            $(
+                $crate::memory_inner!($mem, ($si, $off), $val);
+            )*
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! memory_inner {
+    ($mem:expr, ($si:expr, $off:expr), ($sival:expr, $offval: expr)) => {
+        $crate::test_utils::insert_allocating(
+            &mut $mem,
+            ($si, $off).into(),
+            &$crate::mayberelocatable!($sival, $offval),
+        );
+    };
+    ($mem:expr, ($si:expr, $off:expr), $val:expr) => {
+        $crate::test_utils::insert_allocating(
+            &mut $mem,
+            ($si, $off).into(),
+            &$crate::mayberelocatable!($val),
+        );
+    };
+}
+
+/// Creates `$num` hint references, where reference `i` points to `[fp + i - $num]`
+#[macro_export]
+macro_rules! references {
+    ($num: expr) => {{
+        let mut references = $crate::stdlib::collections::HashMap::<
+            usize,
+            $crate::hint_processor::hint_processor_definition::HintReference,
+        >::new();
+        for i in 0..$num {
+            references.insert(
+                i as usize,
+                $crate::hint_processor::hint_processor_definition::HintReference::new_simple(
+                    (i as i32 - $num),
+                ),
+            );
+        }
+        references
+    }};
+}
+
+/// Creates the `ids` data of a hint, mapping each name to a consecutive cell ending at `[fp - 1]`
+#[macro_export]
+macro_rules! ids_data {
+    ( $( $name: expr ),* ) => {
+        {
+            let ids_names = [$( $name ),*];
+            let references = $crate::references!(ids_names.len() as i32);
+            let mut ids_data = $crate::stdlib::collections::HashMap::<
+                $crate::stdlib::string::String,
+                $crate::hint_processor::hint_processor_definition::HintReference,
+            >::new();
+            for (i, name) in ids_names.iter().enumerate() {
+                ids_data.insert(
+                    $crate::stdlib::string::ToString::to_string(name),
+                    references.get(&i).unwrap().clone(),
+                );
+            }
+            ids_data
+        }
+    };
+}
+
+/// Builds small synthetic programs. Unset fields take their default (empty) values.
+pub struct ProgramBuilder {
+    pub data: Vec<MaybeRelocatable>,
+    pub hints: BTreeMap<usize, Vec<HintParams>>,
+    pub main: Option<usize>,
+    //start and end labels will only be used in proof-mode
+    pub start: Option<usize>,
+    pub end: Option<usize>,
+    pub error_message_attributes: Vec<Attribute>,
+    pub instruction_locations: Option<HashMap<usize, InstructionLocation>>,
+    pub identifiers: HashMap<String, Identifier>,
+    pub constants: HashMap<String, Felt252>,
+    pub builtins: Vec<BuiltinName>,
+    pub reference_manager: ReferenceManager,
+}
+
+impl Default for ProgramBuilder {
+    fn default() -> Self {
+        Self {
+            data: Default::default(),
+            hints: Default::default(),
+            main: Default::default(),
+            start: Default::default(),
+            end: Default::default(),
+            error_message_attributes: Default::default(),
+            instruction_locations: Default::default(),
+            identifiers: Default::default(),
+            constants: Default::default(),
+            builtins: Default::default(),
+            reference_manager: ReferenceManager {
+                references: Vec::new(),
+            },
+        }
+    }
+}
+
+impl ProgramBuilder {
+    /// Builds the program. Panics if a hint has a pc outside of the program's data.
+    pub fn build(self) -> Program {
+        self.into()
+    }
+}
+
+impl From<ProgramBuilder> for Program {
+    fn from(val: ProgramBuilder) -> Self {
+        // NOTE: panics if hints have PCs higher than the program length
+        let hints_collection =
+            HintsCollection::new(&val.hints, val.data.len()).expect("hints are valid");
+        Program {
+            shared_program_data: Arc::new(SharedProgramData {
+                data: val.data,
+                hints_collection,
+                main: val.main,
+                start: val.start,
+                end: val.end,
+                error_message_attributes: val.error_message_attributes,
+                instruction_locations: val.instruction_locations,
+                identifiers: val.identifiers,
+                reference_manager: Program::get_reference_list(&val.reference_manager),
+            }),
+            constants: val.constants,
+            builtins: val.builtins,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn random_felts_differ() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let a = Felt252::random(&mut rng);
+        let b = Felt252::random(&mut rng);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn random_in_segment_is_in_bounds() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let addr = Relocatable::random_in_segment(&mut rng, 3, 10);
+            assert_eq!(addr.segment_index, 3);
+            assert!(addr.offset < 10);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn memory_macro_allocates_segments() {
+        let memory = memory![((2, 1), 5), ((-1, 0), (2, 1))];
+        assert_eq!(memory.data.len(), 3);
+        assert_eq!(memory.temp_data.len(), 1);
+        assert_eq!(
+            memory.get(&relocatable!(2, 1)).unwrap().as_ref(),
+            &mayberelocatable!(5)
+        );
+        assert_eq!(
+            memory.get(&relocatable!(-1, 0)).unwrap().as_ref(),
+            &mayberelocatable!(2, 1)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn memory_macro_inconsistent_write() {
+        memory![((1, 0), 5), ((1, 0), 6)];
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn ids_data_macro() {
+        let ids_data = ids_data!["a", "b"];
+        assert_eq!(ids_data.len(), 2);
+        assert_eq!(ids_data["a"], references!(2)[&0]);
+        assert_eq!(ids_data["b"], references!(2)[&1]);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_builder() {
+        let program = ProgramBuilder {
+            data: vec![mayberelocatable!(1), mayberelocatable!(2)],
+            main: Some(1),
+            builtins: vec![BuiltinName::output],
+            ..Default::default()
+        }
+        .build();
+        assert_eq!(program.shared_program_data.main, Some(1));
+        assert_eq!(program.shared_program_data.data.len(), 2);
+        assert_eq!(program.builtins, vec![BuiltinName::output]);
+    }
+}
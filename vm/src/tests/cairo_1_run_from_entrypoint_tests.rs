@@ -103,7 +103,7 @@ fn factorial_50() {
     run_cairo_1_entrypoint(
         program_data.as_slice(),
         0,
-        &[50.into()],
+        &[mayberelocatable!(50)],
         &[Felt252::from_dec_str(
             "30414093201713378043612608166064768844377641568960512000000000000",
         )
@@ -117,7 +117,7 @@ fn factorial_2000() {
     run_cairo_1_entrypoint(
         program_data.as_slice(),
         0,
-        &[2000.into()],
+        &[mayberelocatable!(2000)],
         &[Felt252::from_dec_str(
             "2570376556569900799903105814841036176886569861654260254942280653735904624674",
         )
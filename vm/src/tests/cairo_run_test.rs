@@ -897,6 +897,13 @@ fn cairo_run_ec_negate() {
     run_program_simple(program_data.as_slice());
 }
 
+#[test]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+fn cairo_run_ec_negate_secp256r1() {
+    let program_data = include_bytes!("../../../cairo_programs/ec_negate_secp256r1.json");
+    run_program_simple(program_data.as_slice());
+}
+
 #[test]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
 fn cairo_run_compute_slope_v2_test() {
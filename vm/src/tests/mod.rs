@@ -8,6 +8,7 @@ use crate::Felt252;
 #[cfg(feature = "cairo-1-hints")]
 use crate::{
     hint_processor::cairo_1_hint_processor::hint_processor::Cairo1HintProcessor,
+    mayberelocatable,
     serde::deserialize_program::BuiltinName,
     types::relocatable::MaybeRelocatable,
     vm::{
@@ -149,16 +150,23 @@ fn run_cairo_1_entrypoint(
     // Other args
 
     // Load builtin costs
-    let builtin_costs: Vec<MaybeRelocatable> =
-        vec![0.into(), 0.into(), 0.into(), 0.into(), 0.into()];
+    let builtin_costs: Vec<MaybeRelocatable> = vec![
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+    ];
     let builtin_costs_ptr = vm.add_memory_segment();
     vm.load_data(builtin_costs_ptr, &builtin_costs).unwrap();
 
     // Load extra data
     let core_program_end_ptr =
         (runner.program_base.unwrap() + runner.program.shared_program_data.data.len()).unwrap();
-    let program_extra_data: Vec<MaybeRelocatable> =
-        vec![0x208B7FFF7FFF7FFE.into(), builtin_costs_ptr.into()];
+    let program_extra_data: Vec<MaybeRelocatable> = vec![
+        mayberelocatable!(0x208B7FFF7FFF7FFE),
+        builtin_costs_ptr.into(),
+    ];
     vm.load_data(core_program_end_ptr, &program_extra_data)
         .unwrap();
 
@@ -251,16 +259,23 @@ fn run_cairo_1_entrypoint_with_run_resources(
     // Other args
 
     // Load builtin costs
-    let builtin_costs: Vec<MaybeRelocatable> =
-        vec![0.into(), 0.into(), 0.into(), 0.into(), 0.into()];
+    let builtin_costs: Vec<MaybeRelocatable> = vec![
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+        mayberelocatable!(0),
+    ];
     let builtin_costs_ptr = vm.add_memory_segment();
     vm.load_data(builtin_costs_ptr, &builtin_costs).unwrap();
 
     // Load extra data
     let core_program_end_ptr =
         (runner.program_base.unwrap() + runner.program.shared_program_data.data.len()).unwrap();
-    let program_extra_data: Vec<MaybeRelocatable> =
-        vec![0x208B7FFF7FFF7FFE.into(), builtin_costs_ptr.into()];
+    let program_extra_data: Vec<MaybeRelocatable> = vec![
+        mayberelocatable!(0x208B7FFF7FFF7FFE),
+        builtin_costs_ptr.into(),
+    ];
     vm.load_data(core_program_end_ptr, &program_extra_data)
         .unwrap();
 
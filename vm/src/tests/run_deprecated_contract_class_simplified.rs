@@ -106,7 +106,7 @@ impl HintProcessorLogic for SimplifiedOsHintProcessor {
             hint_data,
             constants,
         ) {
-            Err(HintError::UnknownHint(_)) => {}
+            Err(HintError::UnknownHint(_) | HintError::HintFeatureDisabled(_)) => {}
             res => return res,
         }
         // Execute os-specific hints
@@ -302,6 +302,7 @@ pub fn vm_load_program(
         &hint_ap_tracking_data,
         &reference_ids,
         &references,
+        &[],
     )?;
     // Create the hint extension
     // As the hint from the compiled constract has offset 0, the hint pc will be equal to the loaded contract's program base:
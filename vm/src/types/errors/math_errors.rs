@@ -32,6 +32,8 @@ pub enum MathError {
     // Relocatable Operations
     #[error("Cant convert felt: {0} to Relocatable")]
     Felt252ToRelocatable(Box<Felt252>),
+    #[error("Cant convert relocatable: {0} to Felt252")]
+    RelocatableToFelt252(Box<Relocatable>),
     #[error("Operation failed: {} - {}, offsets cant be negative", (*.0).0, (*.0).1)]
     RelocatableSubFelt252NegOffset(Box<(Relocatable, Felt252)>),
     #[error("Operation failed: {} - {}, offsets cant be negative", (*.0).0, (*.0).1)]
@@ -65,6 +67,8 @@ pub enum MathError {
         "Operation failed: divmod({}, {}, {}), igcdex({}, {}) != 1 ", (*.0).0, (*.0).1, (*.0).2, (*.0).1, (*.0).2
     )]
     DivModIgcdexNotZero(Box<(BigInt, BigInt, BigInt)>),
+    #[error("Can't compute the doubling slope of point ({}, {}): its y coordinate is 0 modulo {}", (*.0).0, (*.0).1, (*.0).2)]
+    EcDoubleSlopeZeroY(Box<(BigInt, BigInt, BigInt)>),
 }
 
 #[cfg(test)]
@@ -1,5 +1,10 @@
+// The `(*.0).0` syntax of thiserror falsely triggers this clippy warning
+#![allow(clippy::explicit_auto_deref)]
+
+use crate::program_hash::ProgramHashError;
 use crate::stdlib::prelude::*;
 use crate::utils::PRIME_STR;
+use crate::Felt252;
 use thiserror_no_std::Error;
 
 #[derive(Debug, Error)]
@@ -19,6 +24,12 @@ pub enum ProgramError {
     StrippedProgramNoMain,
     #[error("Hint PC ({0}) is greater or equal to program length ({1})")]
     InvalidHintPc(usize, usize),
+    #[error("Failed to compile the program: {0}")]
+    Compiler(String),
+    #[error(transparent)]
+    ProgramHash(#[from] ProgramHashError),
+    #[error("Program hash mismatch: expected {}, computed {}", (*.0).0, (*.0).1)]
+    ProgramHashMismatch(Box<(Felt252, Felt252)>),
 }
 
 #[cfg(test)]
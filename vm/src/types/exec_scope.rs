@@ -1,9 +1,24 @@
-use crate::stdlib::{any::Any, cell::RefCell, collections::HashMap, prelude::*, rc::Rc};
+use crate::stdlib::{
+    any::Any, cell::RefCell, collections::HashMap, fmt::Display, prelude::*, rc::Rc,
+};
 use crate::{
     any_box,
     hint_processor::builtin_hint_processor::dict_manager::DictManager,
+    types::relocatable::{MaybeRelocatable, Relocatable},
     vm::errors::{exec_scope_errors::ExecScopeError, hint_errors::HintError},
+    Felt252,
 };
+use num_bigint::{BigInt, BigUint};
+
+/// Best-effort description of a scope variable, as returned by [ExecutionScopes::describe].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeVariableDescription {
+    pub name: String,
+    /// Name of the variable's type, `None` if it isn't one of the types `describe` knows about.
+    pub type_name: Option<&'static str>,
+    /// The variable's value formatted as a string, `None` if its type is unknown.
+    pub value: Option<String>,
+}
 
 #[derive(Debug)]
 pub struct ExecutionScopes {
@@ -30,6 +45,16 @@ impl ExecutionScopes {
         Ok(())
     }
 
+    ///Drops every scope above the main one and clears the variables of the main scope, so that
+    ///hints run afterwards don't see values left by previous ones
+    pub fn reset(&mut self) {
+        self.data.truncate(1);
+        match self.data.first_mut() {
+            Some(main_scope) => main_scope.clear(),
+            None => self.data.push(HashMap::new()),
+        }
+    }
+
     ///Returns a mutable reference to the dictionary containing the variables present in the current scope
     pub fn get_local_variables_mut(
         &mut self,
@@ -182,6 +207,71 @@ impl ExecutionScopes {
     pub fn insert_value<T: 'static>(&mut self, name: &str, value: T) {
         self.assign_or_update_variable(name, any_box!(value));
     }
+
+    ///Returns a description of the variables present in the current scope, sorted by name.
+    ///Only integers, strings, addresses and lists or dicts of integers can be described, the type
+    ///and value of other variables are left empty.
+    pub fn describe(&self) -> Result<Vec<ScopeVariableDescription>, HintError> {
+        let mut variables = self
+            .get_local_variables()?
+            .iter()
+            .map(|(name, value)| {
+                let (type_name, value) = describe_value(value.as_ref()).unzip();
+                ScopeVariableDescription {
+                    name: name.clone(),
+                    type_name,
+                    value,
+                }
+            })
+            .collect::<Vec<_>>();
+        variables.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(variables)
+    }
+}
+
+fn describe_value(value: &dyn Any) -> Option<(&'static str, String)> {
+    fn list<T: Display>(values: &[T]) -> String {
+        let values = values.iter().map(T::to_string).collect::<Vec<_>>();
+        format!("[{}]", values.join(", "))
+    }
+    fn dict<K: Display + Ord, V: Display>(dict: &HashMap<K, V>) -> String {
+        let mut entries = dict.iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| format!("{key}: {value}"))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", entries.join(", "))
+    }
+    macro_rules! describe {
+        ($($type:ty => $describe:expr),* $(,)?) => {
+            $(
+                if let Some(value) = value.downcast_ref::<$type>() {
+                    return Some((stringify!($type), $describe(value)));
+                }
+            )*
+        };
+    }
+    describe!(
+        Felt252 => Felt252::to_string,
+        BigInt => BigInt::to_string,
+        BigUint => BigUint::to_string,
+        usize => usize::to_string,
+        u64 => u64::to_string,
+        i64 => i64::to_string,
+        bool => bool::to_string,
+        String => String::clone,
+        Relocatable => Relocatable::to_string,
+        MaybeRelocatable => MaybeRelocatable::to_string,
+        Vec<Felt252> => list,
+        Vec<BigInt> => list,
+        Vec<BigUint> => list,
+        Vec<usize> => list,
+        Vec<MaybeRelocatable> => list,
+        HashMap<Felt252, Felt252> => dict,
+        HashMap<BigInt, BigInt> => dict,
+    );
+    None
 }
 
 impl Default for ExecutionScopes {
@@ -475,4 +565,104 @@ mod tests {
         assert!(scopes.get_any_boxed_mut("no_variable").is_err());
         assert!(scopes.get_any_boxed_ref("no_variable").is_err());
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn describe_current_scope() {
+        struct Opaque;
+
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("n", Felt252::from(7));
+        scopes.insert_value("list", vec![BigInt::from(1), BigInt::from(-2)]);
+        scopes.insert_value("opaque", Opaque);
+        scopes.insert_value(
+            "dict",
+            HashMap::from([
+                (Felt252::from(2), Felt252::from(20)),
+                (Felt252::from(1), Felt252::from(10)),
+            ]),
+        );
+
+        assert_eq!(
+            scopes.describe().unwrap(),
+            vec![
+                ScopeVariableDescription {
+                    name: "dict".to_string(),
+                    type_name: Some("HashMap<Felt252, Felt252>"),
+                    value: Some("{1: 10, 2: 20}".to_string()),
+                },
+                ScopeVariableDescription {
+                    name: "list".to_string(),
+                    type_name: Some("Vec<BigInt>"),
+                    value: Some("[1, -2]".to_string()),
+                },
+                ScopeVariableDescription {
+                    name: "n".to_string(),
+                    type_name: Some("Felt252"),
+                    value: Some("7".to_string()),
+                },
+                ScopeVariableDescription {
+                    name: "opaque".to_string(),
+                    type_name: None,
+                    value: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn describe_only_current_scope() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("a", 1_usize);
+        scopes.enter_scope(HashMap::from([(
+            "b".to_string(),
+            any_box!(Relocatable::from((1, 2))),
+        )]));
+
+        assert_eq!(
+            scopes.describe().unwrap(),
+            vec![ScopeVariableDescription {
+                name: "b".to_string(),
+                type_name: Some("Relocatable"),
+                value: Some("1:2".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn reset_drops_scopes_and_variables() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("slope", BigInt::from(3));
+        scopes.enter_scope(HashMap::from([(
+            "x".to_string(),
+            any_box!(BigInt::from(1)),
+        )]));
+        scopes.enter_scope(HashMap::from([(
+            "y".to_string(),
+            any_box!(BigInt::from(2)),
+        )]));
+
+        scopes.reset();
+
+        assert_eq!(scopes.data.len(), 1);
+        assert!(scopes.get_local_variables().unwrap().is_empty());
+        assert_matches!(
+            scopes.get::<BigInt>("slope"),
+            Err(HintError::VariableNotInScopeError(_))
+        );
+        assert_matches!(scopes.exit_scope(), Err(ExecScopeError::ExitMainScopeError));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn reset_restores_main_scope() {
+        let mut scopes = ExecutionScopes { data: Vec::new() };
+
+        scopes.reset();
+
+        assert_eq!(scopes.data.len(), 1);
+        assert!(scopes.get_local_variables().unwrap().is_empty());
+    }
 }
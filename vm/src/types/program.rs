@@ -1,4 +1,5 @@
 use crate::{
+    program_hash::compute_program_hash_chain,
     serde::{
         deserialize_program::{parse_program_json, ProgramJson},
         serialize_program::ProgramSerializer,
@@ -165,6 +166,35 @@ impl HintsCollection {
         self.hints.iter()
     }
 
+    /// Iterates over the hints grouped by the pc (offset within the program) they belong to.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[HintParams])> {
+        #[cfg(not(feature = "extensive_hints"))]
+        let iter = self
+            .hints_ranges
+            .iter()
+            .enumerate()
+            .filter_map(|(pc, range)| {
+                range.and_then(|(start, len)| {
+                    let end = start + len.get();
+                    if end <= self.hints.len() {
+                        Some((pc, &self.hints[start..end]))
+                    } else {
+                        None
+                    }
+                })
+            });
+        #[cfg(feature = "extensive_hints")]
+        let iter = self.hints_ranges.iter().filter_map(|(pc, (start, len))| {
+            let end = start + len.get();
+            if end <= self.hints.len() {
+                Some((pc.offset, &self.hints[*start..end]))
+            } else {
+                None
+            }
+        });
+        iter
+    }
+
     #[cfg(not(feature = "extensive_hints"))]
     pub fn get_hint_range_for_pc(&self, pc: usize) -> Option<HintRange> {
         self.hints_ranges.get(pc).cloned()
@@ -324,6 +354,13 @@ impl Program {
         Some(relocated_instructions)
     }
 
+    /// Iterates over the program hints, grouped by the pc they are attached to.
+    /// Pcs are offsets within the program, so they need to be added to the program base
+    /// when the program is loaded at a base other than (0, 0).
+    pub fn iter_hints(&self) -> impl Iterator<Item = (usize, &[HintParams])> {
+        self.shared_program_data.hints_collection.iter()
+    }
+
     pub fn iter_identifiers(&self) -> impl Iterator<Item = (&str, &Identifier)> {
         self.shared_program_data
             .identifiers
@@ -366,6 +403,23 @@ impl Program {
                 constants.insert(key.clone(), value);
             }
         }
+        // Imported constants are aliases, store them under the importing module's path too
+        let aliases: Vec<_> = identifiers
+            .iter()
+            .filter(|(_, identifier)| identifier.type_.as_deref() == Some("alias"))
+            .filter_map(|(key, identifier)| {
+                let mut destination = identifier.destination.as_ref()?;
+                // Aliases can point to other aliases, bound the walk in case of a cycle
+                for _ in 0..identifiers.len() {
+                    if let Some(value) = constants.get(destination) {
+                        return Some((key.clone(), *value));
+                    }
+                    destination = identifiers.get(destination)?.destination.as_ref()?;
+                }
+                None
+            })
+            .collect();
+        constants.extend(aliases);
         Ok(constants)
     }
 
@@ -384,6 +438,15 @@ impl Program {
         })
     }
 
+    /// Computes the hash the bootloader verifies when running this program as a task, see
+    /// [`compute_program_hash_chain`].
+    pub fn compute_hash(&self, bootloader_version: usize) -> Result<Felt252, ProgramError> {
+        Ok(compute_program_hash_chain(
+            &self.get_stripped_program()?,
+            bootloader_version,
+        )?)
+    }
+
     pub fn serialize(&self) -> Result<Vec<u8>, ProgramError> {
         let program_serializer: ProgramSerializer = ProgramSerializer::from(self);
         let bytes: Vec<u8> = serde_json::to_vec(&program_serializer)?;
@@ -458,37 +521,6 @@ impl TryFrom<CasmContractClass> for Program {
     }
 }
 
-#[cfg(test)]
-impl HintsCollection {
-    pub fn iter(&self) -> impl Iterator<Item = (usize, &[HintParams])> {
-        #[cfg(not(feature = "extensive_hints"))]
-        let iter = self
-            .hints_ranges
-            .iter()
-            .enumerate()
-            .filter_map(|(pc, range)| {
-                range.and_then(|(start, len)| {
-                    let end = start + len.get();
-                    if end <= self.hints.len() {
-                        Some((pc, &self.hints[start..end]))
-                    } else {
-                        None
-                    }
-                })
-            });
-        #[cfg(feature = "extensive_hints")]
-        let iter = self.hints_ranges.iter().filter_map(|(pc, (start, len))| {
-            let end = start + len.get();
-            if end <= self.hints.len() {
-                Some((pc.offset, &self.hints[*start..end]))
-            } else {
-                None
-            }
-        });
-        iter
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use core::ops::Neg;
@@ -594,6 +626,48 @@ mod tests {
             .is_empty());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn program_iter_hints() {
+        let str_to_hint_param = |s: &str| HintParams {
+            code: s.to_string(),
+            accessible_scopes: vec![],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids: HashMap::new(),
+            },
+        };
+        let hints = HashMap::from([
+            (5, vec![str_to_hint_param("c"), str_to_hint_param("d")]),
+            (1, vec![str_to_hint_param("a")]),
+            (4, vec![str_to_hint_param("b")]),
+        ]);
+        let program = Program::new(
+            Vec::new(),
+            vec![mayberelocatable!(0); 6],
+            None,
+            hints,
+            ReferenceManager {
+                references: Vec::new(),
+            },
+            HashMap::new(),
+            Vec::new(),
+            None,
+        )
+        .unwrap();
+
+        let mut hints: Vec<(usize, Vec<&str>)> = program
+            .iter_hints()
+            .map(|(pc, hints)| (pc, hints.iter().map(|h| h.code.as_str()).collect()))
+            .collect();
+        hints.sort();
+        assert_eq!(
+            hints,
+            vec![(1, vec!["a"]), (4, vec!["b"]), (5, vec!["c", "d"])]
+        );
+        assert_eq!(Program::default().iter_hints().count(), 0);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn new_program_with_hints() {
@@ -706,6 +780,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -718,6 +793,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -759,6 +835,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -771,6 +848,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -783,6 +861,55 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn extract_constants_follows_aliases() {
+        let identifier =
+            |type_: &str, value: Option<Felt252>, destination: Option<&str>| Identifier {
+                pc: None,
+                type_: Some(type_.to_string()),
+                value,
+                full_name: None,
+                members: None,
+                cairo_type: None,
+                destination: destination.map(str::to_string),
+            };
+        let identifiers = HashMap::from([
+            (
+                "packed_keccak.BLOCK_SIZE".to_string(),
+                identifier("const", Some(Felt252::from(3)), None),
+            ),
+            (
+                "keccak.BLOCK_SIZE".to_string(),
+                identifier("alias", None, Some("packed_keccak.BLOCK_SIZE")),
+            ),
+            (
+                "__main__.BLOCK_SIZE".to_string(),
+                identifier("alias", None, Some("keccak.BLOCK_SIZE")),
+            ),
+            (
+                "__main__.keccak".to_string(),
+                identifier("alias", None, Some("keccak.keccak")),
+            ),
+            (
+                "__main__.cycle".to_string(),
+                identifier("alias", None, Some("__main__.cycle")),
+            ),
+        ]);
+
+        assert_eq!(
+            Program::extract_constants(&identifiers).unwrap(),
+            [
+                ("packed_keccak.BLOCK_SIZE", Felt252::from(3)),
+                ("keccak.BLOCK_SIZE", Felt252::from(3)),
+                ("__main__.BLOCK_SIZE", Felt252::from(3)),
+            ]
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect::<HashMap<_, _>>(),
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_prime() {
@@ -920,6 +1047,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -932,6 +1060,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1045,6 +1174,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1057,6 +1187,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1109,6 +1240,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1121,6 +1253,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1168,6 +1301,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1179,6 +1313,7 @@ mod tests {
                 full_name: Some("__main__.main.Args".to_string()),
                 members: Some(HashMap::new()),
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1190,6 +1325,7 @@ mod tests {
                 full_name: Some("__main__.main.ImplicitArgs".to_string()),
                 members: Some(HashMap::new()),
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1201,6 +1337,7 @@ mod tests {
                 full_name: Some("__main__.main.Return".to_string()),
                 members: Some(HashMap::new()),
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1212,6 +1349,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1267,6 +1405,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1278,6 +1417,7 @@ mod tests {
                 full_name: Some("__main__.main.Args".to_string()),
                 members: Some(HashMap::new()),
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1289,6 +1429,7 @@ mod tests {
                 full_name: Some("__main__.main.ImplicitArgs".to_string()),
                 members: Some(HashMap::new()),
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1300,6 +1441,7 @@ mod tests {
                 full_name: Some("__main__.main.Return".to_string()),
                 members: Some(HashMap::new()),
                 cairo_type: None,
+                destination: None,
             },
         );
         identifiers.insert(
@@ -1311,6 +1453,7 @@ mod tests {
                 full_name: None,
                 members: None,
                 cairo_type: None,
+                destination: None,
             },
         );
 
@@ -1365,10 +1508,7 @@ mod tests {
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn default_program() {
-        let hints_collection = HintsCollection {
-            hints: Vec::new(),
-            hints_ranges: Default::default(),
-        };
+        let hints_collection = HintsCollection::default();
 
         let shared_program_data = SharedProgramData {
             data: Vec::new(),
@@ -1415,4 +1555,44 @@ mod tests {
             Err(ProgramError::StrippedProgramNoMain)
         );
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_hash_matches_cairo_lang() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .unwrap();
+        // Computed with cairo-lang's `compute_program_hash_chain`
+        assert_eq!(
+            program.compute_hash(0).unwrap(),
+            felt_hex!("0x43b17e9592f33142246af4c06cd2b574b460dd1f718d76b51341175a62b220f")
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn compute_hash_depends_on_bootloader_version() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../cairo_programs/manually_compiled/hintless_loop.json"),
+            Some("main"),
+        )
+        .unwrap();
+        assert_ne!(
+            program.compute_hash(0).unwrap(),
+            program.compute_hash(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn compute_hash_no_main() {
+        let program_content =
+            include_bytes!("../../../cairo_programs/manually_compiled/hintless_loop.json");
+        let program = Program::from_bytes(program_content, None).unwrap();
+        assert_matches!(
+            program.compute_hash(0),
+            Err(ProgramError::StrippedProgramNoMain)
+        );
+    }
 }
@@ -53,18 +53,35 @@ impl From<(isize, usize)> for Relocatable {
     }
 }
 
+/// Builds a relocatable value from a `(segment_index, offset)` pair.
+///
+/// ```
+/// # use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
+/// let value = MaybeRelocatable::from((1, 5));
+/// assert_eq!(value.get_relocatable(), Some(Relocatable::from((1, 5))));
+/// ```
 impl From<(isize, usize)> for MaybeRelocatable {
     fn from(index_offset: (isize, usize)) -> Self {
         MaybeRelocatable::RelocatableValue(Relocatable::from(index_offset))
     }
 }
 
+/// ```
+/// # use cairo_vm::{types::relocatable::MaybeRelocatable, Felt252};
+/// let value = MaybeRelocatable::from(7_usize);
+/// assert_eq!(value.get_int(), Some(Felt252::from(7)));
+/// ```
 impl From<usize> for MaybeRelocatable {
     fn from(num: usize) -> Self {
         MaybeRelocatable::Int(Felt252::from(num))
     }
 }
 
+/// ```
+/// # use cairo_vm::{types::relocatable::MaybeRelocatable, Felt252};
+/// let value = MaybeRelocatable::from(Felt252::MAX);
+/// assert_eq!(value.get_int_ref(), Some(&Felt252::MAX));
+/// ```
 impl From<Felt252> for MaybeRelocatable {
     fn from(num: Felt252) -> Self {
         MaybeRelocatable::Int(num)
@@ -89,6 +106,11 @@ impl From<&Felt252> for MaybeRelocatable {
     }
 }
 
+/// ```
+/// # use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
+/// let ptr = Relocatable::from((2, 0));
+/// assert_eq!(MaybeRelocatable::from(ptr), MaybeRelocatable::RelocatableValue(ptr));
+/// ```
 impl From<Relocatable> for MaybeRelocatable {
     fn from(rel: Relocatable) -> Self {
         MaybeRelocatable::RelocatableValue(rel)
@@ -194,10 +216,10 @@ impl Sub<Relocatable> for Relocatable {
     }
 }
 
-impl TryInto<Relocatable> for MaybeRelocatable {
+impl TryFrom<MaybeRelocatable> for Relocatable {
     type Error = MemoryError;
-    fn try_into(self) -> Result<Relocatable, MemoryError> {
-        match self {
+    fn try_from(other: MaybeRelocatable) -> Result<Self, MemoryError> {
+        match other {
             MaybeRelocatable::RelocatableValue(rel) => Ok(rel),
             _ => Err(MemoryError::AddressNotRelocatable),
         }
@@ -346,6 +368,47 @@ impl MaybeRelocatable {
             MaybeRelocatable::Int(_) => None,
         }
     }
+
+    /// Returns a reference to the inner value if it is a Felt252, returns an error otherwise.
+    ///
+    /// ```
+    /// # use cairo_vm::{relocatable, types::{errors::math_errors::MathError, relocatable::MaybeRelocatable}, Felt252};
+    /// assert_eq!(MaybeRelocatable::from(Felt252::ONE).try_get_int_ref(), Ok(&Felt252::ONE));
+    /// assert_eq!(
+    ///     MaybeRelocatable::from((1, 5)).try_get_int_ref(),
+    ///     Err(MathError::RelocatableToFelt252(Box::new(relocatable!(1, 5))))
+    /// );
+    /// ```
+    pub fn try_get_int_ref(&self) -> Result<&Felt252, MathError> {
+        match self {
+            MaybeRelocatable::Int(num) => Ok(num),
+            MaybeRelocatable::RelocatableValue(rel) => {
+                Err(MathError::RelocatableToFelt252(Box::new(*rel)))
+            }
+        }
+    }
+
+    /// Returns the inner value if it is a Felt252, returns an error otherwise.
+    pub fn try_get_int(&self) -> Result<Felt252, MathError> {
+        self.try_get_int_ref().copied()
+    }
+
+    /// Returns the inner value if it is a Relocatable, returns an error otherwise.
+    ///
+    /// ```
+    /// # use cairo_vm::{relocatable, types::{errors::math_errors::MathError, relocatable::MaybeRelocatable}, Felt252};
+    /// assert_eq!(MaybeRelocatable::from((1, 5)).try_get_relocatable(), Ok(relocatable!(1, 5)));
+    /// assert_eq!(
+    ///     MaybeRelocatable::from(Felt252::ONE).try_get_relocatable(),
+    ///     Err(MathError::Felt252ToRelocatable(Box::new(Felt252::ONE)))
+    /// );
+    /// ```
+    pub fn try_get_relocatable(&self) -> Result<Relocatable, MathError> {
+        match self {
+            MaybeRelocatable::RelocatableValue(rel) => Ok(*rel),
+            MaybeRelocatable::Int(num) => Err(MathError::Felt252ToRelocatable(Box::new(*num))),
+        }
+    }
 }
 
 impl<'a> Add<usize> for &'a Relocatable {
@@ -398,8 +461,8 @@ pub fn relocate_address(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{mayberelocatable, relocatable};
     use crate::{felt_hex, felt_str};
-    use crate::{relocatable, utils::test_utils::mayberelocatable};
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -916,6 +979,44 @@ mod tests {
         assert_eq!(mayberelocatable!(3).get_relocatable(), None)
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_get_relocatable_test() {
+        assert_eq!(
+            mayberelocatable!(1, 2).try_get_relocatable(),
+            Ok(relocatable!(1, 2))
+        );
+        assert_eq!(
+            mayberelocatable!(3).try_get_relocatable(),
+            Err(MathError::Felt252ToRelocatable(Box::new(Felt252::from(3))))
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_get_int_test() {
+        assert_eq!(mayberelocatable!(3).try_get_int(), Ok(Felt252::from(3)));
+        assert_eq!(
+            mayberelocatable!(1, 2).try_get_int(),
+            Err(MathError::RelocatableToFelt252(Box::new(relocatable!(
+                1, 2
+            ))))
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn mayberelocatable_macro_keeps_wide_values() {
+        assert_eq!(
+            mayberelocatable!(u128::MAX),
+            MaybeRelocatable::from(Felt252::from(u128::MAX))
+        );
+        assert_eq!(
+            mayberelocatable!(-1_i128),
+            MaybeRelocatable::from(Felt252::MAX)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn relocatable_display() {
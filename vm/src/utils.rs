@@ -1,21 +1,54 @@
 use crate::stdlib::prelude::*;
 use crate::types::relocatable::Relocatable;
+use crate::vm::errors::memory_errors::MemoryError;
+use crate::Felt252;
 use lazy_static::lazy_static;
 use num_bigint::BigUint;
 use num_traits::Num;
 
 pub const PRIME_STR: &str = "0x800000000000011000000000000000000000000000000000000000000000001";
 
+/// Builds a [`Relocatable`] from its segment index and offset.
+///
+/// ```
+/// # use cairo_vm::{relocatable, types::relocatable::Relocatable};
+/// assert_eq!(relocatable!(1, 5), Relocatable::from((1, 5)));
+/// ```
 #[macro_export]
 macro_rules! relocatable {
     ($val1 : expr, $val2 : expr) => {
-        Relocatable {
+        $crate::types::relocatable::Relocatable {
             segment_index: $val1,
             offset: $val2,
         }
     };
 }
 
+/// Builds a [`MaybeRelocatable`](crate::types::relocatable::MaybeRelocatable), either a
+/// relocatable value from a segment index and an offset, or an integer value from a single
+/// (possibly negative) integer. Unsuffixed literals are read as `i128`, other integers keep their
+/// own type.
+///
+/// ```
+/// # use cairo_vm::{mayberelocatable, relocatable, types::relocatable::MaybeRelocatable, Felt252};
+/// assert_eq!(mayberelocatable!(1, 5), MaybeRelocatable::from(relocatable!(1, 5)));
+/// assert_eq!(mayberelocatable!(7), MaybeRelocatable::from(Felt252::from(7)));
+/// assert_eq!(mayberelocatable!(-1), MaybeRelocatable::from(Felt252::MAX));
+/// assert_eq!(mayberelocatable!(u128::MAX), MaybeRelocatable::from(Felt252::from(u128::MAX)));
+/// ```
+#[macro_export]
+macro_rules! mayberelocatable {
+    ($val1 : expr, $val2 : expr) => {
+        $crate::types::relocatable::MaybeRelocatable::from(($val1, $val2))
+    };
+    ($val1 : literal) => {
+        $crate::types::relocatable::MaybeRelocatable::from($crate::Felt252::from($val1 as i128))
+    };
+    ($val1 : expr) => {
+        $crate::types::relocatable::MaybeRelocatable::from($crate::Felt252::from($val1))
+    };
+}
+
 lazy_static! {
     pub static ref CAIRO_PRIME: BigUint = BigUint::from_str_radix(&PRIME_STR[2..], 16).unwrap();
 }
@@ -49,6 +82,15 @@ pub fn from_relocatable_to_indexes(relocatable: Relocatable) -> (usize, usize) {
     }
 }
 
+/// Encodes `value` as a Cairo short string: its ASCII bytes read as a big-endian felt.
+/// Fails if `value` is longer than 31 characters or contains non-ASCII characters.
+pub fn felt_from_short_string(value: &str) -> Result<Felt252, MemoryError> {
+    if value.len() > 31 || !value.is_ascii() {
+        return Err(MemoryError::InvalidShortString(value.into()));
+    }
+    Ok(Felt252::from_bytes_be_slice(value.as_bytes()))
+}
+
 #[cfg(test)]
 #[macro_use]
 pub mod test_utils {
@@ -122,7 +164,7 @@ pub mod test_utils {
         ($( (($si:expr, $off:expr), $val:tt) ),* $(,)? ) => {
             {
                 let mut segments = $crate::vm::vm_memory::memory_segments::MemorySegmentManager::new();
-                segments.memory = memory!($( (($si, $off), $val) ),*);
+                segments.memory = $crate::memory!($( (($si, $off), $val) ),*);
                 segments
             }
 
@@ -130,61 +172,27 @@ pub mod test_utils {
     }
     pub(crate) use segments;
 
-    macro_rules! memory {
-        ( $( (($si:expr, $off:expr), $val:tt) ),* ) => {
-            {
-                let mut memory = $crate::vm::vm_memory::memory::Memory::new();
-                memory_from_memory!(memory, ( $( (($si, $off), $val) ),* ));
-                memory
-            }
-        };
-    }
-    pub(crate) use memory;
-
-    macro_rules! memory_from_memory {
-        ($mem: expr, ( $( (($si:expr, $off:expr), $val:tt) ),* )) => {
-            {
-                $(
-                    memory_inner!($mem, ($si, $off), $val);
-                )*
-            }
+    macro_rules! memory_entries {
+        ($( (($si:expr, $off:expr), $val:tt) ),* $(,)? ) => {
+            vec![$(
+                (
+                    $crate::types::relocatable::Relocatable::from(($si, $off)),
+                    memory_entry_value!($val),
+                )
+            ),*]
         };
     }
-    pub(crate) use memory_from_memory;
+    pub(crate) use memory_entries;
 
-    macro_rules! memory_inner {
-        ($mem:expr, ($si:expr, $off:expr), ($sival:expr, $offval: expr)) => {
-            let (k, v) = (($si, $off).into(), &mayberelocatable!($sival, $offval));
-            let mut res = $mem.insert(k, v);
-            while matches!(
-                res,
-                Err($crate::vm::errors::memory_errors::MemoryError::UnallocatedSegment(_))
-            ) {
-                if $si < 0 {
-                    $mem.temp_data.push($crate::stdlib::vec::Vec::new())
-                } else {
-                    $mem.data.push($crate::stdlib::vec::Vec::new());
-                }
-                res = $mem.insert(k, v);
-            }
+    macro_rules! memory_entry_value {
+        (($sival:expr, $offval:expr)) => {
+            mayberelocatable!($sival, $offval)
         };
-        ($mem:expr, ($si:expr, $off:expr), $val:expr) => {
-            let (k, v) = (($si, $off).into(), &mayberelocatable!($val));
-            let mut res = $mem.insert(k, v);
-            while matches!(
-                res,
-                Err($crate::vm::errors::memory_errors::MemoryError::UnallocatedSegment(_))
-            ) {
-                if $si < 0 {
-                    $mem.temp_data.push($crate::stdlib::vec::Vec::new())
-                } else {
-                    $mem.data.push($crate::stdlib::vec::Vec::new());
-                }
-                res = $mem.insert(k, v);
-            }
+        ($val:expr) => {
+            mayberelocatable!($val)
         };
     }
-    pub(crate) use memory_inner;
+    pub(crate) use memory_entry_value;
 
     macro_rules! check_memory {
         ( $mem: expr, $( (($si:expr, $off:expr), $val:tt) ),* $(,)? ) => {
@@ -211,35 +219,21 @@ pub mod test_utils {
     }
     pub(crate) use check_memory_address;
 
-    macro_rules! mayberelocatable {
-        ($val1 : expr, $val2 : expr) => {
-            $crate::types::relocatable::MaybeRelocatable::from(($val1, $val2))
-        };
-        ($val1 : expr) => {
-            $crate::types::relocatable::MaybeRelocatable::from(crate::Felt252::from($val1 as i128))
-        };
-    }
-    pub(crate) use mayberelocatable;
-
-    macro_rules! references {
-        ($num: expr) => {{
-            let mut references = crate::stdlib::collections::HashMap::<usize, HintReference>::new();
-            for i in 0..$num {
-                references.insert(i as usize, HintReference::new_simple((i as i32 - $num)));
-            }
-            references
-        }};
-    }
-    pub(crate) use references;
+    pub(crate) use crate::mayberelocatable;
+    pub(crate) use crate::{ids_data, memory};
 
     macro_rules! vm_with_range_check {
         () => {{
-            let mut vm = VirtualMachine::new(false);
-            vm.builtin_runners = vec![
-                $crate::vm::runners::builtin_runner::RangeCheckBuiltinRunner::new(Some(8), 8, true)
+            $crate::vm::vm_core::VirtualMachineBuilder::default()
+                .with_builtin(
+                    $crate::vm::runners::builtin_runner::RangeCheckBuiltinRunner::new(
+                        Some(8),
+                        8,
+                        true,
+                    )
                     .into(),
-            ];
-            vm
+                )
+                .build()
         }};
     }
     pub(crate) use vm_with_range_check;
@@ -292,7 +286,7 @@ pub mod test_utils {
         }};
         ($($field:ident = $value:expr),* $(,)?) => {{
 
-            let program_flat = crate::utils::test_utils::ProgramFlat {
+            let program_flat = $crate::test_utils::ProgramBuilder {
                 $(
                     $field: $value,
                 )*
@@ -305,84 +299,18 @@ pub mod test_utils {
 
     pub(crate) use program;
 
-    pub(crate) struct ProgramFlat {
-        pub(crate) data: crate::utils::Vec<MaybeRelocatable>,
-        pub(crate) hints: crate::stdlib::collections::BTreeMap<
-            usize,
-            crate::utils::Vec<crate::serde::deserialize_program::HintParams>,
-        >,
-        pub(crate) main: Option<usize>,
-        //start and end labels will only be used in proof-mode
-        pub(crate) start: Option<usize>,
-        pub(crate) end: Option<usize>,
-        pub(crate) error_message_attributes:
-            crate::utils::Vec<crate::serde::deserialize_program::Attribute>,
-        pub(crate) instruction_locations: Option<
-            crate::stdlib::collections::HashMap<
-                usize,
-                crate::serde::deserialize_program::InstructionLocation,
-            >,
-        >,
-        pub(crate) identifiers: crate::stdlib::collections::HashMap<
-            crate::stdlib::string::String,
-            crate::serde::deserialize_program::Identifier,
-        >,
-        pub(crate) constants:
-            crate::stdlib::collections::HashMap<crate::stdlib::string::String, crate::Felt252>,
-        pub(crate) builtins: crate::utils::Vec<crate::serde::deserialize_program::BuiltinName>,
-        pub(crate) reference_manager: crate::serde::deserialize_program::ReferenceManager,
-    }
-
-    impl Default for ProgramFlat {
-        fn default() -> Self {
-            Self {
-                data: Default::default(),
-                hints: Default::default(),
-                main: Default::default(),
-                start: Default::default(),
-                end: Default::default(),
-                error_message_attributes: Default::default(),
-                instruction_locations: Default::default(),
-                identifiers: Default::default(),
-                constants: Default::default(),
-                builtins: Default::default(),
-                reference_manager: crate::serde::deserialize_program::ReferenceManager {
-                    references: crate::utils::Vec::new(),
-                },
-            }
-        }
-    }
-
-    impl From<ProgramFlat> for Program {
-        fn from(val: ProgramFlat) -> Self {
-            // NOTE: panics if hints have PCs higher than the program length
-            let hints_collection =
-                HintsCollection::new(&val.hints, val.data.len()).expect("hints are valid");
-            Program {
-                shared_program_data: Arc::new(SharedProgramData {
-                    data: val.data,
-                    hints_collection,
-                    main: val.main,
-                    start: val.start,
-                    end: val.end,
-                    error_message_attributes: val.error_message_attributes,
-                    instruction_locations: val.instruction_locations,
-                    identifiers: val.identifiers,
-                    reference_manager: Program::get_reference_list(&val.reference_manager),
-                }),
-                constants: val.constants,
-                builtins: val.builtins,
-            }
-        }
-    }
-
     macro_rules! vm {
         () => {{
-            VirtualMachine::new(false)
+            $crate::vm::vm_core::VirtualMachineBuilder::default().build()
         }};
 
         ($use_trace:expr) => {{
-            VirtualMachine::new($use_trace)
+            let builder = $crate::vm::vm_core::VirtualMachineBuilder::default();
+            if $use_trace {
+                builder.with_trace_enabled().build()
+            } else {
+                builder.build()
+            }
         }};
     }
     pub(crate) use vm;
@@ -396,21 +324,6 @@ pub mod test_utils {
     }
     pub(crate) use run_context;
 
-    macro_rules! ids_data {
-        ( $( $name: expr ),* ) => {
-            {
-                let ids_names = vec![$( $name ),*];
-                let references = references!(ids_names.len() as i32);
-                let mut ids_data = crate::stdlib::collections::HashMap::<crate::stdlib::string::String, HintReference>::new();
-                for (i, name) in ids_names.iter().enumerate() {
-                    ids_data.insert(crate::stdlib::string::ToString::to_string(name), references.get(&i).unwrap().clone());
-                }
-                ids_data
-            }
-        };
-    }
-    pub(crate) use ids_data;
-
     macro_rules! non_continuous_ids_data {
         ( $( ($name: expr, $offset:expr) ),* $(,)? ) => {
             {
@@ -435,6 +348,32 @@ pub mod test_utils {
         }
     }
 
+    /// Custom builtin with 2 cells per instance, where the second cell echoes the first one
+    #[derive(Debug)]
+    pub(crate) struct EchoBuiltin;
+
+    impl crate::vm::runners::builtin_runner::BuiltinRunnerExt for EchoBuiltin {
+        fn cells_per_instance(&self) -> u32 {
+            2
+        }
+
+        fn n_input_cells(&self) -> u32 {
+            1
+        }
+
+        fn deduce_memory_cell(
+            &self,
+            address: crate::types::relocatable::Relocatable,
+            memory: &crate::vm::vm_memory::memory::Memory,
+        ) -> Result<Option<MaybeRelocatable>, crate::vm::errors::runner_errors::RunnerError>
+        {
+            if address.offset % 2 == 0 {
+                return Ok(None);
+            }
+            Ok(memory.get(&(address - 1)?).map(|value| value.into_owned()))
+        }
+    }
+
     macro_rules! exec_scopes_ref {
         () => {
             &mut ExecutionScopes::new()
@@ -443,6 +382,14 @@ pub mod test_utils {
     pub(crate) use exec_scopes_ref;
 
     macro_rules! run_hint {
+        ($vm:expr, $ids_data:expr, $hint_code:expr, $exec_scopes:expr, $constants:expr, $accessible_scopes:expr) => {{
+            let hint_data = HintProcessorData {
+                accessible_scopes: $accessible_scopes,
+                ..HintProcessorData::new_default($hint_code.to_string(), $ids_data)
+            };
+            let mut hint_processor = BuiltinHintProcessor::new_empty();
+            hint_processor.execute_hint(&mut $vm, $exec_scopes, &any_box!(hint_data), $constants)
+        }};
         ($vm:expr, $ids_data:expr, $hint_code:expr, $exec_scopes:expr, $constants:expr) => {{
             let hint_data = HintProcessorData::new_default($hint_code.to_string(), $ids_data);
             let mut hint_processor = BuiltinHintProcessor::new_empty();
@@ -523,8 +470,8 @@ pub mod test_utils {
                         .trackers
                         .get_mut(&$tracker_num)
                         .unwrap()
-                        .get_value(&MaybeRelocatable::from($key)),
-                    Ok(x) if x == &MaybeRelocatable::from($val)
+                        .get_value(&mayberelocatable!($key)),
+                    Ok(x) if x == &mayberelocatable!($val)
                 ));
             *
         };
@@ -552,7 +499,7 @@ pub mod test_utils {
         ($exec_scopes:expr, $tracker_num:expr, $( ($key:expr, $val:expr )),* ) => {
             let mut tracker = DictTracker::new_empty(relocatable!($tracker_num, 0));
             $(
-            tracker.insert_value(&MaybeRelocatable::from($key), &MaybeRelocatable::from($val));
+            tracker.insert_value(&mayberelocatable!($key), &mayberelocatable!($val));
             )*
             let mut dict_manager = DictManager::new();
             dict_manager.trackers.insert(2, tracker);
@@ -570,16 +517,16 @@ pub mod test_utils {
 
     macro_rules! dict_manager_default {
         ($exec_scopes:expr, $tracker_num:expr,$default:expr, $( ($key:expr, $val:expr )),* ) => {
-            let mut tracker = DictTracker::new_default_dict(relocatable!($tracker_num, 0), &MaybeRelocatable::from($default), None);
+            let mut tracker = DictTracker::new_default_dict(relocatable!($tracker_num, 0), &mayberelocatable!($default), None);
             $(
-            tracker.insert_value(&MaybeRelocatable::from($key), &MaybeRelocatable::from($val));
+            tracker.insert_value(&mayberelocatable!($key), &mayberelocatable!($val));
             )*
             let mut dict_manager = DictManager::new();
             dict_manager.trackers.insert(2, tracker);
             $exec_scopes.insert_value("dict_manager", crate::stdlib::rc::Rc::new(core::cell::RefCell::new(dict_manager)))
         };
         ($exec_scopes:expr, $tracker_num:expr,$default:expr) => {
-            let tracker = DictTracker::new_default_dict(relocatable!($tracker_num, 0), &MaybeRelocatable::from($default), None);
+            let tracker = DictTracker::new_default_dict(relocatable!($tracker_num, 0), &mayberelocatable!($default), None);
             let mut dict_manager = DictManager::new();
             dict_manager.trackers.insert(2, tracker);
             $exec_scopes.insert_value("dict_manager", crate::stdlib::rc::Rc::new(core::cell::RefCell::new(dict_manager)))
@@ -604,6 +551,29 @@ pub mod test_utils {
     }
     pub(crate) use vec_data_inner;
 
+    /// Splits the packed x and y coordinates of a secp point into limbs and writes them as two
+    /// consecutive BigInt3s starting at `base`, allocating segments as needed.
+    #[cfg(feature = "hints-secp")]
+    pub(crate) fn pack_into_memory(
+        memory: &mut crate::vm::vm_memory::memory::Memory,
+        base: (isize, usize),
+        (x_packed, y_packed): (&num_bigint::BigUint, &num_bigint::BigUint),
+    ) {
+        use crate::hint_processor::builtin_hint_processor::secp::secp_utils::bigint3_split;
+
+        let limbs = bigint3_split(x_packed)
+            .unwrap()
+            .into_iter()
+            .chain(bigint3_split(y_packed).unwrap());
+        for (i, limb) in limbs.enumerate() {
+            let (k, v) = (
+                (base.0, base.1 + i).into(),
+                &MaybeRelocatable::from(crate::Felt252::from(&limb)),
+            );
+            crate::test_utils::insert_allocating(memory, k, v);
+        }
+    }
+
     pub fn check_scope_value<T: core::fmt::Debug + core::cmp::PartialEq + 'static>(
         scopes: &ExecutionScopes,
         name: &str,
@@ -630,8 +600,9 @@ mod test {
         serde::deserialize_program::{BuiltinName, ReferenceManager},
         types::{exec_scope::ExecutionScopes, program::Program, relocatable::MaybeRelocatable},
         utils::test_utils::*,
-        vm::{trace::trace_entry::TraceEntry, vm_core::VirtualMachine, vm_memory::memory::Memory},
+        vm::{trace::trace_entry::TraceEntry, vm_memory::memory::Memory},
     };
+    use assert_matches::assert_matches;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -846,7 +817,7 @@ mod test {
     #[should_panic]
     fn check_dictionary_fail() {
         let mut tracker = DictTracker::new_empty(relocatable!(2, 0));
-        tracker.insert_value(&MaybeRelocatable::from(5), &MaybeRelocatable::from(10));
+        tracker.insert_value(&mayberelocatable!(5), &mayberelocatable!(10));
         let mut dict_manager = DictManager::new();
         dict_manager.trackers.insert(2, tracker);
         let mut exec_scopes = ExecutionScopes::new();
@@ -937,6 +908,40 @@ mod test {
         assert_eq!((0, 5), from_relocatable_to_indexes(reloc_3));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_from_short_string_ok() {
+        // Values extracted from cairo book example
+        assert_eq!(
+            felt_from_short_string("Hello, Scarb!").unwrap(),
+            Felt252::from(5735816763073854913753904210465_u128)
+        );
+        assert_eq!(felt_from_short_string("").unwrap(), Felt252::ZERO);
+        assert_eq!(
+            felt_from_short_string(&"a".repeat(31)).unwrap(),
+            Felt252::from_bytes_be_slice(&[b'a'; 31])
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_from_short_string_too_long() {
+        let value = "a".repeat(32);
+        assert_matches!(
+            felt_from_short_string(&value),
+            Err(MemoryError::InvalidShortString(s)) if *s == value
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn felt_from_short_string_non_ascii() {
+        assert_matches!(
+            felt_from_short_string("ñ"),
+            Err(MemoryError::InvalidShortString(s)) if &*s == "ñ"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn program_macro() {
@@ -8,6 +8,7 @@ use thiserror_no_std::Error;
 use crate::Felt252;
 use num_bigint::{BigInt, BigUint};
 
+use crate::program_hash::ProgramHashError;
 use crate::types::{
     errors::math_errors::MathError,
     relocatable::{MaybeRelocatable, Relocatable},
@@ -28,6 +29,8 @@ pub enum HintError {
     Memory(#[from] MemoryError),
     #[error(transparent)]
     Math(#[from] MathError),
+    #[error(transparent)]
+    ProgramHash(#[from] ProgramHashError),
     #[error("HintProcessor failed retrieve the compiled data necessary for hint execution")]
     WrongHintData,
     #[error("Unknown identifier {0}")]
@@ -46,6 +49,8 @@ pub enum HintError {
     CustomHint(Box<str>),
     #[error("Missing constant: {0}")]
     MissingConstant(Box<&'static str>),
+    #[error("Constant {} is ambiguous, candidates: {}", (*.0).0, (*.0).1.join(", "))]
+    AmbiguousConstant(Box<(&'static str, Vec<String>)>),
     #[error("Fail to get constants for hint execution")]
     FailedToGetConstant,
     #[error("Arc too big, {} must be <= {} and {} <= {}", (*.0).0, (*.0).1, (*.0).2, (*.0).3)]
@@ -78,8 +83,10 @@ pub enum HintError {
     KeyNotFound,
     #[error("AP tracking data is None; could not apply correction to address")]
     NoneApTrackingData,
-    #[error("Tracking groups should be the same, got {} and {}", (*.0).0, (*.0).0)]
+    #[error("Tracking groups should be the same, got reference group {} and hint group {}", (*.0).0, (*.0).1)]
     InvalidTrackingGroup(Box<(usize, usize)>),
+    #[error("Reference ap tracking offset {} is greater than the hint's ap tracking offset {}", (*.0).0, (*.0).1)]
+    InvalidTrackingOffset(Box<(usize, usize)>),
     #[error("Expected relocatable for ap, got {0}")]
     InvalidApValue(Box<MaybeRelocatable>),
     #[error("Dict Error: Tried to create a dict without an initial dict")]
@@ -134,6 +141,8 @@ pub enum HintError {
     SecpSplitOutOfRange(Box<BigUint>),
     #[error("verify_zero: Invalid input {0}")]
     SecpVerifyZero(Box<BigInt>),
+    #[error("Provided inverse {} is not the inverse of {} modulo SECP_P", (*.0).0, (*.0).1)]
+    SecpBadInverse(Box<(BigInt, BigInt)>),
     #[error("unsafe_keccak() can only be used with length<={}. Got: length={}", (*.0).0, (*.0).1)]
     KeccakMaxSize(Box<(Felt252, Felt252)>),
     #[error("Invalid word size: {0}")]
@@ -166,8 +175,16 @@ pub enum HintError {
     ValueOutsideValidRange(Box<Felt252>),
     #[error("Assertion failed, {}, is not less or equal to {}", (*.0).0, (*.0).1)]
     NonLeFelt252(Box<(Felt252, Felt252)>),
+    #[error("Assertion failed, {}, is not less or equal to {}", (*.0).0, (*.0).1)]
+    NonLeUint256(Box<(BigUint, BigUint)>),
+    #[error("Non-canonical limb: {} = {} is outside of the range [0, 2**128)", (*.0).0, (*.0).1)]
+    NonCanonicalLimb(Box<(String, Felt252)>),
+    #[error("Negative limb: {0} can't be packed as an unsigned value")]
+    NegativeLimb(Box<Felt252>),
     #[error("Unknown Hint: {0}")]
     UnknownHint(Box<str>),
+    #[error("Unknown Hint: {}\nThis is a builtin hint, enable the \"{}\" cairo-vm feature to run it", (*.0).0, (*.0).1)]
+    HintFeatureDisabled(Box<(Box<str>, &'static str)>),
     #[error("Signature hint must point to the signature builtin segment, not {0}.")]
     AddSignatureWrongEcdsaPtr(Box<Relocatable>),
     #[error("Signature hint must point to the public key cell, not {0}.")]
@@ -178,12 +195,20 @@ pub enum HintError {
     InvalidLenValue(Box<Felt252>),
     #[error("recover_y: {0} does not represent the x coordinate of a point on the curve.")]
     RecoverYPointNotOnCurve(Box<Felt252>),
+    #[error("get_point_from_x: x^3 + BETA = {0} is not a quadratic residue mod SECP_P, so x is not the x coordinate of a point on the curve.")]
+    GetPointFromXNotOnCurve(Box<BigInt>),
+    #[error(
+        "secp_jacobian_to_affine: The point at infinity (Z = 0) has no affine representation."
+    )]
+    JacobianPointAtInfinity,
     #[error("Invalid value for {}. Got: {}. Expected: {}", (*.0).0, (*.0).1, (*.0).2)]
     InvalidValue(Box<(&'static str, Felt252, Felt252)>),
     #[error("Attempt to subtract with overflow: ids.m - 1")]
     NPairBitsTooLowM,
     #[error("{0}")]
     SyscallError(Box<str>),
+    #[error("Unknown curve: {0}")]
+    UnknownCurve(Box<str>),
 }
 
 #[cfg(test)]
@@ -44,6 +44,8 @@ pub enum MemoryError {
     DuplicatedRelocation(isize),
     #[error("Segment effective sizes haven't been calculated.")]
     MissingSegmentUsedSizes,
+    #[error("Segment {0} has neither a used size nor a finalized size")]
+    SegmentNotFinalized(usize),
     #[error("Found a memory gap when calling get_continuous_range with base:{} and size: {}", (*.0).0, (*.0).1)]
     GetRangeMemoryGap(Box<(Relocatable, usize)>),
     #[error("Error calculating builtin memory units")]
@@ -84,6 +86,16 @@ pub enum MemoryError {
     SegmentHasMoreAccessedAddressesThanSize(Box<(usize, usize, usize)>),
     #[error("gen_arg: found argument of invalid type.")]
     GenArgInvalidType,
+    #[error("Invalid short string {0}: expected at most 31 ASCII characters")]
+    InvalidShortString(Box<str>),
+    #[error("Can't write to {}: segment size limit of {} cells exceeded", (*.0).0, (*.0).1)]
+    SegmentSizeLimitExceeded(Box<(Relocatable, usize)>),
+    #[error("Can't add a segment: segment count limit of {0} exceeded")]
+    SegmentCountLimitExceeded(usize),
+    #[error("Can't write to {}: memory limit of {} cells exceeded", (*.0).0, (*.0).1)]
+    TotalCellsLimitExceeded(Box<(Relocatable, usize)>),
+    #[error("Can't write to {0}: segment is read-only")]
+    WriteToReadOnlySegment(Box<Relocatable>),
     // Memory.get() errors
     #[error("Expected integer at address {0}")]
     ExpectedInteger(Box<Relocatable>),
@@ -91,6 +103,8 @@ pub enum MemoryError {
     ExpectedRelocatable(Box<Relocatable>),
     #[error("Unknown memory cell at address {0}")]
     UnknownMemoryCell(Box<Relocatable>),
+    #[error("Unknown output builtin cell at address {0}, output cells can't be read before they are written")]
+    UnknownMemoryCellOutputBuiltin(Box<Relocatable>),
     // SegmentArenaBuiltin
     #[error("segment_arena_builtin: assert used >= INITIAL_SEGMENT_SIZE")]
     InvalidUsedSizeSegmentArena,
@@ -98,6 +112,8 @@ pub enum MemoryError {
     VecCapacityExceeded,
     #[error("Memory wasn't relocated")]
     UnrelocatedMemory,
+    #[error("Address {0} doesn't belong to any relocated segment")]
+    AddressNotRelocated(usize),
     #[error("Malformed public memory")]
     MalformedPublicMemory,
 }
@@ -104,6 +104,10 @@ pub enum RunnerError {
     InvalidPoint,
     #[error("Page ({0}) is not on the expected segment {1}")]
     PageNotOnSegment(Relocatable, usize),
+    #[error("Builtin {0} is already present in the runner")]
+    DuplicateBuiltin(Box<&'static str>),
+    #[error("Custom builtin {0} must be added before initializing the segments")]
+    CustomBuiltinAfterSegments(Box<&'static str>),
 }
 
 #[cfg(test)]
@@ -99,6 +99,8 @@ pub enum VirtualMachineError {
     SliceToArrayError,
     #[error("Failed to compile hint: {0}")]
     CompileHintFail(Box<str>),
+    #[error("Reference {} is ambiguous in the hint's accessible scopes, candidates: {}", (*.0).0, (*.0).1.join(", "))]
+    AmbiguousReference(Box<(String, Vec<String>)>),
     #[error("op1_addr is Op1Addr.IMM, but no immediate was given")]
     NoImm,
     #[error("Execution reached the end of the program. Requested remaining steps: {0}.")]
@@ -129,6 +131,12 @@ pub enum VirtualMachineError {
     FailedToWriteOutput,
     #[error("Failed to find index {0} in the vm's relocation table")]
     RelocationNotFound(usize),
+    #[error("Execution panicked with data: {0:?}")]
+    PanicData(Vec<Felt252>),
+    #[error("Invalid retdata pointers: start {}, end {}", (*.0).0, (*.0).1)]
+    InvalidRetdataPointers(Box<(Relocatable, Relocatable)>),
+    #[error("Can't compute the slope of the line through two equal points, point0 at {0}")]
+    PointsAreEqual(Box<Relocatable>),
 }
 
 #[cfg(test)]
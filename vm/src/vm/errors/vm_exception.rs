@@ -24,6 +24,7 @@ pub struct VmException {
     pub inner_exc: VirtualMachineError,
     pub error_attr_value: Option<String>,
     pub traceback: Option<String>,
+    pub recent_steps: Option<String>,
 }
 
 impl VmException {
@@ -53,6 +54,7 @@ impl VmException {
             inner_exc: error,
             error_attr_value,
             traceback: get_traceback(vm, runner),
+            recent_steps: get_recent_steps(vm),
         }
     }
 }
@@ -124,6 +126,19 @@ pub fn get_traceback(vm: &VirtualMachine, runner: &CairoRunner) -> Option<String
         .then(|| format!("Cairo traceback (most recent call last):\n{traceback}"))
 }
 
+/// Renders the steps recorded by the vm before the error, if they are being tracked
+pub fn get_recent_steps(vm: &VirtualMachine) -> Option<String> {
+    let recent_steps = vm.get_recent_steps()?;
+    let mut steps = String::new();
+    for step in recent_steps.iter() {
+        steps.push_str(&format!(
+            "pc={} ap={} fp={} opcode={:?}\n",
+            step.pc, step.ap, step.fp, step.opcode
+        ));
+    }
+    (!steps.is_empty()).then(|| format!("Recent steps (oldest first):\n{steps}"))
+}
+
 // Substitutes references in the given error_message attribute with their actual value.
 // References are defined with '{}'. E.g., 'x must be positive. Got: {x}'.
 fn substitute_error_message_references(
@@ -238,6 +253,9 @@ impl Display for VmException {
         if let Some(ref string) = self.traceback {
             error_msg.push_str(string);
         }
+        if let Some(ref string) = self.recent_steps {
+            error_msg.push_str(string);
+        }
         // Write error message
         write!(f, "{error_msg}")
     }
@@ -355,6 +373,7 @@ mod test {
                 inner_exc: VirtualMachineError::NoImm,
                 error_attr_value: None,
                 traceback: None,
+                recent_steps: None,
             } if x == pc && y == location
         )
     }
@@ -411,6 +430,7 @@ mod test {
             ))),
             error_attr_value: None,
             traceback: None,
+            recent_steps: None,
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -436,6 +456,7 @@ mod test {
             ))),
             error_attr_value: Some(String::from("Error message: Block may fail\n")),
             traceback: None,
+            recent_steps: None,
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -471,6 +492,7 @@ mod test {
             ))),
             error_attr_value: None,
             traceback: None,
+            recent_steps: None,
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -518,6 +540,7 @@ mod test {
             ))),
             error_attr_value: None,
             traceback: None,
+            recent_steps: None,
         };
         assert_eq!(
             vm_excep.to_string(),
@@ -1190,6 +1213,7 @@ cairo_programs/bad_programs/uint256_sub_b_gt_256.cairo:10:2: (pc=0:12)
                 inner_exc: VirtualMachineError::NoImm,
                 error_attr_value: None,
                 traceback: None,
+                recent_steps: None,
             } if x == pc
         )
     }
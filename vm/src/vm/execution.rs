@@ -0,0 +1,638 @@
+//! # Instruction execution
+//!
+//! Semantics of a single Cairo instruction, independent of the [`VirtualMachine`].
+//!
+//! [`execute_instruction`] computes and deduces the operands of an already decoded instruction,
+//! writes the deduced ones, checks the opcode assertions and updates the registers. Memory is
+//! accessed through the [`MemoryView`] trait, so tools that keep their own memory representation
+//! (such as symbolic or abstract interpreters) can reuse the vm's operand semantics.
+//!
+//! [`VirtualMachine`]: crate::vm::vm_core::VirtualMachine
+
+use crate::stdlib::prelude::*;
+use crate::{
+    types::{
+        errors::math_errors::MathError,
+        instruction::{ApUpdate, FpUpdate, Instruction, Opcode, PcUpdate, Res},
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        context::run_context::RunContext,
+        errors::{memory_errors::MemoryError, vm_errors::VirtualMachineError},
+    },
+};
+use num_traits::{ToPrimitive, Zero};
+
+/// Memory accessed by [`execute_instruction`]
+pub trait MemoryView {
+    /// Returns the value stored at `addr`, or None if the cell is empty
+    fn get(&self, addr: Relocatable) -> Option<MaybeRelocatable>;
+
+    /// Writes `value` at `addr`
+    fn insert(&mut self, addr: Relocatable, value: &MaybeRelocatable) -> Result<(), MemoryError>;
+
+    /// Deduces the value of the empty cell at `addr` (e.g. a builtin's output cell).
+    /// This is tried before deducing the operand from the instruction itself
+    fn deduce(&self, _addr: Relocatable) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
+        Ok(None)
+    }
+
+    /// Returns the error for an `operand` that could neither be read from `addr` nor deduced
+    fn failed_to_compute_operand(&self, operand: &str, addr: Relocatable) -> VirtualMachineError {
+        VirtualMachineError::FailedToComputeOperands(Box::new((operand.to_string(), addr)))
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Operands {
+    pub dst: MaybeRelocatable,
+    pub res: Option<MaybeRelocatable>,
+    pub op0: MaybeRelocatable,
+    pub op1: MaybeRelocatable,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct OperandsAddresses {
+    pub dst_addr: Relocatable,
+    pub op0_addr: Relocatable,
+    pub op1_addr: Relocatable,
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DeducedOperands(u8);
+
+impl DeducedOperands {
+    fn set_dst(&mut self, value: bool) {
+        self.0 |= value as u8;
+    }
+    fn set_op0(&mut self, value: bool) {
+        self.0 |= (value as u8) << 1;
+    }
+    fn set_op1(&mut self, value: bool) {
+        self.0 |= (value as u8) << 2;
+    }
+
+    fn was_dest_deducted(&self) -> bool {
+        self.0 & 1 != 0
+    }
+    fn was_op0_deducted(&self) -> bool {
+        self.0 & 1 << 1 != 0
+    }
+    fn was_op1_deducted(&self) -> bool {
+        self.0 & 1 << 2 != 0
+    }
+}
+
+/// Operands of an instruction executed by [`execute_instruction`] and the addresses they were
+/// read from (or written to, if they were deduced)
+#[derive(PartialEq, Eq, Debug)]
+pub struct StepOutcome {
+    pub operands: Operands,
+    pub operands_addresses: OperandsAddresses,
+}
+
+/// Executes a decoded instruction: computes (and deduces) its operands, writes the deduced ones
+/// to `memory`, checks the opcode assertions and updates `registers`.
+/// `registers` are only updated if the whole instruction succeeds.
+/// Doesn't keep a trace nor mark the accessed addresses, that is up to the caller.
+pub fn execute_instruction<M: MemoryView + ?Sized>(
+    memory: &mut M,
+    registers: &mut RunContext,
+    instruction: &Instruction,
+) -> Result<StepOutcome, VirtualMachineError> {
+    let (operands, operands_addresses, deduced_operands) =
+        compute_operands(memory, registers, instruction)?;
+    insert_deduced_operands(memory, deduced_operands, &operands, &operands_addresses)?;
+    opcode_assertions(registers, instruction, &operands)?;
+    let mut new_registers = RunContext::new(registers.pc, registers.ap, registers.fp);
+    update_registers(&mut new_registers, instruction, &operands)?;
+    *registers = new_registers;
+    Ok(StepOutcome {
+        operands,
+        operands_addresses,
+    })
+}
+
+pub(crate) fn update_fp(
+    registers: &mut RunContext,
+    instruction: &Instruction,
+    operands: &Operands,
+) -> Result<(), VirtualMachineError> {
+    let new_fp_offset: usize = match instruction.fp_update {
+        FpUpdate::APPlus2 => registers.ap + 2,
+        FpUpdate::Dst => match operands.dst {
+            MaybeRelocatable::RelocatableValue(ref rel) => rel.offset,
+            MaybeRelocatable::Int(ref num) => num
+                .to_usize()
+                .ok_or_else(|| MathError::Felt252ToUsizeConversion(Box::new(*num)))?,
+        },
+        FpUpdate::Regular => return Ok(()),
+    };
+    registers.fp = new_fp_offset;
+    Ok(())
+}
+
+pub(crate) fn update_ap(
+    registers: &mut RunContext,
+    instruction: &Instruction,
+    operands: &Operands,
+) -> Result<(), VirtualMachineError> {
+    let new_apset: usize = match instruction.ap_update {
+        ApUpdate::Add => match &operands.res {
+            Some(res) => (registers.get_ap() + res)?.offset,
+            None => return Err(VirtualMachineError::UnconstrainedResAdd),
+        },
+        ApUpdate::Add1 => registers.ap + 1,
+        ApUpdate::Add2 => registers.ap + 2,
+        ApUpdate::Regular => return Ok(()),
+    };
+    registers.ap = new_apset;
+    Ok(())
+}
+
+pub(crate) fn update_pc(
+    registers: &mut RunContext,
+    instruction: &Instruction,
+    operands: &Operands,
+) -> Result<(), VirtualMachineError> {
+    let new_pc: Relocatable = match instruction.pc_update {
+        PcUpdate::Regular => (registers.pc + instruction.size())?,
+        PcUpdate::Jump => match operands.res.as_ref().and_then(|x| x.get_relocatable()) {
+            Some(ref res) => *res,
+            None => return Err(VirtualMachineError::UnconstrainedResJump),
+        },
+        PcUpdate::JumpRel => match &operands.res {
+            Some(res) => match res {
+                MaybeRelocatable::Int(num_res) => (registers.pc + num_res)?,
+                _ => return Err(VirtualMachineError::JumpRelNotInt),
+            },
+            None => return Err(VirtualMachineError::UnconstrainedResJumpRel),
+        },
+        PcUpdate::Jnz => match is_zero(&operands.dst) {
+            true => (registers.pc + instruction.size())?,
+            false => (registers.pc + &operands.op1)?,
+        },
+    };
+    registers.pc = new_pc;
+    Ok(())
+}
+
+pub(crate) fn update_registers(
+    registers: &mut RunContext,
+    instruction: &Instruction,
+    operands: &Operands,
+) -> Result<(), VirtualMachineError> {
+    update_fp(registers, instruction, operands)?;
+    update_ap(registers, instruction, operands)?;
+    update_pc(registers, instruction, operands)?;
+    Ok(())
+}
+
+/// Returns true if the value is zero
+/// Used for JNZ instructions
+pub(crate) fn is_zero(addr: &MaybeRelocatable) -> bool {
+    match addr {
+        MaybeRelocatable::Int(num) => num.is_zero(),
+        _ => false,
+    }
+}
+
+///Returns a tuple (deduced_op0, deduced_res).
+///Deduces the value of op0 if possible (based on dst and op1). Otherwise, returns None.
+///If res was already deduced, returns its deduced value as well.
+pub(crate) fn deduce_op0(
+    registers: &RunContext,
+    instruction: &Instruction,
+    dst: Option<&MaybeRelocatable>,
+    op1: Option<&MaybeRelocatable>,
+) -> Result<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError> {
+    match instruction.opcode {
+        Opcode::Call => Ok((
+            Some(MaybeRelocatable::from((registers.pc + instruction.size())?)),
+            None,
+        )),
+        Opcode::AssertEq => match (&instruction.res, dst, op1) {
+            (Res::Add, Some(dst_addr), Some(op1_addr)) => {
+                Ok((Some(dst_addr.sub(op1_addr)?), dst.cloned()))
+            }
+            (
+                Res::Mul,
+                Some(MaybeRelocatable::Int(num_dst)),
+                Some(MaybeRelocatable::Int(num_op1)),
+            ) if !num_op1.is_zero() => Ok((
+                Some(MaybeRelocatable::Int(num_dst.field_div(
+                    &num_op1.try_into().map_err(|_| MathError::DividedByZero)?,
+                ))),
+                dst.cloned(),
+            )),
+            _ => Ok((None, None)),
+        },
+        _ => Ok((None, None)),
+    }
+}
+
+/// Returns a tuple (deduced_op1, deduced_res).
+///Deduces the value of op1 if possible (based on dst and op0). Otherwise, returns None.
+///If res was already deduced, returns its deduced value as well.
+pub(crate) fn deduce_op1(
+    instruction: &Instruction,
+    dst: Option<&MaybeRelocatable>,
+    op0: Option<MaybeRelocatable>,
+) -> Result<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError> {
+    if let Opcode::AssertEq = instruction.opcode {
+        match instruction.res {
+            Res::Op1 => return Ok((dst.cloned(), dst.cloned())),
+            Res::Add => {
+                return Ok((
+                    dst.zip(op0).and_then(|(dst, op0)| dst.sub(&op0).ok()),
+                    dst.cloned(),
+                ))
+            }
+            Res::Mul => match (dst, op0) {
+                (Some(MaybeRelocatable::Int(num_dst)), Some(MaybeRelocatable::Int(num_op0)))
+                    if !num_op0.is_zero() =>
+                {
+                    return Ok((
+                        Some(MaybeRelocatable::Int(num_dst.field_div(
+                            &num_op0.try_into().map_err(|_| MathError::DividedByZero)?,
+                        ))),
+                        dst.cloned(),
+                    ))
+                }
+                _ => (),
+            },
+            _ => (),
+        };
+    };
+    Ok((None, None))
+}
+
+///Computes the value of res if possible
+pub(crate) fn compute_res(
+    instruction: &Instruction,
+    op0: &MaybeRelocatable,
+    op1: &MaybeRelocatable,
+) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
+    match instruction.res {
+        Res::Op1 => Ok(Some(op1.clone())),
+        Res::Add => Ok(Some(op0.add(op1)?)),
+        Res::Mul => {
+            if let (MaybeRelocatable::Int(num_op0), MaybeRelocatable::Int(num_op1)) = (op0, op1) {
+                return Ok(Some(MaybeRelocatable::Int(num_op0 * num_op1)));
+            }
+            Err(VirtualMachineError::ComputeResRelocatableMul(Box::new((
+                op0.clone(),
+                op1.clone(),
+            ))))
+        }
+        Res::Unconstrained => Ok(None),
+    }
+}
+
+pub(crate) fn deduce_dst(
+    registers: &RunContext,
+    instruction: &Instruction,
+    res: &Option<MaybeRelocatable>,
+) -> Result<MaybeRelocatable, VirtualMachineError> {
+    let dst = match (instruction.opcode, res) {
+        (Opcode::AssertEq, Some(res)) => res.clone(),
+        (Opcode::Call, _) => MaybeRelocatable::from(registers.get_fp()),
+        _ => return Err(VirtualMachineError::NoDst),
+    };
+    Ok(dst)
+}
+
+pub(crate) fn opcode_assertions(
+    registers: &RunContext,
+    instruction: &Instruction,
+    operands: &Operands,
+) -> Result<(), VirtualMachineError> {
+    match instruction.opcode {
+        Opcode::AssertEq => match &operands.res {
+            None => Err(VirtualMachineError::UnconstrainedResAssertEq),
+            Some(res) if res != &operands.dst => Err(VirtualMachineError::DiffAssertValues(
+                Box::new((operands.dst.clone(), res.clone())),
+            )),
+            _ => Ok(()),
+        },
+        Opcode::Call => {
+            let return_pc = MaybeRelocatable::from((registers.pc + instruction.size())?);
+            if operands.op0 != return_pc {
+                return Err(VirtualMachineError::CantWriteReturnPc(Box::new((
+                    operands.op0.clone(),
+                    return_pc,
+                ))));
+            };
+
+            if MaybeRelocatable::from(registers.get_fp()) != operands.dst {
+                return Err(VirtualMachineError::CantWriteReturnFp(Box::new((
+                    operands.dst.clone(),
+                    MaybeRelocatable::from(registers.get_fp()),
+                ))));
+            };
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn insert_deduced_operands<M: MemoryView + ?Sized>(
+    memory: &mut M,
+    deduced_operands: DeducedOperands,
+    operands: &Operands,
+    operands_addresses: &OperandsAddresses,
+) -> Result<(), VirtualMachineError> {
+    if deduced_operands.was_op0_deducted() {
+        memory
+            .insert(operands_addresses.op0_addr, &operands.op0)
+            .map_err(VirtualMachineError::Memory)?;
+    }
+    if deduced_operands.was_op1_deducted() {
+        memory
+            .insert(operands_addresses.op1_addr, &operands.op1)
+            .map_err(VirtualMachineError::Memory)?;
+    }
+    if deduced_operands.was_dest_deducted() {
+        memory
+            .insert(operands_addresses.dst_addr, &operands.dst)
+            .map_err(VirtualMachineError::Memory)?;
+    }
+
+    Ok(())
+}
+
+fn compute_op0_deductions<M: MemoryView + ?Sized>(
+    memory: &M,
+    registers: &RunContext,
+    op0_addr: Relocatable,
+    res: &mut Option<MaybeRelocatable>,
+    instruction: &Instruction,
+    dst_op: &Option<MaybeRelocatable>,
+    op1_op: &Option<MaybeRelocatable>,
+) -> Result<MaybeRelocatable, VirtualMachineError> {
+    let op0_op = match memory.deduce(op0_addr)? {
+        None => {
+            let op0;
+            (op0, *res) = deduce_op0(registers, instruction, dst_op.as_ref(), op1_op.as_ref())?;
+            op0
+        }
+        deduced_memory_cell => deduced_memory_cell,
+    };
+    let op0 = op0_op.ok_or_else(|| memory.failed_to_compute_operand("op0", op0_addr))?;
+    Ok(op0)
+}
+
+fn compute_op1_deductions<M: MemoryView + ?Sized>(
+    memory: &M,
+    op1_addr: Relocatable,
+    res: &mut Option<MaybeRelocatable>,
+    instruction: &Instruction,
+    dst_op: &Option<MaybeRelocatable>,
+    op0: &MaybeRelocatable,
+) -> Result<MaybeRelocatable, VirtualMachineError> {
+    let op1_op = match memory.deduce(op1_addr)? {
+        None => {
+            let (op1, deduced_res) = deduce_op1(instruction, dst_op.as_ref(), Some(op0.clone()))?;
+            if res.is_none() {
+                *res = deduced_res
+            }
+            op1
+        }
+        deduced_memory_cell => deduced_memory_cell,
+    };
+    let op1 = op1_op.ok_or_else(|| memory.failed_to_compute_operand("op1", op1_addr))?;
+    Ok(op1)
+}
+
+/// Compute operands and result, trying to deduce them if normal memory access returns a None
+/// value.
+pub fn compute_operands<M: MemoryView + ?Sized>(
+    memory: &M,
+    registers: &RunContext,
+    instruction: &Instruction,
+) -> Result<(Operands, OperandsAddresses, DeducedOperands), VirtualMachineError> {
+    //Get operands from memory
+    let dst_addr = registers.compute_dst_addr(instruction)?;
+    let dst_op = memory.get(dst_addr);
+
+    let op0_addr = registers.compute_op0_addr(instruction)?;
+    let op0_op = memory.get(op0_addr);
+
+    let op1_addr = registers.compute_op1_addr(instruction, op0_op.as_ref())?;
+    let op1_op = memory.get(op1_addr);
+
+    let mut res: Option<MaybeRelocatable> = None;
+
+    let mut deduced_operands = DeducedOperands::default();
+
+    //Deduce op0 if it wasnt previously computed
+    let op0 = match op0_op {
+        Some(op0) => op0,
+        None => {
+            deduced_operands.set_op0(true);
+            compute_op0_deductions(
+                memory,
+                registers,
+                op0_addr,
+                &mut res,
+                instruction,
+                &dst_op,
+                &op1_op,
+            )?
+        }
+    };
+
+    //Deduce op1 if it wasnt previously computed
+    let op1 = match op1_op {
+        Some(op1) => op1,
+        None => {
+            deduced_operands.set_op1(true);
+            compute_op1_deductions(memory, op1_addr, &mut res, instruction, &dst_op, &op0)?
+        }
+    };
+
+    //Compute res if it wasnt previously deduced
+    if res.is_none() {
+        res = compute_res(instruction, &op0, &op1)?;
+    }
+
+    //Deduce dst if it wasnt previously computed
+    let dst = match dst_op {
+        Some(dst) => dst,
+        None => {
+            deduced_operands.set_dst(true);
+            deduce_dst(registers, instruction, &res)?
+        }
+    };
+    let accessed_addresses = OperandsAddresses {
+        dst_addr,
+        op0_addr,
+        op1_addr,
+    };
+    Ok((
+        Operands { dst, op0, op1, res },
+        accessed_addresses,
+        deduced_operands,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stdlib::collections::HashMap;
+    use crate::vm::decoding::decoder::decode_instruction;
+    use crate::{relocatable, Felt252};
+    use assert_matches::assert_matches;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    #[derive(Default)]
+    struct HashMapMemory(HashMap<Relocatable, MaybeRelocatable>);
+
+    impl MemoryView for HashMapMemory {
+        fn get(&self, addr: Relocatable) -> Option<MaybeRelocatable> {
+            self.0.get(&addr).cloned()
+        }
+
+        fn insert(
+            &mut self,
+            addr: Relocatable,
+            value: &MaybeRelocatable,
+        ) -> Result<(), MemoryError> {
+            self.0.insert(addr, value.clone());
+            Ok(())
+        }
+    }
+
+    impl HashMapMemory {
+        /// Loads the program at segment 0 and the return fp and pc of the entrypoint's frame at
+        /// [fp - 2] and [fp - 1], with fp = (1, 2)
+        fn new(program: &[u64]) -> Self {
+            let mut memory = HashMapMemory::default();
+            for (offset, word) in program.iter().enumerate() {
+                memory
+                    .0
+                    .insert(relocatable!(0, offset), Felt252::from(*word).into());
+            }
+            memory
+                .0
+                .insert(relocatable!(1, 0), relocatable!(2, 0).into());
+            memory
+                .0
+                .insert(relocatable!(1, 1), relocatable!(3, 0).into());
+            memory
+        }
+
+        fn step(&mut self, registers: &mut RunContext) -> Result<StepOutcome, VirtualMachineError> {
+            let encoded = self.0[&registers.pc].get_int().unwrap().to_u64().unwrap();
+            let instruction = decode_instruction(encoded)?;
+            execute_instruction(self, registers, &instruction)
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn execute_instructions_over_hash_map_memory() {
+        let mut memory = HashMapMemory::new(&[
+            // [ap] = 5, ap++
+            0x480680017fff8000,
+            5,
+            // [ap] = 7, ap++
+            0x480680017fff8000,
+            7,
+            // [ap] = [ap - 1] + [ap - 2], ap++
+            0x48307ffe7fff8000,
+        ]);
+        let mut registers = RunContext::new(relocatable!(0, 0), 2, 2);
+
+        assert_matches!(memory.step(&mut registers), Ok(_));
+        assert_eq!(
+            memory.get(relocatable!(1, 2)),
+            Some(Felt252::from(5).into())
+        );
+        assert_eq!((registers.pc, registers.ap), (relocatable!(0, 2), 3));
+
+        assert_matches!(memory.step(&mut registers), Ok(_));
+        assert_eq!(
+            memory.get(relocatable!(1, 3)),
+            Some(Felt252::from(7).into())
+        );
+        assert_eq!((registers.pc, registers.ap), (relocatable!(0, 4), 4));
+
+        let outcome = memory.step(&mut registers).unwrap();
+        assert_eq!(
+            outcome,
+            StepOutcome {
+                operands: Operands {
+                    dst: Felt252::from(12).into(),
+                    res: Some(Felt252::from(12).into()),
+                    op0: Felt252::from(7).into(),
+                    op1: Felt252::from(5).into(),
+                },
+                operands_addresses: OperandsAddresses {
+                    dst_addr: relocatable!(1, 4),
+                    op0_addr: relocatable!(1, 3),
+                    op1_addr: relocatable!(1, 2),
+                },
+            }
+        );
+        assert_eq!(
+            memory.get(relocatable!(1, 4)),
+            Some(Felt252::from(12).into())
+        );
+        assert_eq!(
+            (registers.pc, registers.ap, registers.fp),
+            (relocatable!(0, 5), 5, 2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn execute_instruction_failed_assertion() {
+        // [ap] = 5, ap++
+        let mut memory = HashMapMemory::new(&[0x480680017fff8000, 5]);
+        memory.0.insert(relocatable!(1, 2), Felt252::from(6).into());
+        let mut registers = RunContext::new(relocatable!(0, 0), 2, 2);
+
+        assert_matches!(
+            memory.step(&mut registers),
+            Err(VirtualMachineError::DiffAssertValues(bx))
+                if *bx == (Felt252::from(6).into(), Felt252::from(5).into())
+        );
+        // Registers are left untouched
+        assert_eq!((registers.pc, registers.ap), (relocatable!(0, 0), 2));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn execute_instruction_failed_register_update() {
+        // call abs 5
+        let mut memory = HashMapMemory::new(&[0x1084800180018000, 5]);
+        let mut registers = RunContext::new(relocatable!(0, 0), 2, 2);
+
+        assert_matches!(
+            memory.step(&mut registers),
+            Err(VirtualMachineError::UnconstrainedResJump)
+        );
+        // fp and ap can be updated before the jump fails, but none of them are committed
+        assert_eq!(
+            (registers.pc, registers.ap, registers.fp),
+            (relocatable!(0, 0), 2, 2)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn execute_instruction_unknown_operand() {
+        // [ap] = [ap - 1] + [ap - 2], ap++
+        let mut memory = HashMapMemory::new(&[0x48307ffe7fff8000]);
+        let mut registers = RunContext::new(relocatable!(0, 0), 4, 2);
+
+        assert_matches!(
+            memory.step(&mut registers),
+            Err(VirtualMachineError::FailedToComputeOperands(bx))
+                if *bx == ("op0".to_string(), relocatable!(1, 3))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod context;
 pub mod decoding;
 pub mod errors;
+pub mod execution;
 pub mod runners;
 pub mod security;
 pub mod trace;
@@ -196,23 +196,23 @@ impl BitwiseBuiltinRunner {
 
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
-        if let Some(segment) = memory.data.get(self.base) {
-            let segment_len = segment.len();
-            for (index, off) in (0..segment_len)
-                .step_by(CELLS_PER_BITWISE as usize)
-                .enumerate()
-            {
-                // Add the input cells of each bitwise instance to the private inputs
-                if let (Ok(x), Ok(y)) = (
-                    memory.get_integer((self.base as isize, off).into()),
-                    memory.get_integer((self.base as isize, off + 1).into()),
-                ) {
-                    private_inputs.push(PrivateInput::Pair(PrivateInputPair {
-                        index,
-                        x: *x,
-                        y: *y,
-                    }))
-                }
+        let cells_per_instance = CELLS_PER_BITWISE as usize;
+        for (index, off) in memory
+            .iter_segment(self.base as isize)
+            .map(|(addr, _)| addr.offset)
+            .filter(|off| off % cells_per_instance == 0)
+            .map(|off| (off / cells_per_instance, off))
+        {
+            // Add the input cells of each bitwise instance to the private inputs
+            if let (Ok(x), Ok(y)) = (
+                memory.get_integer((self.base as isize, off).into()),
+                memory.get_integer((self.base as isize, off + 1).into()),
+            ) {
+                private_inputs.push(PrivateInput::Pair(PrivateInputPair {
+                    index,
+                    x: *x,
+                    y: *y,
+                }))
             }
         }
         private_inputs
@@ -226,7 +226,7 @@ mod tests {
     use crate::serde::deserialize_program::BuiltinName;
     use crate::vm::errors::memory_errors::MemoryError;
     use crate::vm::runners::builtin_runner::BuiltinRunner;
-    use crate::vm::vm_core::VirtualMachine;
+
     use crate::Felt252;
     use crate::{
         hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
@@ -644,7 +644,7 @@ mod tests {
             ((0, 14), 14)
         ];
         assert_eq!(
-            builtin.air_private_input(&memory),
+            builtin.air_private_input(&memory).unwrap(),
             (vec![
                 PrivateInput::Pair(PrivateInputPair {
                     index: 0,
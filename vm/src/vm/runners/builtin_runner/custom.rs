@@ -0,0 +1,207 @@
+use crate::stdlib::{boxed::Box, fmt::Debug, sync::Arc, vec::Vec};
+use crate::{
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::{
+        errors::{memory_errors::MemoryError, runner_errors::RunnerError},
+        vm_memory::{memory::Memory, memory_segments::MemorySegmentManager},
+    },
+};
+use num_integer::div_ceil;
+
+/// Logic of a builtin that is not part of the VM, registered with
+/// [`CairoRunner::add_custom_builtin`](crate::vm::runners::cairo_runner::CairoRunner::add_custom_builtin).
+pub trait BuiltinRunnerExt: Debug + Send + Sync {
+    /// Number of memory cells used by each instance of the builtin
+    fn cells_per_instance(&self) -> u32;
+
+    /// Number of cells of each instance written by the program, the rest of them are deduced
+    fn n_input_cells(&self) -> u32;
+
+    /// Deduces the value of the cell at `address`, returns `None` if it can't be deduced
+    fn deduce_memory_cell(
+        &self,
+        address: Relocatable,
+        memory: &Memory,
+    ) -> Result<Option<MaybeRelocatable>, RunnerError>;
+}
+
+/// Runner of a custom builtin. Custom builtins are always included in the initial stack, after
+/// the layout's builtins. They aren't part of any layout, so they have no ratio and their cells
+/// are not preallocated.
+#[derive(Debug, Clone)]
+pub struct CustomBuiltinRunner {
+    name: &'static str,
+    pub base: usize,
+    pub(crate) cells_per_instance: u32,
+    pub(crate) n_input_cells: u32,
+    pub(crate) stop_ptr: Option<usize>,
+    logic: Arc<dyn BuiltinRunnerExt>,
+}
+
+impl CustomBuiltinRunner {
+    pub(crate) fn new(name: &'static str, logic: Box<dyn BuiltinRunnerExt>) -> Self {
+        CustomBuiltinRunner {
+            name,
+            base: 0,
+            cells_per_instance: logic.cells_per_instance(),
+            n_input_cells: logic.n_input_cells(),
+            stop_ptr: None,
+            logic: logic.into(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
+        self.base = segments.add().segment_index as usize // segments.add() always returns a positive index
+    }
+
+    pub fn initial_stack(&self) -> Vec<MaybeRelocatable> {
+        vec![MaybeRelocatable::from((self.base as isize, 0))]
+    }
+
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    pub fn deduce_memory_cell(
+        &self,
+        address: Relocatable,
+        memory: &Memory,
+    ) -> Result<Option<MaybeRelocatable>, RunnerError> {
+        self.logic.deduce_memory_cell(address, memory)
+    }
+
+    pub fn get_memory_segment_addresses(&self) -> (usize, Option<usize>) {
+        (self.base, self.stop_ptr)
+    }
+
+    pub fn get_used_cells(&self, segments: &MemorySegmentManager) -> Result<usize, MemoryError> {
+        segments
+            .get_segment_used_size(self.base)
+            .ok_or(MemoryError::MissingSegmentUsedSizes)
+    }
+
+    pub fn get_used_instances(
+        &self,
+        segments: &MemorySegmentManager,
+    ) -> Result<usize, MemoryError> {
+        let used_cells = self.get_used_cells(segments)?;
+        Ok(div_ceil(used_cells, self.cells_per_instance as usize))
+    }
+
+    pub fn final_stack(
+        &mut self,
+        segments: &MemorySegmentManager,
+        pointer: Relocatable,
+    ) -> Result<Relocatable, RunnerError> {
+        let stop_pointer_addr =
+            (pointer - 1).map_err(|_| RunnerError::NoStopPointer(Box::new(self.name)))?;
+        let stop_pointer = segments
+            .memory
+            .get_relocatable(stop_pointer_addr)
+            .map_err(|_| RunnerError::NoStopPointer(Box::new(self.name)))?;
+        if self.base as isize != stop_pointer.segment_index {
+            return Err(RunnerError::InvalidStopPointerIndex(Box::new((
+                self.name,
+                stop_pointer,
+                self.base,
+            ))));
+        }
+        let stop_ptr = stop_pointer.offset;
+        let used = self.get_used_instances(segments)? * self.cells_per_instance as usize;
+        if stop_ptr != used {
+            return Err(RunnerError::InvalidStopPointer(Box::new((
+                self.name,
+                Relocatable::from((self.base as isize, used)),
+                Relocatable::from((self.base as isize, stop_ptr)),
+            ))));
+        }
+        self.stop_ptr = Some(stop_ptr);
+        Ok(stop_pointer_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::relocatable;
+    use crate::utils::test_utils::*;
+    use crate::vm::runners::builtin_runner::BuiltinRunner;
+    use crate::vm::runners::cairo_pie::SegmentInfo;
+    use crate::vm::vm_core::VirtualMachine;
+    use crate::Felt252;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    fn echo_builtin() -> BuiltinRunner {
+        CustomBuiltinRunner::new("echo_builtin", Box::new(EchoBuiltin)).into()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn initialize_segments_and_initial_stack() {
+        let mut builtin = echo_builtin();
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        builtin.initialize_segments(&mut segments);
+        assert_eq!(builtin.base(), 1);
+        assert_eq!(builtin.name(), "echo_builtin");
+        assert_eq!(builtin.ratio(), None);
+        assert!(builtin.is_ratio_less());
+        assert_eq!(
+            builtin.initial_stack(),
+            vec![MaybeRelocatable::from((1, 0))]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn deduce_memory_cell() {
+        let builtin = echo_builtin();
+        let memory = memory![((0, 0), 7)];
+        assert_eq!(
+            builtin.deduce_memory_cell(relocatable!(0, 1), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt252::from(7))))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(relocatable!(0, 0), &memory),
+            Ok(None)
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(relocatable!(0, 3), &memory),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn final_stack_and_used_instances() {
+        let mut builtin = echo_builtin();
+        let mut vm = VirtualMachine::new(false);
+        vm.segments = segments![((0, 0), 1), ((1, 0), (0, 4))];
+        vm.segments.segment_used_sizes = Some(vec![5]);
+        assert_eq!(builtin.get_used_instances(&vm.segments), Ok(3));
+        assert_eq!(
+            builtin.final_stack(&vm.segments, relocatable!(1, 1)),
+            Err(RunnerError::InvalidStopPointer(Box::new((
+                "echo_builtin",
+                relocatable!(0, 6),
+                relocatable!(0, 4)
+            ))))
+        );
+        vm.segments.segment_used_sizes = Some(vec![3]);
+        assert_eq!(builtin.get_used_instances(&vm.segments), Ok(2));
+        assert_eq!(
+            builtin.final_stack(&vm.segments, relocatable!(1, 1)),
+            Ok(relocatable!(1, 0))
+        );
+        assert_eq!(
+            builtin.get_segment_info(),
+            Ok(SegmentInfo { index: 0, size: 4 })
+        );
+    }
+}
@@ -264,29 +264,29 @@ impl EcOpBuiltinRunner {
 
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
-        if let Some(segment) = memory.data.get(self.base) {
-            let segment_len = segment.len();
-            for (index, off) in (0..segment_len)
-                .step_by(CELLS_PER_EC_OP as usize)
-                .enumerate()
-            {
-                // Add the input cells of each ec_op instance to the private inputs
-                if let (Ok(p_x), Ok(p_y), Ok(q_x), Ok(q_y), Ok(m)) = (
-                    memory.get_integer((self.base as isize, off).into()),
-                    memory.get_integer((self.base as isize, off + 1).into()),
-                    memory.get_integer((self.base as isize, off + 2).into()),
-                    memory.get_integer((self.base as isize, off + 3).into()),
-                    memory.get_integer((self.base as isize, off + 4).into()),
-                ) {
-                    private_inputs.push(PrivateInput::EcOp(PrivateInputEcOp {
-                        index,
-                        p_x: *p_x,
-                        p_y: *p_y,
-                        m: *m,
-                        q_x: *q_x,
-                        q_y: *q_y,
-                    }))
-                }
+        let cells_per_instance = CELLS_PER_EC_OP as usize;
+        for (index, off) in memory
+            .iter_segment(self.base as isize)
+            .map(|(addr, _)| addr.offset)
+            .filter(|off| off % cells_per_instance == 0)
+            .map(|off| (off / cells_per_instance, off))
+        {
+            // Add the input cells of each ec_op instance to the private inputs
+            if let (Ok(p_x), Ok(p_y), Ok(q_x), Ok(q_y), Ok(m)) = (
+                memory.get_integer((self.base as isize, off).into()),
+                memory.get_integer((self.base as isize, off + 1).into()),
+                memory.get_integer((self.base as isize, off + 2).into()),
+                memory.get_integer((self.base as isize, off + 3).into()),
+                memory.get_integer((self.base as isize, off + 4).into()),
+            ) {
+                private_inputs.push(PrivateInput::EcOp(PrivateInputEcOp {
+                    index,
+                    p_x: *p_x,
+                    p_y: *p_y,
+                    m: *m,
+                    q_x: *q_x,
+                    q_y: *q_y,
+                }))
             }
         }
         private_inputs
@@ -308,7 +308,6 @@ mod tests {
     use crate::vm::{
         errors::{memory_errors::MemoryError, runner_errors::RunnerError},
         runners::builtin_runner::BuiltinRunner,
-        vm_core::VirtualMachine,
     };
     use EcOpBuiltinRunner;
 
@@ -1004,7 +1003,7 @@ mod tests {
             ((0, 4), 4)
         ];
         assert_eq!(
-            builtin.air_private_input(&memory),
+            builtin.air_private_input(&memory).unwrap(),
             (vec![PrivateInput::EcOp(PrivateInputEcOp {
                 index: 0,
                 p_x: 0.into(),
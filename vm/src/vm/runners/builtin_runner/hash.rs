@@ -191,23 +191,23 @@ impl HashBuiltinRunner {
 
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
-        if let Some(segment) = memory.data.get(self.base) {
-            let segment_len = segment.len();
-            for (index, off) in (0..segment_len)
-                .step_by(CELLS_PER_HASH as usize)
-                .enumerate()
-            {
-                // Add the input cells of each hash instance to the private inputs
-                if let (Ok(x), Ok(y)) = (
-                    memory.get_integer((self.base as isize, off).into()),
-                    memory.get_integer((self.base as isize, off + 1).into()),
-                ) {
-                    private_inputs.push(PrivateInput::Pair(PrivateInputPair {
-                        index,
-                        x: *x,
-                        y: *y,
-                    }))
-                }
+        let cells_per_instance = CELLS_PER_HASH as usize;
+        for (index, off) in memory
+            .iter_segment(self.base as isize)
+            .map(|(addr, _)| addr.offset)
+            .filter(|off| off % cells_per_instance == 0)
+            .map(|off| (off / cells_per_instance, off))
+        {
+            // Add the input cells of each hash instance to the private inputs
+            if let (Ok(x), Ok(y)) = (
+                memory.get_integer((self.base as isize, off).into()),
+                memory.get_integer((self.base as isize, off + 1).into()),
+            ) {
+                private_inputs.push(PrivateInput::Pair(PrivateInputPair {
+                    index,
+                    x: *x,
+                    y: *y,
+                }))
             }
         }
         private_inputs
@@ -224,10 +224,7 @@ mod tests {
     use crate::vm::runners::cairo_runner::CairoRunner;
     use crate::{felt_hex, relocatable};
 
-    use crate::vm::{
-        errors::memory_errors::MemoryError, runners::builtin_runner::BuiltinRunner,
-        vm_core::VirtualMachine,
-    };
+    use crate::vm::{errors::memory_errors::MemoryError, runners::builtin_runner::BuiltinRunner};
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -590,7 +587,7 @@ mod tests {
             ((0, 9), 9)
         ];
         assert_eq!(
-            builtin.air_private_input(&memory),
+            builtin.air_private_input(&memory).unwrap(),
             (vec![
                 PrivateInput::Pair(PrivateInputPair {
                     index: 0,
@@ -224,44 +224,44 @@ impl KeccakBuiltinRunner {
 
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
-        if let Some(segment) = memory.data.get(self.base) {
-            let segment_len = segment.len();
-            for (index, off) in (0..segment_len)
-                .step_by(self.cells_per_instance as usize)
-                .enumerate()
-            {
-                // Add the input cells of each keccak instance to the private inputs
-                if let (
-                    Ok(input_s0),
-                    Ok(input_s1),
-                    Ok(input_s2),
-                    Ok(input_s3),
-                    Ok(input_s4),
-                    Ok(input_s5),
-                    Ok(input_s6),
-                    Ok(input_s7),
-                ) = (
-                    memory.get_integer((self.base as isize, off).into()),
-                    memory.get_integer((self.base as isize, off + 1).into()),
-                    memory.get_integer((self.base as isize, off + 2).into()),
-                    memory.get_integer((self.base as isize, off + 3).into()),
-                    memory.get_integer((self.base as isize, off + 4).into()),
-                    memory.get_integer((self.base as isize, off + 5).into()),
-                    memory.get_integer((self.base as isize, off + 6).into()),
-                    memory.get_integer((self.base as isize, off + 7).into()),
-                ) {
-                    private_inputs.push(PrivateInput::KeccakState(PrivateInputKeccakState {
-                        index,
-                        input_s0: *input_s0,
-                        input_s1: *input_s1,
-                        input_s2: *input_s2,
-                        input_s3: *input_s3,
-                        input_s4: *input_s4,
-                        input_s5: *input_s5,
-                        input_s6: *input_s6,
-                        input_s7: *input_s7,
-                    }))
-                }
+        let cells_per_instance = self.cells_per_instance as usize;
+        for (index, off) in memory
+            .iter_segment(self.base as isize)
+            .map(|(addr, _)| addr.offset)
+            .filter(|off| off % cells_per_instance == 0)
+            .map(|off| (off / cells_per_instance, off))
+        {
+            // Add the input cells of each keccak instance to the private inputs
+            if let (
+                Ok(input_s0),
+                Ok(input_s1),
+                Ok(input_s2),
+                Ok(input_s3),
+                Ok(input_s4),
+                Ok(input_s5),
+                Ok(input_s6),
+                Ok(input_s7),
+            ) = (
+                memory.get_integer((self.base as isize, off).into()),
+                memory.get_integer((self.base as isize, off + 1).into()),
+                memory.get_integer((self.base as isize, off + 2).into()),
+                memory.get_integer((self.base as isize, off + 3).into()),
+                memory.get_integer((self.base as isize, off + 4).into()),
+                memory.get_integer((self.base as isize, off + 5).into()),
+                memory.get_integer((self.base as isize, off + 6).into()),
+                memory.get_integer((self.base as isize, off + 7).into()),
+            ) {
+                private_inputs.push(PrivateInput::KeccakState(PrivateInputKeccakState {
+                    index,
+                    input_s0: *input_s0,
+                    input_s1: *input_s1,
+                    input_s2: *input_s2,
+                    input_s3: *input_s3,
+                    input_s4: *input_s4,
+                    input_s5: *input_s5,
+                    input_s6: *input_s6,
+                    input_s7: *input_s7,
+                }))
             }
         }
         private_inputs
@@ -280,7 +280,6 @@ mod tests {
     use crate::vm::{
         errors::{memory_errors::MemoryError, runner_errors::RunnerError},
         runners::builtin_runner::BuiltinRunner,
-        vm_core::VirtualMachine,
     };
 
     #[cfg(target_arch = "wasm32")]
@@ -743,7 +742,7 @@ mod tests {
             ((0, 7), 7)
         ];
         assert_eq!(
-            builtin.air_private_input(&memory),
+            builtin.air_private_input(&memory).unwrap(),
             (vec![PrivateInput::KeccakState(PrivateInputKeccakState {
                 index: 0,
                 input_s0: 0.into(),
@@ -5,11 +5,13 @@ use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::{self, InsufficientAllocatedCellsError, MemoryError};
 use crate::vm::errors::runner_errors::RunnerError;
 use crate::vm::errors::vm_errors::VirtualMachineError;
+use crate::vm::runners::cairo_pie::SegmentInfo;
 use crate::vm::vm_core::VirtualMachine;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
 
 mod bitwise;
+mod custom;
 mod ec_op;
 mod hash;
 mod keccak;
@@ -23,6 +25,7 @@ pub use self::keccak::KeccakBuiltinRunner;
 pub use self::poseidon::PoseidonBuiltinRunner;
 pub use self::segment_arena::SegmentArenaBuiltinRunner;
 pub use bitwise::BitwiseBuiltinRunner;
+pub use custom::{BuiltinRunnerExt, CustomBuiltinRunner};
 pub use ec_op::EcOpBuiltinRunner;
 pub use hash::HashBuiltinRunner;
 use num_integer::div_floor;
@@ -49,6 +52,8 @@ pub const SEGMENT_ARENA_BUILTIN_NAME: &str = "segment_arena_builtin";
  * moving the guarantees to runtime by using an `enum` rather than a `Trait`.
  * This works under the assumption that we don't expect downstream users to
  * extend Cairo by adding new builtin runners.
+ * Builtins that are not part of the VM can still be run through the `Custom`
+ * variant, which holds their logic as a `BuiltinRunnerExt` trait object.
  */
 #[derive(Debug, Clone)]
 pub enum BuiltinRunner {
@@ -61,6 +66,7 @@ pub enum BuiltinRunner {
     Signature(SignatureBuiltinRunner),
     Poseidon(PoseidonBuiltinRunner),
     SegmentArena(SegmentArenaBuiltinRunner),
+    Custom(CustomBuiltinRunner),
 }
 
 impl BuiltinRunner {
@@ -80,6 +86,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref mut segment_arena) => {
                 segment_arena.initialize_segments(segments)
             }
+            BuiltinRunner::Custom(ref mut custom) => custom.initialize_segments(segments),
         }
     }
 
@@ -94,6 +101,7 @@ impl BuiltinRunner {
             BuiltinRunner::Signature(ref signature) => signature.initial_stack(),
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.initial_stack(),
             BuiltinRunner::SegmentArena(ref segment_arena) => segment_arena.initial_stack(),
+            BuiltinRunner::Custom(ref custom) => custom.initial_stack(),
         }
     }
 
@@ -121,6 +129,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref mut segment_arena) => {
                 segment_arena.final_stack(segments, stack_pointer)
             }
+            BuiltinRunner::Custom(ref mut custom) => custom.final_stack(segments, stack_pointer),
         }
     }
 
@@ -129,33 +138,35 @@ impl BuiltinRunner {
         &self,
         vm: &VirtualMachine,
     ) -> Result<usize, memory_errors::MemoryError> {
-        match *self {
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => Ok(0),
-            _ => {
-                match self.ratio() {
-                    None => {
-                        // Dynamic layout has the exact number of instances it needs (up to a power of 2).
-                        let instances: usize =
-                            self.get_used_cells(&vm.segments)? / self.cells_per_instance() as usize;
-                        let components = (instances / self.instances_per_component() as usize)
-                            .next_power_of_two();
-                        Ok(self.cells_per_instance() as usize
-                            * self.instances_per_component() as usize
-                            * components)
-                    }
-                    Some(ratio) => {
-                        let min_step = (ratio * self.instances_per_component()) as usize;
-                        if vm.current_step < min_step {
-                            return Err(InsufficientAllocatedCellsError::MinStepNotReached(
-                                Box::new((min_step, self.name())),
-                            )
-                            .into());
-                        };
-                        let value = safe_div_usize(vm.current_step, ratio as usize)
-                            .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?;
-                        Ok(self.cells_per_instance() as usize * value)
-                    }
-                }
+        // Ratio-less builtins don't preallocate cells, so they don't add to the allocated units
+        if self.is_ratio_less() {
+            return Ok(0);
+        }
+        match self.ratio() {
+            None => {
+                // Dynamic layout has the exact number of instances it needs (up to a power of 2).
+                let instances: usize =
+                    self.get_used_cells(&vm.segments)? / self.cells_per_instance() as usize;
+                let components =
+                    (instances / self.instances_per_component() as usize).next_power_of_two();
+                Ok(self.cells_per_instance() as usize
+                    * self.instances_per_component() as usize
+                    * components)
+            }
+            Some(ratio) => {
+                let min_step = (ratio * self.instances_per_component()) as usize;
+                if vm.current_step < min_step {
+                    return Err(
+                        InsufficientAllocatedCellsError::MinStepNotReached(Box::new((
+                            min_step,
+                            self.name(),
+                        )))
+                        .into(),
+                    );
+                };
+                let value = safe_div_usize(vm.current_step, ratio as usize)
+                    .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?;
+                Ok(self.cells_per_instance() as usize * value)
             }
         }
     }
@@ -173,15 +184,29 @@ impl BuiltinRunner {
             BuiltinRunner::Poseidon(ref poseidon) => poseidon.base(),
             //Warning, returns only the segment index, base offset will be 3
             BuiltinRunner::SegmentArena(ref segment_arena) => segment_arena.base(),
+            BuiltinRunner::Custom(ref custom) => custom.base(),
         }
     }
 
+    /// Returns true for builtins that have no ratio in any layout (output, segment_arena and
+    /// custom builtins).
+    /// Their cells are not preallocated: the allocated size is the used size, and they impose no
+    /// minimum number of steps.
+    pub fn is_ratio_less(&self) -> bool {
+        matches!(
+            self,
+            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) | BuiltinRunner::Custom(_)
+        )
+    }
+
     pub fn ratio(&self) -> Option<u32> {
         match self {
             BuiltinRunner::Bitwise(bitwise) => bitwise.ratio(),
             BuiltinRunner::EcOp(ec) => ec.ratio(),
             BuiltinRunner::Hash(hash) => hash.ratio(),
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => None,
+            BuiltinRunner::Output(_)
+            | BuiltinRunner::SegmentArena(_)
+            | BuiltinRunner::Custom(_) => None,
             BuiltinRunner::RangeCheck(range_check) => range_check.ratio(),
             BuiltinRunner::Keccak(keccak) => keccak.ratio(),
             BuiltinRunner::Signature(ref signature) => signature.ratio(),
@@ -202,6 +227,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref segment_arena) => {
                 segment_arena.add_validation_rule(memory)
             }
+            BuiltinRunner::Custom(_) => {}
         }
     }
 
@@ -226,6 +252,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref segment_arena) => {
                 segment_arena.deduce_memory_cell(address, memory)
             }
+            BuiltinRunner::Custom(ref custom) => custom.deduce_memory_cell(address, memory),
         }
     }
 
@@ -262,6 +289,16 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref segment_arena) => {
                 segment_arena.get_memory_segment_addresses()
             }
+            BuiltinRunner::Custom(ref custom) => custom.get_memory_segment_addresses(),
+        }
+    }
+
+    /// Returns the builtin's segment index and stop_ptr offset (as the segment size).
+    /// Fails if the stop_ptr is not set, which means `final_stack` wasn't called.
+    pub fn get_segment_info(&self) -> Result<SegmentInfo, RunnerError> {
+        match self.get_memory_segment_addresses() {
+            (index, Some(stop_ptr)) => Ok((index as isize, stop_ptr).into()),
+            _ => Err(RunnerError::NoStopPointer(Box::new(self.name()))),
         }
     }
 
@@ -278,6 +315,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref segment_arena) => {
                 segment_arena.get_used_cells(segments)
             }
+            BuiltinRunner::Custom(ref custom) => custom.get_used_cells(segments),
         }
     }
 
@@ -297,6 +335,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref segment_arena) => {
                 segment_arena.get_used_instances(segments)
             }
+            BuiltinRunner::Custom(ref custom) => custom.get_used_instances(segments),
         }
     }
 
@@ -344,6 +383,7 @@ impl BuiltinRunner {
             BuiltinRunner::Signature(builtin) => builtin.cells_per_instance,
             BuiltinRunner::Poseidon(builtin) => builtin.cells_per_instance,
             BuiltinRunner::SegmentArena(builtin) => builtin.cells_per_instance,
+            BuiltinRunner::Custom(builtin) => builtin.cells_per_instance,
         }
     }
 
@@ -358,6 +398,7 @@ impl BuiltinRunner {
             BuiltinRunner::Signature(builtin) => builtin.n_input_cells,
             BuiltinRunner::Poseidon(builtin) => builtin.n_input_cells,
             BuiltinRunner::SegmentArena(builtin) => builtin.n_input_cells_per_instance,
+            BuiltinRunner::Custom(builtin) => builtin.n_input_cells,
         }
     }
 
@@ -367,7 +408,9 @@ impl BuiltinRunner {
             BuiltinRunner::EcOp(builtin) => builtin.instances_per_component,
             BuiltinRunner::Hash(builtin) => builtin.instances_per_component,
             BuiltinRunner::RangeCheck(builtin) => builtin.instances_per_component,
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => 1,
+            BuiltinRunner::Output(_)
+            | BuiltinRunner::SegmentArena(_)
+            | BuiltinRunner::Custom(_) => 1,
             BuiltinRunner::Keccak(builtin) => builtin.instances_per_component,
             BuiltinRunner::Signature(builtin) => builtin.instances_per_component,
             BuiltinRunner::Poseidon(builtin) => builtin.instances_per_component,
@@ -385,6 +428,7 @@ impl BuiltinRunner {
             BuiltinRunner::Signature(_) => SIGNATURE_BUILTIN_NAME,
             BuiltinRunner::Poseidon(_) => POSEIDON_BUILTIN_NAME,
             BuiltinRunner::SegmentArena(_) => SEGMENT_ARENA_BUILTIN_NAME,
+            BuiltinRunner::Custom(custom) => custom.name(),
         }
     }
 
@@ -454,25 +498,20 @@ impl BuiltinRunner {
         &self,
         vm: &VirtualMachine,
     ) -> Result<(usize, usize), MemoryError> {
-        match self {
-            BuiltinRunner::Output(_) | BuiltinRunner::SegmentArena(_) => {
-                let used = self.get_used_cells(&vm.segments)?;
-                Ok((used, used))
-            }
-            _ => {
-                let used = self.get_used_cells(&vm.segments)?;
-                let size = self.get_allocated_memory_units(vm)?;
-                if used > size {
-                    return Err(InsufficientAllocatedCellsError::BuiltinCells(Box::new((
-                        self.name(),
-                        used,
-                        size,
-                    )))
-                    .into());
-                }
-                Ok((used, size))
-            }
+        let used = self.get_used_cells(&vm.segments)?;
+        if self.is_ratio_less() {
+            return Ok((used, used));
         }
+        let size = self.get_allocated_memory_units(vm)?;
+        if used > size {
+            return Err(InsufficientAllocatedCellsError::BuiltinCells(Box::new((
+                self.name(),
+                used,
+                size,
+            )))
+            .into());
+        }
+        Ok((used, size))
     }
 
     pub fn get_additional_data(&self) -> BuiltinAdditionalData {
@@ -485,17 +524,17 @@ impl BuiltinRunner {
     }
 
     // Returns information about the builtin that should be added to the AIR private input.
-    pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
-        match self {
+    pub fn air_private_input(&self, memory: &Memory) -> Result<Vec<PrivateInput>, RunnerError> {
+        Ok(match self {
             BuiltinRunner::RangeCheck(builtin) => builtin.air_private_input(memory),
             BuiltinRunner::Bitwise(builtin) => builtin.air_private_input(memory),
             BuiltinRunner::Hash(builtin) => builtin.air_private_input(memory),
             BuiltinRunner::EcOp(builtin) => builtin.air_private_input(memory),
             BuiltinRunner::Poseidon(builtin) => builtin.air_private_input(memory),
-            BuiltinRunner::Signature(builtin) => builtin.air_private_input(memory),
+            BuiltinRunner::Signature(builtin) => builtin.air_private_input(memory)?,
             BuiltinRunner::Keccak(builtin) => builtin.air_private_input(memory),
             _ => vec![],
-        }
+        })
     }
 
     #[cfg(test)]
@@ -512,6 +551,7 @@ impl BuiltinRunner {
             BuiltinRunner::SegmentArena(ref mut segment_arena) => {
                 segment_arena.stop_ptr = Some(stop_ptr)
             }
+            BuiltinRunner::Custom(ref mut custom) => custom.stop_ptr = Some(stop_ptr),
         }
     }
 }
@@ -570,6 +610,12 @@ impl From<SegmentArenaBuiltinRunner> for BuiltinRunner {
     }
 }
 
+impl From<CustomBuiltinRunner> for BuiltinRunner {
+    fn from(runner: CustomBuiltinRunner) -> Self {
+        BuiltinRunner::Custom(runner)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -586,7 +632,6 @@ mod tests {
             bitwise_instance_def::BitwiseInstanceDef, ec_op_instance_def::EcOpInstanceDef,
         },
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
     };
     use assert_matches::assert_matches;
 
@@ -1013,6 +1058,40 @@ mod tests {
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(0));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_allocated_memory_units_segment_arena() {
+        let builtin = BuiltinRunner::SegmentArena(SegmentArenaBuiltinRunner::new(true));
+        let vm = vm!();
+
+        // No min step requirement, even at step 0
+        assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(0));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_used_cells_and_allocated_size_output() {
+        let builtin = BuiltinRunner::Output(OutputBuiltinRunner::new(true));
+        let mut vm = vm!();
+        vm.segments.segment_used_sizes = Some(vec![3]);
+
+        assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((3, 3)));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn is_ratio_less_tests() {
+        let output_builtin: BuiltinRunner = OutputBuiltinRunner::new(true).into();
+        assert!(output_builtin.is_ratio_less());
+        let segment_arena_builtin: BuiltinRunner = SegmentArenaBuiltinRunner::new(true).into();
+        assert!(segment_arena_builtin.is_ratio_less());
+        let hash_builtin: BuiltinRunner = HashBuiltinRunner::new(None, true).into();
+        assert!(!hash_builtin.is_ratio_less());
+        let range_check_builtin: BuiltinRunner =
+            BuiltinRunner::RangeCheck(RangeCheckBuiltinRunner::new(Some(8), 8, true));
+        assert!(!range_check_builtin.is_ratio_less());
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_allocated_memory_units_range_check() {
@@ -1200,6 +1279,16 @@ mod tests {
         assert_matches!(builtin.run_security_checks(&vm), Ok(()));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_security_checks_for_output_with_unwritten_cells() {
+        let builtin = BuiltinRunner::Output(OutputBuiltinRunner::new(true));
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((0, 3), 2)];
+
+        assert_matches!(builtin.run_security_checks(&vm), Ok(()));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_security_checks_empty_memory() {
@@ -205,10 +205,7 @@ mod tests {
 
     use crate::{
         utils::test_utils::*,
-        vm::{
-            errors::memory_errors::MemoryError, runners::builtin_runner::BuiltinRunner,
-            vm_core::VirtualMachine,
-        },
+        vm::{errors::memory_errors::MemoryError, runners::builtin_runner::BuiltinRunner},
     };
 
     #[cfg(target_arch = "wasm32")]
@@ -532,7 +529,7 @@ mod tests {
         let builtin: BuiltinRunner = OutputBuiltinRunner::new(true).into();
 
         let memory = memory![((0, 0), 0), ((0, 1), 1), ((0, 2), 2), ((0, 3), 3)];
-        assert!(builtin.air_private_input(&memory).is_empty());
+        assert!(builtin.air_private_input(&memory).unwrap().is_empty());
     }
 
     #[test]
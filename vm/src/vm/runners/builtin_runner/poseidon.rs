@@ -167,25 +167,25 @@ impl PoseidonBuiltinRunner {
 
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
         let mut private_inputs = vec![];
-        if let Some(segment) = memory.data.get(self.base) {
-            let segment_len = segment.len();
-            for (index, off) in (0..segment_len)
-                .step_by(CELLS_PER_POSEIDON as usize)
-                .enumerate()
-            {
-                // Add the input cells of each poseidon instance to the private inputs
-                if let (Ok(input_s0), Ok(input_s1), Ok(input_s2)) = (
-                    memory.get_integer((self.base as isize, off).into()),
-                    memory.get_integer((self.base as isize, off + 1).into()),
-                    memory.get_integer((self.base as isize, off + 2).into()),
-                ) {
-                    private_inputs.push(PrivateInput::PoseidonState(PrivateInputPoseidonState {
-                        index,
-                        input_s0: *input_s0,
-                        input_s1: *input_s1,
-                        input_s2: *input_s2,
-                    }))
-                }
+        let cells_per_instance = CELLS_PER_POSEIDON as usize;
+        for (index, off) in memory
+            .iter_segment(self.base as isize)
+            .map(|(addr, _)| addr.offset)
+            .filter(|off| off % cells_per_instance == 0)
+            .map(|off| (off / cells_per_instance, off))
+        {
+            // Add the input cells of each poseidon instance to the private inputs
+            if let (Ok(input_s0), Ok(input_s1), Ok(input_s2)) = (
+                memory.get_integer((self.base as isize, off).into()),
+                memory.get_integer((self.base as isize, off + 1).into()),
+                memory.get_integer((self.base as isize, off + 2).into()),
+            ) {
+                private_inputs.push(PrivateInput::PoseidonState(PrivateInputPoseidonState {
+                    index,
+                    input_s0: *input_s0,
+                    input_s1: *input_s1,
+                    input_s2: *input_s2,
+                }))
             }
         }
         private_inputs
@@ -202,7 +202,7 @@ mod tests {
     use crate::utils::test_utils::*;
     use crate::vm::runners::cairo_runner::CairoRunner;
 
-    use crate::vm::{runners::builtin_runner::BuiltinRunner, vm_core::VirtualMachine};
+    use crate::vm::runners::builtin_runner::BuiltinRunner;
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
 
@@ -458,7 +458,7 @@ mod tests {
             ((0, 11), 11)
         ];
         assert_eq!(
-            builtin.air_private_input(&memory),
+            builtin.air_private_input(&memory).unwrap(),
             (vec![
                 PrivateInput::PoseidonState(PrivateInputPoseidonState {
                     index: 0,
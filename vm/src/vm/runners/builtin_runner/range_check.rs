@@ -127,13 +127,13 @@ impl RangeCheckBuiltinRunner {
 
     pub fn get_range_check_usage(&self, memory: &Memory) -> Option<(usize, usize)> {
         let range_check_segment = memory.data.get(self.base)?;
-        let mut rc_bounds =
-            (!range_check_segment.is_empty()).then_some((usize::MAX, usize::MIN))?;
+        let mut rc_bounds: Option<(usize, usize)> = None;
 
         // Split value into n_parts parts of less than _INNER_RC_BOUND size.
-        for value in range_check_segment {
-            rc_bounds = value
-                .as_ref()?
+        // Values written out of order (e.g. by hints) may leave holes in the segment, which don't
+        // constrain the bounds.
+        for value in range_check_segment.iter().flatten() {
+            let bounds = value
                 .get_value()
                 .get_int_ref()?
                 .to_le_digits()
@@ -145,11 +145,12 @@ impl RangeCheckBuiltinRunner {
                         .map(move |i| ((digit >> (i * INNER_RC_BOUND_SHIFT)) & INNER_RC_BOUND_MASK))
                 })
                 .take(self.n_parts as usize)
-                .fold(rc_bounds, |mm, x| {
+                .fold(rc_bounds.unwrap_or((usize::MAX, usize::MIN)), |mm, x| {
                     (min(mm.0, x as usize), max(mm.1, x as usize))
                 });
+            rc_bounds = Some(bounds);
         }
-        Some(rc_bounds)
+        rc_bounds
     }
 
     pub fn get_used_instances(
@@ -197,15 +198,17 @@ impl RangeCheckBuiltinRunner {
     }
 
     pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
-        let mut private_inputs = vec![];
-        if let Some(segment) = memory.data.get(self.base) {
-            for (index, val) in segment.iter().enumerate() {
-                if let Some(value) = val.as_ref().and_then(|cell| cell.get_value().get_int()) {
-                    private_inputs.push(PrivateInput::Value(PrivateInputValue { index, value }))
-                }
-            }
-        }
-        private_inputs
+        memory
+            .iter_segment(self.base as isize)
+            .filter_map(|(addr, val)| {
+                val.get_int().map(|value| {
+                    PrivateInput::Value(PrivateInputValue {
+                        index: addr.offset,
+                        value,
+                    })
+                })
+            })
+            .collect()
     }
 }
 
@@ -219,10 +222,7 @@ mod tests {
         hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
         types::program::Program,
         utils::test_utils::*,
-        vm::{
-            runners::{builtin_runner::BuiltinRunner, cairo_runner::CairoRunner},
-            vm_core::VirtualMachine,
-        },
+        vm::runners::{builtin_runner::BuiltinRunner, cairo_runner::CairoRunner},
     };
 
     #[cfg(target_arch = "wasm32")]
@@ -583,6 +583,14 @@ mod tests {
         assert_eq!(builtin.get_range_check_usage(&memory), Some((0, 61576)));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_range_check_usage_with_holes() {
+        let builtin = RangeCheckBuiltinRunner::new(Some(8), 8, true);
+        let memory = memory![((0, 1), 0x1234_5678), ((0, 4), 0x0001_0002)];
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((0, 0x5678)));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_range_check_empty_memory() {
@@ -610,7 +618,7 @@ mod tests {
 
         let memory = memory![((0, 0), 0), ((0, 1), 1), ((0, 2), 2)];
         assert_eq!(
-            builtin.air_private_input(&memory),
+            builtin.air_private_input(&memory).unwrap(),
             (vec![
                 PrivateInput::Value(PrivateInputValue {
                     index: 0,
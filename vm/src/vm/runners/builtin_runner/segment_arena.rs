@@ -2,6 +2,7 @@ use crate::stdlib::boxed::Box;
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::vm::errors::runner_errors::RunnerError;
 use crate::vm::vm_memory::memory::Memory;
+use crate::Felt252;
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},
     vm::vm_memory::memory_segments::MemorySegmentManager,
@@ -39,8 +40,8 @@ impl SegmentArenaBuiltinRunner {
     pub fn initialize_segments(&mut self, segments: &mut MemorySegmentManager) {
         let info = &[
             MaybeRelocatable::from(segments.add()),
-            MaybeRelocatable::from(0),
-            MaybeRelocatable::from(0),
+            MaybeRelocatable::from(Felt252::ZERO),
+            MaybeRelocatable::from(Felt252::ZERO),
         ];
         let segment_start = gen_arg(segments, info);
         // 0 + 3 can't fail
@@ -142,7 +143,7 @@ fn gen_arg(segments: &mut MemorySegmentManager, data: &[MaybeRelocatable; 3]) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::vm::vm_core::VirtualMachine;
+
     use crate::{relocatable, utils::test_utils::*, vm::runners::builtin_runner::BuiltinRunner};
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -153,8 +154,8 @@ mod tests {
         let mut segments = MemorySegmentManager::new();
         let data = &[
             MaybeRelocatable::from(segments.add()),
-            MaybeRelocatable::from(0),
-            MaybeRelocatable::from(0),
+            mayberelocatable!(0),
+            mayberelocatable!(0),
         ];
         let base = gen_arg(&mut segments, data);
         assert_eq!(base, (1, 0).into());
@@ -502,6 +503,6 @@ mod tests {
         let builtin: BuiltinRunner = SegmentArenaBuiltinRunner::new(true).into();
 
         let memory = memory![((0, 0), 0), ((0, 1), 1), ((0, 2), 2), ((0, 3), 3)];
-        assert!(builtin.air_private_input(&memory).is_empty());
+        assert!(builtin.air_private_input(&memory).unwrap().is_empty());
     }
 }
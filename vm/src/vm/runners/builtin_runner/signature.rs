@@ -242,34 +242,28 @@ impl SignatureBuiltinRunner {
         BuiltinAdditionalData::Signature(signatures)
     }
 
-    pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
+    pub fn air_private_input(&self, memory: &Memory) -> Result<Vec<PrivateInput>, RunnerError> {
         let mut private_inputs = vec![];
         for (addr, signature) in self.signatures.borrow().iter() {
-            if let (Ok(pubkey), Ok(msg)) = (memory.get_integer(*addr), memory.get_integer(addr + 1))
-            {
-                private_inputs.push(PrivateInput::Signature(PrivateInputSignature {
-                    index: addr
-                        .offset
-                        .saturating_sub(self.base)
-                        .checked_div(CELLS_PER_SIGNATURE as usize)
-                        .unwrap_or_default(),
-                    pubkey: *pubkey,
-                    msg: *msg,
-                    signature_input: SignatureInput {
-                        r: Felt252::from_bytes_be(&signature.r.to_bytes_be()),
-                        w: Felt252::from(
-                            &div_mod(
-                                &BigInt::one(),
-                                &BigInt::from_bytes_be(Sign::Plus, &signature.s.to_bytes_be()),
-                                &EC_ORDER,
-                            )
-                            .unwrap_or_default(),
-                        ),
-                    },
-                }))
-            }
+            let pubkey = memory.get_integer(*addr)?;
+            let msg = memory.get_integer(addr + 1)?;
+            let w = div_mod(
+                &BigInt::one(),
+                &BigInt::from_bytes_be(Sign::Plus, &signature.s.to_bytes_be()),
+                &EC_ORDER,
+            )?;
+            private_inputs.push(PrivateInput::Signature(PrivateInputSignature {
+                // The builtin segment starts at offset 0
+                index: addr.offset / CELLS_PER_SIGNATURE as usize,
+                pubkey: *pubkey,
+                msg: *msg,
+                signature_input: SignatureInput {
+                    r: Felt252::from_bytes_be(&signature.r.to_bytes_be()),
+                    w: Felt252::from(&w),
+                },
+            }))
         }
-        private_inputs
+        Ok(private_inputs)
     }
 }
 
@@ -283,7 +277,6 @@ mod tests {
         vm::{
             errors::memory_errors::{InsufficientAllocatedCellsError, MemoryError},
             runners::builtin_runner::BuiltinRunner,
-            vm_core::VirtualMachine,
             vm_memory::{memory::Memory, memory_segments::MemorySegmentManager},
         },
     };
@@ -643,4 +636,75 @@ mod tests {
             BuiltinAdditionalData::Signature(signatures)
         )
     }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_air_private_input() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        builtin.base = 4;
+        builtin
+            .add_signature((4, 0).into(), &(Felt252::from(45678), Felt252::from(3)))
+            .unwrap();
+        builtin
+            .add_signature((4, 2).into(), &(Felt252::from(1234), Felt252::from(3)))
+            .unwrap();
+        let memory = memory![((4, 0), 10), ((4, 1), 11), ((4, 2), 12), ((4, 3), 13)];
+        let w = Felt252::from(&div_mod(&BigInt::one(), &BigInt::from(3), &EC_ORDER).unwrap());
+
+        let mut private_inputs = builtin.air_private_input(&memory).unwrap();
+        private_inputs.sort_by_key(|input| match input {
+            PrivateInput::Signature(signature) => signature.index,
+            _ => usize::MAX,
+        });
+        assert_eq!(
+            private_inputs,
+            vec![
+                PrivateInput::Signature(PrivateInputSignature {
+                    index: 0,
+                    pubkey: 10.into(),
+                    msg: 11.into(),
+                    signature_input: SignatureInput { r: 45678.into(), w },
+                }),
+                PrivateInput::Signature(PrivateInputSignature {
+                    index: 1,
+                    pubkey: 12.into(),
+                    msg: 13.into(),
+                    signature_input: SignatureInput { r: 1234.into(), w },
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_air_private_input_corrupted_pubkey() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        builtin.base = 4;
+        builtin
+            .add_signature((4, 0).into(), &(Felt252::from(45678), Felt252::from(3)))
+            .unwrap();
+        let memory = memory![((4, 0), (2, 0)), ((4, 1), 11)];
+        assert_eq!(
+            builtin.air_private_input(&memory),
+            Err(RunnerError::Memory(MemoryError::ExpectedInteger(Box::new(
+                (4, 0).into()
+            ))))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_air_private_input_missing_message() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        builtin
+            .add_signature((0, 0).into(), &(Felt252::from(45678), Felt252::from(3)))
+            .unwrap();
+        let memory = memory![((0, 0), 10)];
+        assert_eq!(
+            builtin.air_private_input(&memory),
+            Err(RunnerError::Memory(MemoryError::UnknownMemoryCell(
+                Box::new((0, 1).into())
+            )))
+        );
+    }
 }
@@ -14,6 +14,8 @@ use crate::{
     },
 };
 
+#[cfg(feature = "extensive_hints")]
+use crate::types::program::HintRange;
 use crate::Felt252;
 use crate::{
     hint_processor::hint_processor_definition::{HintProcessor, HintReference},
@@ -43,10 +45,12 @@ use crate::{
         security::verify_secure_runner,
         {
             runners::builtin_runner::{
-                BitwiseBuiltinRunner, BuiltinRunner, EcOpBuiltinRunner, HashBuiltinRunner,
-                OutputBuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner,
+                BitwiseBuiltinRunner, BuiltinRunner, BuiltinRunnerExt, CustomBuiltinRunner,
+                EcOpBuiltinRunner, HashBuiltinRunner, OutputBuiltinRunner, RangeCheckBuiltinRunner,
+                SignatureBuiltinRunner,
             },
             vm_core::VirtualMachine,
+            vm_memory::memory_segments::MemorySegmentManager,
         },
     },
 };
@@ -78,6 +82,63 @@ impl From<Vec<MaybeRelocatable>> for CairoArg {
     }
 }
 
+/// Builds a Cairo 1 `ByteArray` argument.
+/// The bytes are split into full 31-byte words (`data`) and a `pending_word` holding the
+/// remaining `pending_word_len` bytes, all encoded big-endian.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ByteArrayArg {
+    data: Vec<Felt252>,
+    pending_word: Felt252,
+    pending_word_len: usize,
+}
+
+impl ByteArrayArg {
+    pub fn new(bytes: &[u8]) -> Self {
+        let chunks = bytes.chunks_exact(31);
+        let pending = chunks.remainder();
+        ByteArrayArg {
+            data: chunks.map(Felt252::from_bytes_be_slice).collect(),
+            pending_word: Felt252::from_bytes_be_slice(pending),
+            pending_word_len: pending.len(),
+        }
+    }
+
+    pub fn data(&self) -> &[Felt252] {
+        &self.data
+    }
+
+    pub fn pending_word(&self) -> Felt252 {
+        self.pending_word
+    }
+
+    pub fn pending_word_len(&self) -> usize {
+        self.pending_word_len
+    }
+
+    /// Writes the full words into a new segment and returns the arguments in ABI order:
+    /// `data_start`, `data_end`, `pending_word` and `pending_word_len`.
+    pub fn gen_args(
+        &self,
+        segments: &mut MemorySegmentManager,
+    ) -> Result<Vec<CairoArg>, MemoryError> {
+        let data_start = segments.add();
+        let data = self.data.iter().map(MaybeRelocatable::from).collect();
+        let data_end = segments.load_data(data_start, &data)?;
+        Ok(vec![
+            MaybeRelocatable::from(data_start).into(),
+            MaybeRelocatable::from(data_end).into(),
+            MaybeRelocatable::from(self.pending_word).into(),
+            MaybeRelocatable::from(self.pending_word_len).into(),
+        ])
+    }
+}
+
+impl From<&str> for ByteArrayArg {
+    fn from(value: &str) -> Self {
+        ByteArrayArg::new(value.as_bytes())
+    }
+}
+
 // ================
 //   RunResources
 // ================
@@ -160,6 +221,9 @@ pub struct CairoRunner {
     pub relocated_memory: Vec<Option<Felt252>>,
     pub exec_scopes: ExecutionScopes,
     pub relocated_trace: Option<Vec<RelocatedTraceEntry>>,
+    /// When set, execution resources report 0 memory holes instead of counting them.
+    /// Only honoured outside of proof mode, as counting relies on accessed addresses tracking.
+    pub disable_memory_holes_count: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -214,6 +278,7 @@ impl CairoRunner {
                 None
             },
             relocated_trace: None,
+            disable_memory_holes_count: false,
         })
     }
 
@@ -241,6 +306,17 @@ impl CairoRunner {
         Ok(end)
     }
 
+    /// Marks the segment the program was loaded into as read-only, so that writes to it fail as
+    /// soon as they happen instead of when verifying the run. Must be called after loading the program.
+    pub fn mark_program_segment_read_only(&self, vm: &mut VirtualMachine) {
+        if let Some(index) = self
+            .program_base
+            .and_then(|base| usize::try_from(base.segment_index).ok())
+        {
+            vm.segments.mark_read_only(index);
+        }
+    }
+
     /// Creates the builtin runners according to the builtins used by the program and the selected layout
     /// When running in proof_mode, all builtins in the layout will be created, and only those in the program will be included
     /// When not running in proof_mode, only program builtins will be created and included
@@ -340,6 +416,29 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Registers a builtin that is not part of the VM, running `logic` on its own segment.
+    /// Must be called after `initialize_builtins` and before `initialize_segments`.
+    /// Custom builtins are appended after the layout's builtins, so they come last in the
+    /// initial stack, and they are left out of the AIR public input.
+    /// As programs can't declare custom builtins in their `builtins` list, the custom builtin's
+    /// pointer is always passed to main, so the program must take it as its last implicit argument.
+    pub fn add_custom_builtin(
+        &self,
+        vm: &mut VirtualMachine,
+        name: &'static str,
+        logic: Box<dyn BuiltinRunnerExt>,
+    ) -> Result<(), RunnerError> {
+        if self.program_base.is_some() {
+            return Err(RunnerError::CustomBuiltinAfterSegments(Box::new(name)));
+        }
+        if vm.builtin_runners.iter().any(|b| b.name() == name) {
+            return Err(RunnerError::DuplicateBuiltin(Box::new(name)));
+        }
+        vm.builtin_runners
+            .push(CustomBuiltinRunner::new(name, logic).into());
+        Ok(())
+    }
+
     fn is_proof_mode(&self) -> bool {
         self.runner_mode == RunnerMode::ProofModeCanonical
             || self.runner_mode == RunnerMode::ProofModeCairo1
@@ -608,8 +707,12 @@ impl CairoRunner {
                         &hint.flow_tracking_data.ap_tracking,
                         &hint.flow_tracking_data.reference_ids,
                         references,
+                        &hint.accessible_scopes,
                     )
-                    .map_err(|_| VirtualMachineError::CompileHintFail(hint.code.clone().into()))
+                    .map_err(|err| match err {
+                        VirtualMachineError::AmbiguousReference(_) => err,
+                        _ => VirtualMachineError::CompileHintFail(hint.code.clone().into()),
+                    })
             })
             .collect()
     }
@@ -622,6 +725,37 @@ impl CairoRunner {
         &self.program.builtins
     }
 
+    /// Translates `pc` into an offset within the program, according to the base the program
+    /// was loaded at. Returns None if `pc` doesn't point into the program.
+    #[cfg(not(feature = "extensive_hints"))]
+    fn get_program_offset(&self, pc: Relocatable) -> Option<usize> {
+        match self.program_base {
+            Some(base) if base.segment_index == pc.segment_index => {
+                pc.offset.checked_sub(base.offset)
+            }
+            Some(_) => None,
+            None => Some(pc.offset),
+        }
+    }
+
+    /// Returns the program hint ranges keyed by their absolute pc, according to the base the
+    /// program was loaded at.
+    #[cfg(feature = "extensive_hints")]
+    fn get_hint_ranges(&self) -> HashMap<Relocatable, HintRange> {
+        let hint_ranges = &self
+            .program
+            .shared_program_data
+            .hints_collection
+            .hints_ranges;
+        match self.program_base {
+            Some(base) => hint_ranges
+                .iter()
+                .map(|(pc, range)| ((base + pc.offset).unwrap_or(*pc), *range))
+                .collect(),
+            None => hint_ranges.clone(),
+        }
+    }
+
     pub fn run_until_pc(
         &mut self,
         address: Relocatable,
@@ -634,25 +768,21 @@ impl CairoRunner {
         #[cfg(feature = "extensive_hints")]
         let mut hint_data = self.get_hint_data(references, hint_processor)?;
         #[cfg(feature = "extensive_hints")]
-        let mut hint_ranges = self
-            .program
-            .shared_program_data
-            .hints_collection
-            .hints_ranges
-            .clone();
+        let mut hint_ranges = self.get_hint_ranges();
         #[cfg(feature = "hooks")]
         vm.execute_before_first_step(self, &hint_data)?;
+        #[cfg(not(feature = "extensive_hints"))]
+        let hints_collection = &self.program.shared_program_data.hints_collection;
         while vm.run_context.pc != address && !hint_processor.consumed() {
+            #[cfg(not(feature = "extensive_hints"))]
+            let pc = self.get_program_offset(vm.run_context.pc);
             vm.step(
                 hint_processor,
                 &mut self.exec_scopes,
                 #[cfg(feature = "extensive_hints")]
                 &mut hint_data,
                 #[cfg(not(feature = "extensive_hints"))]
-                self.program
-                    .shared_program_data
-                    .hints_collection
-                    .get_hint_range_for_pc(vm.run_context.pc.offset)
+                pc.and_then(|pc| hints_collection.get_hint_range_for_pc(pc))
                     .and_then(|range| {
                         range.and_then(|(start, length)| hint_data.get(start..start + length.get()))
                     })
@@ -685,18 +815,16 @@ impl CairoRunner {
         #[cfg(feature = "extensive_hints")]
         let mut hint_data = self.get_hint_data(references, hint_processor)?;
         #[cfg(feature = "extensive_hints")]
-        let mut hint_ranges = self
-            .program
-            .shared_program_data
-            .hints_collection
-            .hints_ranges
-            .clone();
+        let mut hint_ranges = self.get_hint_ranges();
         #[cfg(not(feature = "extensive_hints"))]
         let hint_data = &self
-            .program
-            .shared_program_data
-            .hints_collection
-            .get_hint_range_for_pc(vm.run_context.pc.offset)
+            .get_program_offset(vm.run_context.pc)
+            .and_then(|pc| {
+                self.program
+                    .shared_program_data
+                    .hints_collection
+                    .get_hint_range_for_pc(pc)
+            })
             .and_then(|range| {
                 range.and_then(|(start, length)| hint_data.get(start..start + length.get()))
             })
@@ -801,6 +929,9 @@ impl CairoRunner {
 
         let mut used_units_by_builtins = 0;
         for builtin_runner in &vm.builtin_runners {
+            if builtin_runner.is_ratio_less() {
+                continue;
+            }
             let used_units = builtin_runner.get_used_diluted_check_units(
                 diluted_pool_instance.spacing,
                 diluted_pool_instance.n_bits,
@@ -910,23 +1041,13 @@ impl CairoRunner {
         }
         //Relocated addresses start at 1
         self.relocated_memory.push(None);
-        for (index, segment) in vm.segments.memory.data.iter().enumerate() {
-            for (seg_offset, cell) in segment.iter().enumerate() {
-                match cell {
-                    Some(cell) => {
-                        let relocated_addr = relocate_address(
-                            Relocatable::from((index as isize, seg_offset)),
-                            relocation_table,
-                        )?;
-                        let value = relocate_value(cell.get_value().clone(), relocation_table)?;
-                        if self.relocated_memory.len() <= relocated_addr {
-                            self.relocated_memory.resize(relocated_addr + 1, None);
-                        }
-                        self.relocated_memory[relocated_addr] = Some(value);
-                    }
-                    None => self.relocated_memory.push(None),
-                }
+        for (addr, value) in vm.segments.memory.iter_all(false) {
+            let relocated_addr = relocate_address(addr, relocation_table)?;
+            let value = relocate_value(value.clone(), relocation_table)?;
+            if self.relocated_memory.len() <= relocated_addr {
+                self.relocated_memory.resize(relocated_addr + 1, None);
             }
+            self.relocated_memory[relocated_addr] = Some(value);
         }
         Ok(())
     }
@@ -940,12 +1061,10 @@ impl CairoRunner {
         if !relocate_mem && vm.trace.is_none() {
             return Ok(());
         }
-        // relocate_segments can fail if compute_effective_sizes is not called before.
-        // The expect should be unreachable.
         let relocation_table = vm
             .segments
             .relocate_segments()
-            .expect("compute_effective_sizes called but relocate_memory still returned error");
+            .map_err(TraceError::MemoryError)?;
 
         if relocate_mem {
             if let Err(memory_error) = self.relocate_memory(vm, &relocation_table) {
@@ -965,18 +1084,13 @@ impl CairoRunner {
         &self,
         vm: &VirtualMachine,
     ) -> Result<Vec<(usize, usize)>, RunnerError> {
-        let mut builtin_segment_info = Vec::new();
-
-        for builtin in &vm.builtin_runners {
-            let (index, stop_ptr) = builtin.get_memory_segment_addresses();
-
-            builtin_segment_info.push((
-                index,
-                stop_ptr.ok_or_else(|| RunnerError::NoStopPointer(Box::new(builtin.name())))?,
-            ));
-        }
-
-        Ok(builtin_segment_info)
+        vm.builtin_runners
+            .iter()
+            .map(|builtin| {
+                let segment_info = builtin.get_segment_info()?;
+                Ok((segment_info.index as usize, segment_info.size))
+            })
+            .collect()
     }
 
     // Returns a map from builtin's name wihout the "_builtin" suffix to its base's segment index and stop_ptr offset
@@ -988,19 +1102,13 @@ impl CairoRunner {
         let mut builtin_segment_info = HashMap::new();
 
         for builtin in &vm.builtin_runners {
-            let (index, stop_ptr) = builtin.get_memory_segment_addresses();
-
             builtin_segment_info.insert(
                 builtin
                     .name()
                     .strip_suffix("_builtin")
                     .unwrap_or_default()
                     .to_string(),
-                (
-                    index as isize,
-                    stop_ptr.ok_or_else(|| RunnerError::NoStopPointer(Box::new(builtin.name())))?,
-                )
-                    .into(),
+                builtin.get_segment_info()?,
             );
         }
 
@@ -1019,7 +1127,16 @@ impl CairoRunner {
                 .map(|x| x.len())
                 .unwrap_or(vm.current_step),
         };
-        let n_memory_holes = self.get_memory_holes(vm)?;
+        let n_memory_holes = if self.is_proof_mode() {
+            self.get_memory_holes(vm)?
+        } else if self.disable_memory_holes_count {
+            0
+        } else {
+            vm.segments.get_memory_holes_with_effective_sizes(
+                vm.builtin_runners.len(),
+                self.program.builtins.contains(&BuiltinName::output),
+            )?
+        };
 
         let mut builtin_instance_counter = HashMap::new();
         for builtin_runner in &vm.builtin_runners {
@@ -1110,7 +1227,7 @@ impl CairoRunner {
             .iter()
             .map(|arg| vm.segments.gen_cairo_arg(arg))
             .collect::<Result<Vec<MaybeRelocatable>, VirtualMachineError>>()?;
-        let return_fp = MaybeRelocatable::from(0);
+        let return_fp = MaybeRelocatable::from(Felt252::ZERO);
         let end = self.initialize_function_entrypoint(vm, entrypoint, stack, return_fp)?;
 
         self.initialize_vm(vm)?;
@@ -1285,9 +1402,11 @@ impl CairoRunner {
             .iter_mut()
             .rev()
             .filter(|builtin_runner| {
-                self.get_program_builtins()
-                    .iter()
-                    .any(|bn| bn.name() == builtin_runner.name())
+                matches!(builtin_runner, BuiltinRunner::Custom(_))
+                    || self
+                        .get_program_builtins()
+                        .iter()
+                        .any(|bn| bn.name() == builtin_runner.name())
             })
         {
             stack_ptr = runner.final_stack(&vm.segments, stack_ptr)?
@@ -1426,15 +1545,18 @@ impl CairoRunner {
         )
     }
 
-    pub fn get_air_private_input(&self, vm: &VirtualMachine) -> AirPrivateInput {
+    pub fn get_air_private_input(
+        &self,
+        vm: &VirtualMachine,
+    ) -> Result<AirPrivateInput, RunnerError> {
         let mut private_inputs = HashMap::new();
         for builtin in vm.builtin_runners.iter() {
             private_inputs.insert(
                 builtin.name(),
-                builtin.air_private_input(&vm.segments.memory),
+                builtin.air_private_input(&vm.segments.memory)?,
             );
         }
-        AirPrivateInput(private_inputs)
+        Ok(AirPrivateInput(private_inputs))
     }
 }
 
@@ -1548,12 +1670,17 @@ mod tests {
     };
     use crate::vm::vm_memory::memory::MemoryCell;
 
-    use crate::felt_hex;
+    use crate::{felt_hex, felt_str};
     use crate::{
-        hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+        hint_processor::builtin_hint_processor::builtin_hint_processor_definition::{
+            BuiltinHintProcessor, HintProcessorData,
+        },
         relocatable,
-        serde::deserialize_program::{Identifier, ReferenceManager},
+        serde::deserialize_program::{
+            ApTracking, FlowTrackingData, HintParams, Identifier, OffsetValue, ReferenceManager,
+        },
         types::instance_definitions::bitwise_instance_def::BitwiseInstanceDef,
+        types::instruction::Register,
         utils::test_utils::*,
         vm::trace::trace_entry::TraceEntry,
     };
@@ -3355,6 +3482,55 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_program_loaded_at_two_bases() {
+        // [ap] = 5; ap++
+        // ret
+        // With a hint entering a new scope at pc 0
+        let hint = HintParams {
+            code: "vm_enter_scope()".to_string(),
+            accessible_scopes: vec![],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids: HashMap::new(),
+            },
+        };
+        let program = program!(
+            data = vec_data!((5189976364521848832_i64), (5), (2345108766317314046_i64)),
+            hints = BTreeMap::from([(0, vec![hint])]),
+            main = Some(0),
+        );
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut vm = vm!();
+
+        // First copy at (0, 0)
+        let mut first_runner = cairo_runner!(program);
+        first_runner.initialize_segments(&mut vm, None);
+        let end = first_runner.initialize_main_entrypoint(&mut vm).unwrap();
+        first_runner.initialize_vm(&mut vm).unwrap();
+        assert_matches!(
+            first_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+
+        // Second copy right after the first one, sharing the execution segment
+        let mut second_runner = cairo_runner!(program);
+        second_runner.program_base = Some(Relocatable::from((0, program.data_len())));
+        second_runner.execution_base = Some(vm.get_ap());
+        let end = second_runner.initialize_main_entrypoint(&mut vm).unwrap();
+        second_runner.initialize_vm(&mut vm).unwrap();
+        assert_eq!(vm.get_pc(), Relocatable::from((0, 3)));
+        assert_matches!(
+            second_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+
+        // The hint ran once for each copy of the program
+        assert_eq!(first_runner.exec_scopes.data.len(), 2);
+        assert_eq!(second_runner.exec_scopes.data.len(), 2);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn run_empty() {
@@ -3800,6 +3976,159 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_builtin_segment_info_for_pie_base_not_finished() {
+        let program = program!();
+
+        let cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        vm.builtin_runners = vec![BuiltinRunner::Output(OutputBuiltinRunner::new(true))];
+        assert_eq!(
+            cairo_runner.get_builtin_segment_info_for_pie(&vm),
+            Err(RunnerError::NoStopPointer(Box::new(
+                BuiltinName::output.name()
+            ))),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn builtin_segments_info_pie_and_public_input_match() {
+        let program = include_bytes!(
+            "../../../../cairo_programs/manually_compiled/proof_mode_output_only.json"
+        );
+        let cairo_run_config = crate::cairo_run::CairoRunConfig {
+            layout: "small",
+            relocate_mem: true,
+            trace_enabled: true,
+            ..Default::default()
+        };
+        let (cairo_runner, vm) = crate::cairo_run::cairo_run(
+            program,
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            cairo_runner.get_builtin_segments_info(&vm),
+            Ok(vec![(2, 1)])
+        );
+        let pie_segments = cairo_runner
+            .get_cairo_pie(&vm)
+            .unwrap()
+            .metadata
+            .builtin_segments;
+        let public_input = cairo_runner.get_air_public_input(&vm).unwrap();
+        let relocation_table = vm.relocation_table.as_ref().unwrap();
+        assert_eq!(pie_segments.len(), 1);
+        for (name, segment_info) in pie_segments {
+            let addresses = &public_input.memory_segments[name.as_str()];
+            let begin_addr = relocation_table[segment_info.index as usize];
+            assert_eq!(addresses.begin_addr, begin_addr);
+            assert_eq!(addresses.stop_ptr, begin_addr + segment_info.size);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_address_round_trip_all_segments() {
+        let program = include_bytes!(
+            "../../../../cairo_programs/manually_compiled/proof_mode_output_only.json"
+        );
+        let cairo_run_config = crate::cairo_run::CairoRunConfig {
+            layout: "small",
+            relocate_mem: true,
+            ..Default::default()
+        };
+        let (_, vm) = crate::cairo_run::cairo_run(
+            program,
+            &cairo_run_config,
+            &mut BuiltinHintProcessor::new_empty(),
+        )
+        .unwrap();
+
+        let relocation_table = vm.get_relocation_table().unwrap();
+        assert_eq!(relocation_table.len(), vm.segments.num_segments());
+        for (segment_index, base) in relocation_table.iter().enumerate() {
+            let size = vm.segments.get_segment_size(segment_index).unwrap();
+            for offset in 0..size {
+                let addr = Relocatable::from((segment_index as isize, offset));
+                let relocated = vm.relocate_address(addr).unwrap();
+                assert_eq!(relocated, base + offset);
+                assert_eq!(vm.unrelocate_address(relocated), Ok(addr));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_address_round_trip_zero_segment() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((1, 0), 2), ((1, 1), 3)];
+        // Empty segment between the used ones
+        vm.segments.add();
+        let zero_segment = vm.segments.add_zero_segment(3);
+        vm.segments.finalize_zero_segment();
+        cairo_runner.relocate(&mut vm, true).unwrap();
+
+        assert_eq!(vm.get_relocation_table(), Some(&[1, 2, 4, 4][..]));
+        for (segment_index, size) in [(0, 1), (1, 2), (zero_segment, 3)] {
+            for offset in 0..size {
+                let addr = Relocatable::from((segment_index as isize, offset));
+                assert_eq!(
+                    vm.unrelocate_address(vm.relocate_address(addr).unwrap()),
+                    Ok(addr)
+                );
+            }
+        }
+        assert_eq!(vm.unrelocate_address(5), Ok(Relocatable::from((3, 1))));
+        assert_eq!(
+            vm.unrelocate_address(0),
+            Err(MemoryError::AddressNotRelocated(0))
+        );
+        assert_eq!(
+            vm.unrelocate_address(7),
+            Err(MemoryError::AddressNotRelocated(7))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_without_memory_nor_trace() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        vm.segments = segments![((0, 0), 1), ((1, 0), 2)];
+        cairo_runner.relocate(&mut vm, false).unwrap();
+
+        assert_eq!(vm.get_relocation_table(), None);
+        assert!(cairo_runner.relocated_memory.is_empty());
+        assert_eq!(
+            vm.relocate_address(Relocatable::from((1, 0))),
+            Err(MemoryError::UnrelocatedMemory)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_address_before_relocation() {
+        let vm = vm!();
+        assert_eq!(vm.get_relocation_table(), None);
+        assert_eq!(
+            vm.relocate_address((0, 0).into()),
+            Err(MemoryError::UnrelocatedMemory)
+        );
+        assert_eq!(
+            vm.unrelocate_address(1),
+            Err(MemoryError::UnrelocatedMemory)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_execution_resources_trace_not_enabled() {
@@ -3856,6 +4185,79 @@ mod tests {
         assert_eq!(runner.get_execution_resources(&vm).unwrap().n_steps, 80);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_execution_resources_computes_effective_sizes_on_demand() {
+        let program = program!();
+
+        let cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        vm.segments = segments![((0, 0), 0), ((0, 1), 0), ((0, 3), 0)];
+        vm.segments.memory.mark_as_accessed((0, 0).into());
+        vm.segments.memory.mark_as_accessed((0, 3).into());
+        vm.current_step = 10;
+        assert_eq!(vm.segments.segment_used_sizes, None);
+        let expected = ExecutionResources {
+            n_steps: 10,
+            n_memory_holes: 2,
+            builtin_instance_counter: HashMap::new(),
+        };
+        assert_eq!(
+            cairo_runner.get_execution_resources(&vm),
+            Ok(expected.clone())
+        );
+        vm.segments.compute_effective_sizes();
+        assert_eq!(cairo_runner.get_execution_resources(&vm), Ok(expected));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_execution_resources_disable_memory_holes_count() {
+        let program = program!();
+
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        vm.segments = segments![((0, 0), 0), ((0, 3), 0)];
+        vm.segments.memory.mark_as_accessed((0, 0).into());
+        vm.current_step = 10;
+        cairo_runner.disable_memory_holes_count = true;
+        assert_eq!(
+            cairo_runner.get_execution_resources(&vm),
+            Ok(ExecutionResources {
+                n_steps: 10,
+                n_memory_holes: 0,
+                builtin_instance_counter: HashMap::new(),
+            }),
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_execution_resources_proof_mode_requires_segment_used_sizes() {
+        let program = program!();
+
+        let mut cairo_runner = cairo_runner!(program, "plain", true);
+        let mut vm = vm!();
+
+        vm.segments = segments![((0, 0), 0), ((0, 3), 0)];
+        vm.segments.memory.mark_as_accessed((0, 0).into());
+        cairo_runner.disable_memory_holes_count = true;
+        assert_eq!(
+            cairo_runner.get_execution_resources(&vm),
+            Err(RunnerError::Memory(MemoryError::MissingSegmentUsedSizes)),
+        );
+        vm.segments.compute_effective_sizes();
+        assert_eq!(
+            cairo_runner
+                .get_execution_resources(&vm)
+                .unwrap()
+                .n_memory_holes,
+            3
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_execution_resources_empty_builtins() {
@@ -4099,6 +4501,42 @@ mod tests {
         );
     }
 
+    /// Test that get_perm_range_check_limits() merges the instruction offsets with the values
+    /// written into the range check segment after the validation rules were added, as hints do.
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_perm_range_check_limits_with_hint_written_values() {
+        let program = program!();
+
+        let cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        vm.builtin_runners = vec![RangeCheckBuiltinRunner::new(Some(8), 8, true).into()];
+        vm.builtin_runners[0].initialize_segments(&mut vm.segments);
+        vm.builtin_runners[0].add_validation_rule(&mut vm.segments.memory);
+        vm.rc_limits = Some((32760, 32780));
+
+        // Only the instruction offsets are known
+        assert_matches!(
+            cairo_runner.get_perm_range_check_limits(&vm),
+            Some((32760, 32780))
+        );
+
+        // A hint writes a value whose parts are all the maximum, leaving a hole before it
+        vm.insert_value((0, 2).into(), Felt252::from(u128::MAX))
+            .unwrap();
+        assert_matches!(
+            cairo_runner.get_perm_range_check_limits(&vm),
+            Some((32760, 0xffff))
+        );
+
+        // And another one whose parts are all the minimum
+        vm.insert_value((0, 0).into(), Felt252::ZERO).unwrap();
+        assert_matches!(
+            cairo_runner.get_perm_range_check_limits(&vm),
+            Some((0, 0xffff))
+        );
+    }
+
     /// Test that check_range_check_usage() returns successfully when trace is
     /// not enabled.
     #[test]
@@ -4550,6 +4988,7 @@ mod tests {
                     full_name: None,
                     members: None,
                     cairo_type: None,
+                    destination: None,
                 },
             )]
             .into_iter()
@@ -4578,6 +5017,7 @@ mod tests {
                         full_name: None,
                         members: None,
                         cairo_type: None,
+                        destination: None,
                     },
                 ),
                 (
@@ -4589,6 +5029,7 @@ mod tests {
                         full_name: None,
                         members: None,
                         cairo_type: None,
+                        destination: None,
                     },
                 ),
             ]
@@ -4618,6 +5059,7 @@ mod tests {
                     full_name: None,
                     members: None,
                     cairo_type: None,
+                    destination: None,
                 },
             )]
             .into_iter()
@@ -4936,6 +5378,108 @@ mod tests {
         assert!(cairo_runner.get_memory_holes(&vm).unwrap().is_zero());
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_from_entrypoint_short_string_arg() {
+        let program = Program::from_bytes(
+            include_bytes!("../../../../cairo_programs/manually_compiled/hash_short_string.json"),
+            None,
+        )
+        .unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+
+        let entrypoint = program
+            .shared_program_data
+            .identifiers
+            .get("__main__.hash_short_string")
+            .unwrap()
+            .pc
+            .unwrap();
+
+        cairo_runner.initialize_function_runner(&mut vm).unwrap();
+        let pedersen_base = vm
+            .get_builtin_runners()
+            .iter()
+            .find(|b| b.name() == HASH_BUILTIN_NAME)
+            .unwrap()
+            .base() as isize;
+        let value = vm.segments.gen_arg(&"hello").unwrap();
+        assert_eq!(value, mayberelocatable!(0x68656c6c6f));
+
+        assert_matches!(
+            cairo_runner.run_from_entrypoint(
+                entrypoint,
+                &[
+                    &MaybeRelocatable::from((pedersen_base, 0)).into(), //pedersen_ptr
+                    &value.into()
+                ],
+                true,
+                None,
+                &mut vm,
+                &mut hint_processor,
+            ),
+            Ok(())
+        );
+        // pedersen("hello", "hello")
+        assert_eq!(
+            vm.get_return_values(2).unwrap(),
+            vec![
+                MaybeRelocatable::from((pedersen_base, 3)),
+                MaybeRelocatable::from(felt_str!(
+                    "1091421952050272455139079281806639743280793968016540814745004887807540514024"
+                ))
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn byte_array_arg_layout() {
+        let mut segments = MemorySegmentManager::new();
+        let value = "This string is longer than thirty-one bytes";
+        let byte_array = ByteArrayArg::from(value);
+        assert_eq!(
+            byte_array.data(),
+            &[Felt252::from_bytes_be_slice(&value.as_bytes()[..31])]
+        );
+        assert_eq!(
+            byte_array.pending_word(),
+            Felt252::from_bytes_be_slice(&value.as_bytes()[31..])
+        );
+        assert_eq!(byte_array.pending_word_len(), 12);
+
+        let args = byte_array.gen_args(&mut segments).unwrap();
+        assert_eq!(
+            args,
+            vec![
+                CairoArg::Single(MaybeRelocatable::from((0, 0))),
+                CairoArg::Single(MaybeRelocatable::from((0, 1))),
+                CairoArg::Single(byte_array.pending_word().into()),
+                CairoArg::Single(mayberelocatable!(12)),
+            ]
+        );
+        assert_eq!(
+            segments.memory.get_integer((0, 0).into()).unwrap().as_ref(),
+            &byte_array.data()[0]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn byte_array_arg_empty_and_exact_word() {
+        let empty = ByteArrayArg::from("");
+        assert!(empty.data().is_empty());
+        assert_eq!(empty.pending_word(), Felt252::ZERO);
+        assert_eq!(empty.pending_word_len(), 0);
+
+        let exact = ByteArrayArg::new(&[b'a'; 31]);
+        assert_eq!(exact.data().len(), 1);
+        assert_eq!(exact.pending_word(), Felt252::ZERO);
+        assert_eq!(exact.pending_word_len(), 0);
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn cairo_arg_from_single() {
@@ -5475,7 +6019,7 @@ mod tests {
             &mut BuiltinHintProcessor::new_empty(),
         )
         .unwrap();
-        let air_private_input = runner.get_air_private_input(&vm);
+        let air_private_input = runner.get_air_private_input(&vm).unwrap();
         assert!(air_private_input.0[HASH_BUILTIN_NAME].is_empty());
         assert!(air_private_input.0[RANGE_CHECK_BUILTIN_NAME].is_empty());
         assert!(air_private_input.0[BITWISE_BUILTIN_NAME].is_empty());
@@ -5501,4 +6045,247 @@ mod tests {
             })]
         );
     }
+
+    /*Program used (the bytecode of initialize_and_run_output_builtin, with the echo builtin
+    taking the place of the output builtin):
+
+    func write{echo_ptr: felt*}(value) {
+        assert [echo_ptr] = value;
+        let echo_ptr = echo_ptr + 1;
+        return ();
+    }
+
+    func main{echo_ptr: felt*}() {
+        write(a);
+        write(b);
+        return ();
+    }
+    */
+    fn echo_program(a: i64, b: i64) -> Program {
+        program!(
+            data = vec_data!(
+                (4612671182993129469_i64),
+                (5198983563776393216_i64),
+                (1),
+                (2345108766317314046_i64),
+                (5191102247248822272_i64),
+                (5189976364521848832_i64),
+                (a),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020474",
+                    10
+                )),
+                (5189976364521848832_i64),
+                (b),
+                (1226245742482522112_i64),
+                ((
+                    "3618502788666131213697322783095070105623107215331596699973092056135872020470",
+                    10
+                )),
+                (2345108766317314046_i64)
+            ),
+            main = Some(4),
+        )
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_with_echo_builtin() {
+        let program = echo_program(5, 5);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!(true);
+        cairo_runner.initialize_builtins(&mut vm, false).unwrap();
+        cairo_runner
+            .add_custom_builtin(&mut vm, "echo_builtin", Box::new(EchoBuiltin))
+            .unwrap();
+        cairo_runner.initialize_segments(&mut vm, None);
+        let end = cairo_runner.initialize_main_entrypoint(&mut vm).unwrap();
+        cairo_runner.initialize_vm(&mut vm).unwrap();
+        assert_eq!(vm.builtin_runners[0].name(), "echo_builtin");
+        assert_eq!(vm.builtin_runners[0].base(), 2);
+        // The builtin's pointer is main's implicit argument
+        check_memory!(vm.segments.memory, ((1, 0), (2, 0)));
+
+        assert_matches!(
+            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+        // The first cell was written by the program, the second one deduced
+        check_memory!(vm.segments.memory, ((2, 0), 5), ((2, 1), 5));
+
+        cairo_runner
+            .end_run(false, false, &mut vm, &mut hint_processor)
+            .unwrap();
+        cairo_runner.read_return_values(&mut vm).unwrap();
+        assert_eq!(
+            vm.builtin_runners[0].get_segment_info(),
+            Ok(cairo_pie::SegmentInfo { index: 2, size: 2 })
+        );
+        assert_matches!(
+            verify_secure_runner(&cairo_runner, false, None, &mut vm),
+            Ok(())
+        );
+
+        let resources = cairo_runner.get_execution_resources(&vm).unwrap();
+        assert_eq!(resources.builtin_instance_counter["echo_builtin"], 1);
+
+        cairo_runner.relocate(&mut vm, true).unwrap();
+        assert!(vm.get_memory_segment_addresses().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn run_with_echo_builtin_inconsistent_write() {
+        let program = echo_program(1, 17);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm, false).unwrap();
+        cairo_runner
+            .add_custom_builtin(&mut vm, "echo_builtin", Box::new(EchoBuiltin))
+            .unwrap();
+        cairo_runner.initialize_segments(&mut vm, None);
+        let end = cairo_runner.initialize_main_entrypoint(&mut vm).unwrap();
+        cairo_runner.initialize_vm(&mut vm).unwrap();
+        // Writing 17 to the cell deduced as 1 fails
+        assert_matches!(
+            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            Err(VirtualMachineError::DiffAssertValues(_))
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn add_custom_builtin_errors() {
+        let program = program![BuiltinName::output];
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm, false).unwrap();
+        assert_eq!(
+            cairo_runner.add_custom_builtin(&mut vm, "output_builtin", Box::new(EchoBuiltin)),
+            Err(RunnerError::DuplicateBuiltin(Box::new("output_builtin")))
+        );
+        cairo_runner.initialize_segments(&mut vm, None);
+        assert_eq!(
+            cairo_runner.add_custom_builtin(&mut vm, "echo_builtin", Box::new(EchoBuiltin)),
+            Err(RunnerError::CustomBuiltinAfterSegments(Box::new(
+                "echo_builtin"
+            )))
+        );
+    }
+
+    /// Program with two references named `x`, defined in `main` and in the nested `main.inner`
+    /// scope, used by hints at pcs 0 and 2 with the given accessible scopes
+    fn shadowed_reference_program(inner_scopes: &str) -> Program {
+        let program_json = r#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "attributes": [],
+                "debug_info": null,
+                "builtins": [],
+                "data": [
+                    "0x480680017fff8000",
+                    "0x3e8",
+                    "0x480680017fff8000",
+                    "0x7d0",
+                    "0x208b7fff7fff7ffe"
+                ],
+                "identifiers": {
+                    "__main__.main": {
+                        "decorators": [],
+                        "pc": 0,
+                        "type": "function"
+                    }
+                },
+                "hints": {
+                    "0": [
+                        {
+                            "accessible_scopes": ["__main__", "__main__.main"],
+                            "code": "print(ids.x)",
+                            "flow_tracking_data": {
+                                "ap_tracking": {"group": 0, "offset": 0},
+                                "reference_ids": {"__main__.main.x": 0, "__main__.main.inner.x": 1}
+                            }
+                        }
+                    ],
+                    "2": [
+                        {
+                            "accessible_scopes": INNER_SCOPES,
+                            "code": "print(ids.x)",
+                            "flow_tracking_data": {
+                                "ap_tracking": {"group": 0, "offset": 0},
+                                "reference_ids": {"__main__.main.x": 0, "__main__.main.inner.x": 1}
+                            }
+                        }
+                    ]
+                },
+                "reference_manager": {
+                    "references": [
+                        {
+                            "ap_tracking_data": {"group": 0, "offset": 0},
+                            "pc": 0,
+                            "value": "[cast(fp + (-3), felt*)]"
+                        },
+                        {
+                            "ap_tracking_data": {"group": 0, "offset": 0},
+                            "pc": 2,
+                            "value": "[cast(fp + (-4), felt*)]"
+                        }
+                    ]
+                }
+            }"#
+        .replace("INNER_SCOPES", inner_scopes);
+        Program::from_bytes(program_json.as_bytes(), Some("main")).unwrap()
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_hint_data_resolves_shadowed_references_by_scope() {
+        let program =
+            shadowed_reference_program(r#"["__main__", "__main__.main", "__main__.main.inner"]"#);
+        let cairo_runner = cairo_runner!(program);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let hint_data = cairo_runner
+            .get_hint_data(
+                &cairo_runner.program.shared_program_data.reference_manager,
+                &mut hint_processor,
+            )
+            .unwrap();
+        let x_offsets: Vec<_> = hint_data
+            .iter()
+            .map(|data| {
+                data.downcast_ref::<HintProcessorData>().unwrap().ids_data["x"]
+                    .offset1
+                    .clone()
+            })
+            .collect();
+        assert_eq!(
+            x_offsets,
+            vec![
+                OffsetValue::Reference(Register::FP, -3, false),
+                OffsetValue::Reference(Register::FP, -4, false)
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_hint_data_ambiguous_reference() {
+        let program = shadowed_reference_program(r#"["__main__"]"#);
+        let cairo_runner = cairo_runner!(program);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        assert_matches!(
+            cairo_runner.get_hint_data(
+                &cairo_runner.program.shared_program_data.reference_manager,
+                &mut hint_processor,
+            ),
+            Err(VirtualMachineError::AmbiguousReference(bx))
+                if *bx == (
+                    "x".to_string(),
+                    vec!["__main__.main.inner.x".to_string(), "__main__.main.x".to_string()]
+                )
+        );
+    }
 }
@@ -64,9 +64,9 @@ pub fn verify_secure_runner(
     // This means that every temporary address has been properly relocated to a real address
     // Asumption: If temporary memory is empty, this means no temporary memory addresses were generated and all addresses in memory are real
     if !vm.segments.memory.temp_data.is_empty() {
-        for value in vm.segments.memory.data.iter().flatten() {
-            match value.as_ref().map(|x| x.get_value()) {
-                Some(MaybeRelocatable::RelocatableValue(addr)) if addr.segment_index < 0 => {
+        for (_, value) in vm.segments.memory.iter_all(false) {
+            match value {
+                MaybeRelocatable::RelocatableValue(addr) if addr.segment_index < 0 => {
                     return Err(VirtualMachineError::InvalidMemoryValueTemporaryAddress(
                         Box::new(*addr),
                     ))
@@ -88,8 +88,6 @@ mod test {
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::serde::deserialize_program::BuiltinName;
 
-    use crate::types::relocatable::Relocatable;
-
     use crate::Felt252;
     use crate::{relocatable, types::program::Program, utils::test_utils::*};
     use assert_matches::assert_matches;
@@ -40,3 +40,116 @@ pub mod trace_entry {
         Ok(relocation_table[segment_index] + value.offset)
     }
 }
+
+pub mod recent_steps {
+    use crate::{
+        stdlib::prelude::*,
+        types::{instruction::Opcode, relocatable::Relocatable},
+    };
+
+    ///A step recorded by [`RecentSteps`].
+    ///Holds the register values before the instruction was executed, and its decoded opcode
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    pub struct RecentStep {
+        pub pc: Relocatable,
+        pub ap: usize,
+        pub fp: usize,
+        pub opcode: Opcode,
+    }
+
+    /// Fixed-size ring buffer holding the last steps executed by the vm.
+    /// Unlike the trace, it can be kept enabled on every run, as recording a step never allocates.
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    pub struct RecentSteps {
+        entries: Vec<RecentStep>,
+        capacity: usize,
+        // Index of the oldest entry once the buffer is full
+        next: usize,
+    }
+
+    impl RecentSteps {
+        pub fn new(capacity: usize) -> Self {
+            RecentSteps {
+                entries: Vec::with_capacity(capacity),
+                capacity,
+                next: 0,
+            }
+        }
+
+        pub(crate) fn record(&mut self, step: RecentStep) {
+            if self.entries.len() < self.capacity {
+                self.entries.push(step);
+            } else if let Some(entry) = self.entries.get_mut(self.next) {
+                *entry = step;
+                self.next = (self.next + 1) % self.capacity;
+            }
+        }
+
+        /// Iterates over the recorded steps, from the oldest to the most recent one
+        pub fn iter(&self) -> impl Iterator<Item = &RecentStep> {
+            let (newest, oldest) = self.entries.split_at(self.next);
+            oldest.iter().chain(newest)
+        }
+
+        pub fn len(&self) -> usize {
+            self.entries.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.entries.is_empty()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::*;
+
+        fn step(offset: usize) -> RecentStep {
+            RecentStep {
+                pc: (0, offset).into(),
+                ap: offset,
+                fp: 0,
+                opcode: Opcode::NOp,
+            }
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn recent_steps_keeps_last_entries() {
+            let mut recent_steps = RecentSteps::new(3);
+            for offset in 0..5 {
+                recent_steps.record(step(offset));
+            }
+            assert_eq!(recent_steps.len(), 3);
+            assert_eq!(
+                recent_steps.iter().copied().collect::<Vec<_>>(),
+                vec![step(2), step(3), step(4)]
+            );
+            // Recording doesn't grow the buffer past its initial allocation
+            assert_eq!(recent_steps.entries.capacity(), 3);
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn recent_steps_not_full() {
+            let mut recent_steps = RecentSteps::new(3);
+            recent_steps.record(step(0));
+            recent_steps.record(step(1));
+            assert_eq!(
+                recent_steps.iter().copied().collect::<Vec<_>>(),
+                vec![step(0), step(1)]
+            );
+        }
+
+        #[test]
+        #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+        fn recent_steps_zero_capacity() {
+            let mut recent_steps = RecentSteps::new(0);
+            recent_steps.record(step(0));
+            assert!(recent_steps.is_empty());
+        }
+    }
+}
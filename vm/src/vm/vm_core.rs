@@ -5,12 +5,9 @@ use crate::types::program::HintRange;
 use crate::{
     hint_processor::hint_processor_definition::HintProcessor,
     types::{
-        errors::math_errors::MathError,
         exec_scope::ExecutionScopes,
-        instruction::{
-            is_call_instruction, ApUpdate, FpUpdate, Instruction, Opcode, PcUpdate, Res,
-        },
-        relocatable::{MaybeRelocatable, Relocatable},
+        instruction::{is_call_instruction, Instruction},
+        relocatable::{relocate_address, MaybeRelocatable, Relocatable},
     },
     vm::{
         context::run_context::RunContext,
@@ -19,8 +16,14 @@ use crate::{
             exec_scope_errors::ExecScopeError, memory_errors::MemoryError,
             vm_errors::VirtualMachineError,
         },
-        runners::builtin_runner::{BuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner},
-        trace::trace_entry::TraceEntry,
+        runners::{
+            builtin_runner::{BuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner},
+            cairo_pie::SegmentInfo,
+        },
+        trace::{
+            recent_steps::{RecentStep, RecentSteps},
+            trace_entry::TraceEntry,
+        },
         vm_memory::memory_segments::MemorySegmentManager,
     },
 };
@@ -32,55 +35,18 @@ use core::num::NonZeroUsize;
 use num_traits::{ToPrimitive, Zero};
 
 use super::errors::runner_errors::RunnerError;
+use super::execution::{compute_operands, execute_instruction, MemoryView, StepOutcome};
+pub use super::execution::{DeducedOperands, Operands, OperandsAddresses};
 use super::runners::builtin_runner::OUTPUT_BUILTIN_NAME;
 
 const MAX_TRACEBACK_ENTRIES: u32 = 20;
 
-#[derive(PartialEq, Eq, Debug)]
-pub struct Operands {
-    dst: MaybeRelocatable,
-    res: Option<MaybeRelocatable>,
-    op0: MaybeRelocatable,
-    op1: MaybeRelocatable,
-}
-
-#[derive(PartialEq, Eq, Debug)]
-pub struct OperandsAddresses {
-    dst_addr: Relocatable,
-    op0_addr: Relocatable,
-    op1_addr: Relocatable,
-}
-
-#[derive(Default, Debug, Clone, Copy)]
-pub struct DeducedOperands(u8);
-
-impl DeducedOperands {
-    fn set_dst(&mut self, value: bool) {
-        self.0 |= value as u8;
-    }
-    fn set_op0(&mut self, value: bool) {
-        self.0 |= (value as u8) << 1;
-    }
-    fn set_op1(&mut self, value: bool) {
-        self.0 |= (value as u8) << 2;
-    }
-
-    fn was_dest_deducted(&self) -> bool {
-        self.0 & 1 != 0
-    }
-    fn was_op0_deducted(&self) -> bool {
-        self.0 & 1 << 1 != 0
-    }
-    fn was_op1_deducted(&self) -> bool {
-        self.0 & 1 << 2 != 0
-    }
-}
-
 pub struct VirtualMachine {
     pub(crate) run_context: RunContext,
     pub builtin_runners: Vec<BuiltinRunner>,
     pub segments: MemorySegmentManager,
     pub(crate) trace: Option<Vec<TraceEntry>>,
+    pub(crate) recent_steps: Option<RecentSteps>,
     pub(crate) current_step: usize,
     pub(crate) rc_limits: Option<(isize, isize)>,
     skip_instruction_execution: bool,
@@ -109,6 +75,7 @@ impl VirtualMachine {
             run_context,
             builtin_runners: Vec::new(),
             trace,
+            recent_steps: None,
             current_step: 0,
             skip_instruction_execution: false,
             segments: MemorySegmentManager::new(),
@@ -125,164 +92,6 @@ impl VirtualMachine {
         self.segments.compute_effective_sizes();
     }
 
-    fn update_fp(
-        &mut self,
-        instruction: &Instruction,
-        operands: &Operands,
-    ) -> Result<(), VirtualMachineError> {
-        let new_fp_offset: usize = match instruction.fp_update {
-            FpUpdate::APPlus2 => self.run_context.ap + 2,
-            FpUpdate::Dst => match operands.dst {
-                MaybeRelocatable::RelocatableValue(ref rel) => rel.offset,
-                MaybeRelocatable::Int(ref num) => num
-                    .to_usize()
-                    .ok_or_else(|| MathError::Felt252ToUsizeConversion(Box::new(*num)))?,
-            },
-            FpUpdate::Regular => return Ok(()),
-        };
-        self.run_context.fp = new_fp_offset;
-        Ok(())
-    }
-
-    fn update_ap(
-        &mut self,
-        instruction: &Instruction,
-        operands: &Operands,
-    ) -> Result<(), VirtualMachineError> {
-        let new_apset: usize = match instruction.ap_update {
-            ApUpdate::Add => match &operands.res {
-                Some(res) => (self.run_context.get_ap() + res)?.offset,
-                None => return Err(VirtualMachineError::UnconstrainedResAdd),
-            },
-            ApUpdate::Add1 => self.run_context.ap + 1,
-            ApUpdate::Add2 => self.run_context.ap + 2,
-            ApUpdate::Regular => return Ok(()),
-        };
-        self.run_context.ap = new_apset;
-        Ok(())
-    }
-
-    fn update_pc(
-        &mut self,
-        instruction: &Instruction,
-        operands: &Operands,
-    ) -> Result<(), VirtualMachineError> {
-        let new_pc: Relocatable = match instruction.pc_update {
-            PcUpdate::Regular => (self.run_context.pc + instruction.size())?,
-            PcUpdate::Jump => match operands.res.as_ref().and_then(|x| x.get_relocatable()) {
-                Some(ref res) => *res,
-                None => return Err(VirtualMachineError::UnconstrainedResJump),
-            },
-            PcUpdate::JumpRel => match &operands.res {
-                Some(res) => match res {
-                    MaybeRelocatable::Int(num_res) => (self.run_context.pc + num_res)?,
-                    _ => return Err(VirtualMachineError::JumpRelNotInt),
-                },
-                None => return Err(VirtualMachineError::UnconstrainedResJumpRel),
-            },
-            PcUpdate::Jnz => match VirtualMachine::is_zero(&operands.dst) {
-                true => (self.run_context.pc + instruction.size())?,
-                false => (self.run_context.pc + &operands.op1)?,
-            },
-        };
-        self.run_context.pc = new_pc;
-        Ok(())
-    }
-
-    fn update_registers(
-        &mut self,
-        instruction: &Instruction,
-        operands: Operands,
-    ) -> Result<(), VirtualMachineError> {
-        self.update_fp(instruction, &operands)?;
-        self.update_ap(instruction, &operands)?;
-        self.update_pc(instruction, &operands)?;
-        Ok(())
-    }
-
-    /// Returns true if the value is zero
-    /// Used for JNZ instructions
-    fn is_zero(addr: &MaybeRelocatable) -> bool {
-        match addr {
-            MaybeRelocatable::Int(num) => num.is_zero(),
-            _ => false,
-        }
-    }
-
-    ///Returns a tuple (deduced_op0, deduced_res).
-    ///Deduces the value of op0 if possible (based on dst and op1). Otherwise, returns None.
-    ///If res was already deduced, returns its deduced value as well.
-    fn deduce_op0(
-        &self,
-        instruction: &Instruction,
-        dst: Option<&MaybeRelocatable>,
-        op1: Option<&MaybeRelocatable>,
-    ) -> Result<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError> {
-        match instruction.opcode {
-            Opcode::Call => Ok((
-                Some(MaybeRelocatable::from(
-                    (self.run_context.pc + instruction.size())?,
-                )),
-                None,
-            )),
-            Opcode::AssertEq => match (&instruction.res, dst, op1) {
-                (Res::Add, Some(dst_addr), Some(op1_addr)) => {
-                    Ok((Some(dst_addr.sub(op1_addr)?), dst.cloned()))
-                }
-                (
-                    Res::Mul,
-                    Some(MaybeRelocatable::Int(num_dst)),
-                    Some(MaybeRelocatable::Int(num_op1)),
-                ) if !num_op1.is_zero() => Ok((
-                    Some(MaybeRelocatable::Int(num_dst.field_div(
-                        &num_op1.try_into().map_err(|_| MathError::DividedByZero)?,
-                    ))),
-                    dst.cloned(),
-                )),
-                _ => Ok((None, None)),
-            },
-            _ => Ok((None, None)),
-        }
-    }
-
-    /// Returns a tuple (deduced_op1, deduced_res).
-    ///Deduces the value of op1 if possible (based on dst and op0). Otherwise, returns None.
-    ///If res was already deduced, returns its deduced value as well.
-    fn deduce_op1(
-        &self,
-        instruction: &Instruction,
-        dst: Option<&MaybeRelocatable>,
-        op0: Option<MaybeRelocatable>,
-    ) -> Result<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError> {
-        if let Opcode::AssertEq = instruction.opcode {
-            match instruction.res {
-                Res::Op1 => return Ok((dst.cloned(), dst.cloned())),
-                Res::Add => {
-                    return Ok((
-                        dst.zip(op0).and_then(|(dst, op0)| dst.sub(&op0).ok()),
-                        dst.cloned(),
-                    ))
-                }
-                Res::Mul => match (dst, op0) {
-                    (
-                        Some(MaybeRelocatable::Int(num_dst)),
-                        Some(MaybeRelocatable::Int(num_op0)),
-                    ) if !num_op0.is_zero() => {
-                        return Ok((
-                            Some(MaybeRelocatable::Int(num_dst.field_div(
-                                &num_op0.try_into().map_err(|_| MathError::DividedByZero)?,
-                            ))),
-                            dst.cloned(),
-                        ))
-                    }
-                    _ => (),
-                },
-                _ => (),
-            };
-        };
-        Ok((None, None))
-    }
-
     fn deduce_memory_cell(
         &self,
         address: Relocatable,
@@ -298,110 +107,26 @@ impl VirtualMachine {
         Ok(None)
     }
 
-    ///Computes the value of res if possible
-    fn compute_res(
-        &self,
-        instruction: &Instruction,
-        op0: &MaybeRelocatable,
-        op1: &MaybeRelocatable,
-    ) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
-        match instruction.res {
-            Res::Op1 => Ok(Some(op1.clone())),
-            Res::Add => Ok(Some(op0.add(op1)?)),
-            Res::Mul => {
-                if let (MaybeRelocatable::Int(num_op0), MaybeRelocatable::Int(num_op1)) = (op0, op1)
-                {
-                    return Ok(Some(MaybeRelocatable::Int(num_op0 * num_op1)));
-                }
-                Err(VirtualMachineError::ComputeResRelocatableMul(Box::new((
-                    op0.clone(),
-                    op1.clone(),
-                ))))
-            }
-            Res::Unconstrained => Ok(None),
-        }
-    }
-
-    fn deduce_dst(
-        &self,
-        instruction: &Instruction,
-        res: &Option<MaybeRelocatable>,
-    ) -> Result<MaybeRelocatable, VirtualMachineError> {
-        let dst = match (instruction.opcode, res) {
-            (Opcode::AssertEq, Some(res)) => res.clone(),
-            (Opcode::Call, _) => MaybeRelocatable::from(self.run_context.get_fp()),
-            _ => return Err(VirtualMachineError::NoDst),
-        };
-        Ok(dst)
-    }
-
-    fn opcode_assertions(
-        &self,
-        instruction: &Instruction,
-        operands: &Operands,
-    ) -> Result<(), VirtualMachineError> {
-        match instruction.opcode {
-            Opcode::AssertEq => match &operands.res {
-                None => Err(VirtualMachineError::UnconstrainedResAssertEq),
-                Some(res) if res != &operands.dst => Err(VirtualMachineError::DiffAssertValues(
-                    Box::new((operands.dst.clone(), res.clone())),
-                )),
-                _ => Ok(()),
-            },
-            Opcode::Call => {
-                let return_pc = MaybeRelocatable::from((self.run_context.pc + instruction.size())?);
-                if operands.op0 != return_pc {
-                    return Err(VirtualMachineError::CantWriteReturnPc(Box::new((
-                        operands.op0.clone(),
-                        return_pc,
-                    ))));
-                };
-
-                if MaybeRelocatable::from(self.run_context.get_fp()) != operands.dst {
-                    return Err(VirtualMachineError::CantWriteReturnFp(Box::new((
-                        operands.dst.clone(),
-                        MaybeRelocatable::from(self.run_context.get_fp()),
-                    ))));
-                };
-                Ok(())
-            }
-            _ => Ok(()),
-        }
-    }
-
-    fn insert_deduced_operands(
-        &mut self,
-        deduced_operands: DeducedOperands,
-        operands: &Operands,
-        operands_addresses: &OperandsAddresses,
-    ) -> Result<(), VirtualMachineError> {
-        if deduced_operands.was_op0_deducted() {
-            self.segments
-                .memory
-                .insert(operands_addresses.op0_addr, &operands.op0)
-                .map_err(VirtualMachineError::Memory)?;
-        }
-        if deduced_operands.was_op1_deducted() {
-            self.segments
-                .memory
-                .insert(operands_addresses.op1_addr, &operands.op1)
-                .map_err(VirtualMachineError::Memory)?;
-        }
-        if deduced_operands.was_dest_deducted() {
-            self.segments
-                .memory
-                .insert(operands_addresses.dst_addr, &operands.dst)
-                .map_err(VirtualMachineError::Memory)?;
+    fn run_instruction(&mut self, instruction: &Instruction) -> Result<(), VirtualMachineError> {
+        if let Some(ref mut recent_steps) = &mut self.recent_steps {
+            recent_steps.record(RecentStep {
+                pc: self.run_context.pc,
+                ap: self.run_context.ap,
+                fp: self.run_context.fp,
+                opcode: instruction.opcode,
+            });
         }
 
-        Ok(())
-    }
-
-    fn run_instruction(&mut self, instruction: &Instruction) -> Result<(), VirtualMachineError> {
-        let (operands, operands_addresses, deduced_operands) =
-            self.compute_operands(instruction)?;
-        self.insert_deduced_operands(deduced_operands, &operands, &operands_addresses)?;
-        self.opcode_assertions(instruction, &operands)?;
+        // The registers are moved out of the vm while the instruction is executed, as the vm
+        // itself is the memory being accessed
+        let mut registers = RunContext::new(
+            self.run_context.pc,
+            self.run_context.ap,
+            self.run_context.fp,
+        );
+        let StepOutcome {
+            operands_addresses, ..
+        } = execute_instruction(self, &mut registers, instruction)?;
 
         if let Some(ref mut trace) = &mut self.trace {
             trace.push(TraceEntry {
@@ -434,7 +159,7 @@ impl VirtualMachine {
             .memory
             .mark_as_accessed(operands_addresses.op1_addr);
 
-        self.update_registers(instruction, operands)?;
+        self.run_context = registers;
         self.current_step += 1;
 
         Ok(())
@@ -567,116 +292,13 @@ impl VirtualMachine {
         Ok(())
     }
 
-    fn compute_op0_deductions(
-        &self,
-        op0_addr: Relocatable,
-        res: &mut Option<MaybeRelocatable>,
-        instruction: &Instruction,
-        dst_op: &Option<MaybeRelocatable>,
-        op1_op: &Option<MaybeRelocatable>,
-    ) -> Result<MaybeRelocatable, VirtualMachineError> {
-        let op0_op = match self.deduce_memory_cell(op0_addr)? {
-            None => {
-                let op0;
-                (op0, *res) = self.deduce_op0(instruction, dst_op.as_ref(), op1_op.as_ref())?;
-                op0
-            }
-            deduced_memory_cell => deduced_memory_cell,
-        };
-        let op0 = op0_op.ok_or_else(|| {
-            VirtualMachineError::FailedToComputeOperands(Box::new(("op0".to_string(), op0_addr)))
-        })?;
-        Ok(op0)
-    }
-
-    fn compute_op1_deductions(
-        &self,
-        op1_addr: Relocatable,
-        res: &mut Option<MaybeRelocatable>,
-        instruction: &Instruction,
-        dst_op: &Option<MaybeRelocatable>,
-        op0: &MaybeRelocatable,
-    ) -> Result<MaybeRelocatable, VirtualMachineError> {
-        let op1_op = match self.deduce_memory_cell(op1_addr)? {
-            None => {
-                let (op1, deduced_res) =
-                    self.deduce_op1(instruction, dst_op.as_ref(), Some(op0.clone()))?;
-                if res.is_none() {
-                    *res = deduced_res
-                }
-                op1
-            }
-            deduced_memory_cell => deduced_memory_cell,
-        };
-        let op1 = op1_op.ok_or_else(|| {
-            VirtualMachineError::FailedToComputeOperands(Box::new(("op1".to_string(), op1_addr)))
-        })?;
-        Ok(op1)
-    }
-
     /// Compute operands and result, trying to deduce them if normal memory access returns a None
     /// value.
     pub fn compute_operands(
         &self,
         instruction: &Instruction,
     ) -> Result<(Operands, OperandsAddresses, DeducedOperands), VirtualMachineError> {
-        //Get operands from memory
-        let dst_addr = self.run_context.compute_dst_addr(instruction)?;
-        let dst_op = self.segments.memory.get(&dst_addr).map(Cow::into_owned);
-
-        let op0_addr = self.run_context.compute_op0_addr(instruction)?;
-        let op0_op = self.segments.memory.get(&op0_addr).map(Cow::into_owned);
-
-        let op1_addr = self
-            .run_context
-            .compute_op1_addr(instruction, op0_op.as_ref())?;
-        let op1_op = self.segments.memory.get(&op1_addr).map(Cow::into_owned);
-
-        let mut res: Option<MaybeRelocatable> = None;
-
-        let mut deduced_operands = DeducedOperands::default();
-
-        //Deduce op0 if it wasnt previously computed
-        let op0 = match op0_op {
-            Some(op0) => op0,
-            None => {
-                deduced_operands.set_op0(true);
-                self.compute_op0_deductions(op0_addr, &mut res, instruction, &dst_op, &op1_op)?
-            }
-        };
-
-        //Deduce op1 if it wasnt previously computed
-        let op1 = match op1_op {
-            Some(op1) => op1,
-            None => {
-                deduced_operands.set_op1(true);
-                self.compute_op1_deductions(op1_addr, &mut res, instruction, &dst_op, &op0)?
-            }
-        };
-
-        //Compute res if it wasnt previously deduced
-        if res.is_none() {
-            res = self.compute_res(instruction, &op0, &op1)?;
-        }
-
-        //Deduce dst if it wasnt previously computed
-        let dst = match dst_op {
-            Some(dst) => dst,
-            None => {
-                deduced_operands.set_dst(true);
-                self.deduce_dst(instruction, &res)?
-            }
-        };
-        let accessed_addresses = OperandsAddresses {
-            dst_addr,
-            op0_addr,
-            op1_addr,
-        };
-        Ok((
-            Operands { dst, op0, op1, res },
-            accessed_addresses,
-            deduced_operands,
-        ))
+        compute_operands(self, &self.run_context, instruction)
     }
 
     ///Makes sure that all assigned memory cells are consistent with their auto deduction rules.
@@ -814,6 +436,12 @@ impl VirtualMachine {
         self.segments.add()
     }
 
+    /// Adds a new segment, failing if it exceeds `MemoryLimits::max_segments`.
+    /// Hints should use this instead of [`Self::add_memory_segment`].
+    pub fn try_add_memory_segment(&mut self) -> Result<Relocatable, MemoryError> {
+        self.segments.try_add()
+    }
+
     pub fn get_ap(&self) -> Relocatable {
         self.run_context.get_ap()
     }
@@ -899,6 +527,43 @@ impl VirtualMachine {
         self.segments.memory.get_continuous_range(addr, n_ret)
     }
 
+    ///Extracts the return data of an entrypoint following the Cairo 1 convention, where the last
+    ///three return values are the panic flag and the start and end pointers of the retdata.
+    ///Returns the retdata, or a `PanicData` error containing it if the panic flag is set.
+    pub fn extract_return_data(&self) -> Result<Vec<Felt252>, VirtualMachineError> {
+        let addr = (self.run_context.get_ap() - 3)
+            .map_err(|_| MemoryError::FailedToGetReturnValues(Box::new((3, self.get_ap()))))?;
+        let panic_flag = self.get_integer(addr)?.into_owned();
+        let retdata_start = self.get_relocatable((addr + 1_usize)?)?;
+        let retdata_end = self.get_relocatable((addr + 2_usize)?)?;
+        let used_size = usize::try_from(retdata_start.segment_index)
+            .ok()
+            .and_then(|index| {
+                self.segments
+                    .get_segment_used_size(index)
+                    .or_else(|| self.segments.memory.data.get(index).map(|s| s.len()))
+            });
+        if retdata_start.segment_index != retdata_end.segment_index
+            || retdata_start.offset > retdata_end.offset
+            || used_size.map_or(true, |size| retdata_end.offset > size)
+        {
+            return Err(VirtualMachineError::InvalidRetdataPointers(Box::new((
+                retdata_start,
+                retdata_end,
+            ))));
+        }
+        let retdata = self
+            .get_integer_range(retdata_start, retdata_end.offset - retdata_start.offset)?
+            .into_iter()
+            .map(Cow::into_owned)
+            .collect();
+        if panic_flag.is_zero() {
+            Ok(retdata)
+        } else {
+            Err(VirtualMachineError::PanicData(retdata))
+        }
+    }
+
     ///Gets n elements from memory starting from addr (n being size)
     pub fn get_range(&self, addr: Relocatable, size: usize) -> Vec<Option<Cow<MaybeRelocatable>>> {
         self.segments.memory.get_range(addr, size)
@@ -946,6 +611,16 @@ impl VirtualMachine {
         self.trace = None
     }
 
+    /// Keeps track of the last `capacity` steps executed (registers and opcode), which are included
+    /// in the errors raised during the run. Unlike the trace, this is cheap enough to be kept enabled.
+    pub fn enable_recent_steps(&mut self, capacity: usize) {
+        self.recent_steps = Some(RecentSteps::new(capacity))
+    }
+
+    pub fn get_recent_steps(&self) -> Option<&RecentSteps> {
+        self.recent_steps.as_ref()
+    }
+
     #[cfg(feature = "with_tracer")]
     pub fn relocate_segments(&self) -> Result<Vec<usize>, MemoryError> {
         self.segments.relocate_segments()
@@ -983,6 +658,11 @@ impl VirtualMachine {
         self.segments.add_temporary_segment()
     }
 
+    /// Adds a new temporary segment, failing if it exceeds `MemoryLimits::max_segments`.
+    pub fn try_add_temporary_segment(&mut self) -> Result<Relocatable, MemoryError> {
+        self.segments.try_add_temporary_segment()
+    }
+
     /// Add a new relocation rule.
     ///
     /// Will return an error if any of the following conditions are not met:
@@ -1038,6 +718,41 @@ impl VirtualMachine {
         Ok(())
     }
 
+    /// Returns the relocation table computed by [CairoRunner::relocate](crate::vm::runners::cairo_runner::CairoRunner::relocate),
+    /// which maps each segment index to the relocated address of its first cell.
+    /// Returns None if neither memory nor the trace were relocated.
+    pub fn get_relocation_table(&self) -> Option<&[usize]> {
+        self.relocation_table.as_deref()
+    }
+
+    /// Returns the relocated (flat) address of `addr`.
+    pub fn relocate_address(&self, addr: Relocatable) -> Result<usize, MemoryError> {
+        let relocation_table = self
+            .relocation_table
+            .as_ref()
+            .ok_or(MemoryError::UnrelocatedMemory)?;
+        relocate_address(addr, relocation_table)
+    }
+
+    /// Returns the segment and offset that were relocated to the flat address `addr`.
+    pub fn unrelocate_address(&self, addr: usize) -> Result<Relocatable, MemoryError> {
+        let relocation_table = self
+            .relocation_table
+            .as_ref()
+            .ok_or(MemoryError::UnrelocatedMemory)?;
+        // Empty segments share their base with the next one, so look for the last segment
+        // starting at or before addr
+        let segment_index = relocation_table
+            .partition_point(|base| *base <= addr)
+            .checked_sub(1)
+            .ok_or(MemoryError::AddressNotRelocated(addr))?;
+        let offset = addr - relocation_table[segment_index];
+        match self.segments.get_segment_size(segment_index) {
+            Some(size) if offset < size => Ok((segment_index as isize, offset).into()),
+            _ => Err(MemoryError::AddressNotRelocated(addr)),
+        }
+    }
+
     /// Returns a list of addresses of memory cells that constitute the public memory.
     pub fn get_public_memory_addresses(&self) -> Result<Vec<(usize, usize)>, VirtualMachineError> {
         if let Some(relocation_table) = &self.relocation_table {
@@ -1057,23 +772,20 @@ impl VirtualMachine {
             .as_ref()
             .ok_or(MemoryError::UnrelocatedMemory)?;
 
-        let relocate = |segment: (usize, usize)| -> Result<(usize, usize), VirtualMachineError> {
-            let (index, stop_ptr_offset) = segment;
+        let relocate = |segment: SegmentInfo| -> Result<(usize, usize), VirtualMachineError> {
+            let index = segment.index as usize;
             let base = relocation_table
                 .get(index)
                 .ok_or(VirtualMachineError::RelocationNotFound(index))?;
-            Ok((*base, base + stop_ptr_offset))
+            Ok((*base, base + segment.size))
         };
 
         self.builtin_runners
             .iter()
+            // Custom builtins are not part of the layout, so they are left out of the public input
+            .filter(|builtin| !matches!(builtin, BuiltinRunner::Custom(_)))
             .map(|builtin| -> Result<_, VirtualMachineError> {
-                let addresses =
-                    if let (base, Some(stop_ptr)) = builtin.get_memory_segment_addresses() {
-                        (base, stop_ptr)
-                    } else {
-                        return Err(RunnerError::NoStopPointer(Box::new(builtin.name())).into());
-                    };
+                let addresses = builtin.get_segment_info()?;
 
                 Ok((
                     builtin.name().strip_suffix("_builtin").unwrap_or_default(),
@@ -1115,6 +827,36 @@ impl VirtualMachine {
     }
 }
 
+/// The vm's memory as seen by [`execute_instruction`]: empty cells of builtin segments are deduced
+/// by their builtin runner
+impl MemoryView for VirtualMachine {
+    fn get(&self, addr: Relocatable) -> Option<MaybeRelocatable> {
+        self.segments.memory.get(&addr).map(Cow::into_owned)
+    }
+
+    fn insert(&mut self, addr: Relocatable, value: &MaybeRelocatable) -> Result<(), MemoryError> {
+        self.segments.memory.insert(addr, value)
+    }
+
+    fn deduce(&self, addr: Relocatable) -> Result<Option<MaybeRelocatable>, VirtualMachineError> {
+        self.deduce_memory_cell(addr)
+    }
+
+    /// The output builtin has no deduction rule, so an operand in its segment can only be missing
+    /// if the program reads an output cell it never wrote.
+    fn failed_to_compute_operand(&self, operand: &str, addr: Relocatable) -> VirtualMachineError {
+        let is_output_cell = self.builtin_runners.iter().any(|builtin| {
+            matches!(builtin, BuiltinRunner::Output(_))
+                && builtin.base() as isize == addr.segment_index
+        });
+        if is_output_cell {
+            MemoryError::UnknownMemoryCellOutputBuiltin(Box::new(addr)).into()
+        } else {
+            VirtualMachineError::FailedToComputeOperands(Box::new((operand.to_string(), addr)))
+        }
+    }
+}
+
 pub struct VirtualMachineBuilder {
     pub(crate) run_context: RunContext,
     pub(crate) builtin_runners: Vec<BuiltinRunner>,
@@ -1194,11 +936,54 @@ impl VirtualMachineBuilder {
         self
     }
 
+    /// Adds `builtin` to the builtin runners of the VM. Its segments are not initialized.
+    pub fn with_builtin(mut self, builtin: BuiltinRunner) -> VirtualMachineBuilder {
+        self.builtin_runners.push(builtin);
+        self
+    }
+
+    /// Inserts each value into its address, adding the segments (or temporary segments) needed
+    /// to hold them.
+    pub fn with_memory(
+        mut self,
+        entries: impl IntoIterator<Item = (Relocatable, MaybeRelocatable)>,
+    ) -> Result<VirtualMachineBuilder, MemoryError> {
+        for (addr, value) in entries {
+            if addr.segment_index < 0 {
+                while self.segments.num_temp_segments() < (-addr.segment_index) as usize {
+                    self.segments.add_temporary_segment();
+                }
+            } else {
+                while self.segments.num_segments() <= addr.segment_index as usize {
+                    self.segments.add();
+                }
+            }
+            self.segments.memory.insert(addr, value)?;
+        }
+        Ok(self)
+    }
+
+    pub fn with_run_context(
+        mut self,
+        ap: usize,
+        fp: usize,
+        pc: Relocatable,
+    ) -> VirtualMachineBuilder {
+        self.run_context = RunContext { pc, ap, fp };
+        self
+    }
+
+    pub fn with_trace_enabled(mut self) -> VirtualMachineBuilder {
+        self.trace = Some(Vec::new());
+        self
+    }
+
     pub fn build(self) -> VirtualMachine {
         VirtualMachine {
             run_context: self.run_context,
             builtin_runners: self.builtin_runners,
             trace: self.trace,
+            recent_steps: None,
             current_step: self.current_step,
             skip_instruction_execution: self.skip_instruction_execution,
             segments: self.segments,
@@ -1217,7 +1002,12 @@ mod tests {
     use super::*;
     use crate::felt_hex;
     use crate::stdlib::collections::HashMap;
+    use crate::types::instruction::{ApUpdate, FpUpdate, Opcode, PcUpdate, Res};
     use crate::types::program::Program;
+    use crate::vm::execution::{
+        compute_res, deduce_dst, deduce_op0, deduce_op1, is_zero, opcode_assertions, update_ap,
+        update_fp, update_pc, update_registers,
+    };
     use crate::vm::runners::builtin_runner::{
         BITWISE_BUILTIN_NAME, EC_OP_BUILTIN_NAME, HASH_BUILTIN_NAME,
     };
@@ -1275,7 +1065,7 @@ mod tests {
         let mut vm = vm!();
         run_context!(vm, 4, 5, 6);
         assert_matches!(
-            vm.update_fp(&instruction, &operands),
+            update_fp(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.fp, 7)
@@ -1308,7 +1098,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_fp(&instruction, &operands),
+            update_fp(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.fp, 6)
@@ -1341,7 +1131,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_fp(&instruction, &operands),
+            update_fp(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.fp, 0)
@@ -1375,7 +1165,7 @@ mod tests {
         run_context!(vm, 4, 5, 6);
 
         assert_matches!(
-            vm.update_fp(&instruction, &operands),
+            update_fp(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.fp, 11)
@@ -1411,7 +1201,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_ap(&instruction, &operands),
+            update_ap(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.ap, 13);
@@ -1447,7 +1237,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_ap(&instruction, &operands),
+            update_ap(&mut vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::UnconstrainedResAdd)
         );
     }
@@ -1482,7 +1272,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_ap(&instruction, &operands),
+            update_ap(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.ap, 6);
@@ -1518,7 +1308,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_ap(&instruction, &operands),
+            update_ap(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.ap, 7);
@@ -1554,7 +1344,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_ap(&instruction, &operands),
+            update_ap(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.ap, 5);
@@ -1587,7 +1377,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 1)));
@@ -1620,7 +1410,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 2)));
@@ -1653,7 +1443,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 8)));
@@ -1689,7 +1479,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::UnconstrainedResJump)
         );
     }
@@ -1722,7 +1512,7 @@ mod tests {
         run_context!(vm, 1, 1, 1);
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 9)));
@@ -1755,7 +1545,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::UnconstrainedResJumpRel)
         );
     }
@@ -1786,7 +1576,7 @@ mod tests {
 
         let mut vm = vm!();
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Err::<(), VirtualMachineError>(VirtualMachineError::JumpRelNotInt)
         );
     }
@@ -1818,7 +1608,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 1)));
@@ -1851,7 +1641,7 @@ mod tests {
         let mut vm = vm!();
 
         assert_matches!(
-            vm.update_pc(&instruction, &operands),
+            update_pc(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 10)));
@@ -1887,7 +1677,7 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.update_registers(&instruction, operands),
+            update_registers(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 5)));
@@ -1923,7 +1713,7 @@ mod tests {
         run_context!(vm, 4, 5, 6);
 
         assert_matches!(
-            vm.update_registers(&instruction, operands),
+            update_registers(&mut vm.run_context, &instruction, &operands),
             Ok::<(), VirtualMachineError>(())
         );
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 12)));
@@ -1935,14 +1725,14 @@ mod tests {
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn is_zero_int_value() {
         let value = MaybeRelocatable::Int(Felt252::from(1));
-        assert!(!VirtualMachine::is_zero(&value));
+        assert!(!is_zero(&value));
     }
 
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn is_zero_relocatable_value() {
         let value = MaybeRelocatable::from((1, 2));
-        assert!(!VirtualMachine::is_zero(&value));
+        assert!(!is_zero(&value));
     }
 
     #[test]
@@ -1965,7 +1755,7 @@ mod tests {
         let vm = vm!();
 
         assert_matches!(
-            vm.deduce_op0(&instruction, None, None),
+            deduce_op0(&vm.run_context, &instruction, None, None),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 Some(x),
                 None
@@ -1996,7 +1786,7 @@ mod tests {
         let op1 = MaybeRelocatable::Int(Felt252::from(2));
 
         assert_matches!(
-            vm.deduce_op0(&instruction, Some(&dst), Some(&op1)),
+            deduce_op0(&vm.run_context, &instruction, Some(&dst), Some(&op1)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 x,
                 y
@@ -2025,7 +1815,7 @@ mod tests {
         let vm = vm!();
 
         assert_matches!(
-            vm.deduce_op0(&instruction, None, None),
+            deduce_op0(&vm.run_context, &instruction, None, None),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2055,7 +1845,7 @@ mod tests {
         let op1 = MaybeRelocatable::Int(Felt252::from(2));
 
         assert_matches!(
-            vm.deduce_op0(&instruction, Some(&dst), Some(&op1)),
+            deduce_op0(&vm.run_context, &instruction, Some(&dst), Some(&op1)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 Some(x),
                 Some(y)
@@ -2086,7 +1876,7 @@ mod tests {
         let dst = MaybeRelocatable::Int(Felt252::from(4));
         let op1 = MaybeRelocatable::Int(Felt252::from(0));
         assert_matches!(
-            vm.deduce_op0(&instruction, Some(&dst), Some(&op1)),
+            deduce_op0(&vm.run_context, &instruction, Some(&dst), Some(&op1)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2115,7 +1905,7 @@ mod tests {
         let dst = MaybeRelocatable::Int(Felt252::from(4));
         let op1 = MaybeRelocatable::Int(Felt252::from(0));
         assert_matches!(
-            vm.deduce_op0(&instruction, Some(&dst), Some(&op1)),
+            deduce_op0(&vm.run_context, &instruction, Some(&dst), Some(&op1)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2145,7 +1935,7 @@ mod tests {
         let op1 = MaybeRelocatable::Int(Felt252::from(0));
 
         assert_matches!(
-            vm.deduce_op0(&instruction, Some(&dst), Some(&op1)),
+            deduce_op0(&vm.run_context, &instruction, Some(&dst), Some(&op1)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2169,10 +1959,8 @@ mod tests {
             opcode: Opcode::Call,
         };
 
-        let vm = vm!();
-
         assert_matches!(
-            vm.deduce_op1(&instruction, None, None),
+            deduce_op1(&instruction, None, None),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2196,12 +1984,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let dst = MaybeRelocatable::Int(Felt252::from(3));
         let op0 = MaybeRelocatable::Int(Felt252::from(2));
         assert_matches!(
-            vm.deduce_op1(&instruction, Some(&dst), Some(op0)),
+            deduce_op1(&instruction, Some(&dst), Some(op0)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 x,
                 y
@@ -2227,9 +2013,8 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
         assert_matches!(
-            vm.deduce_op1(&instruction, None, None),
+            deduce_op1(&instruction, None, None),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2253,12 +2038,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let dst = MaybeRelocatable::Int(Felt252::from(4));
         let op0 = MaybeRelocatable::Int(Felt252::from(2));
         assert_matches!(
-            vm.deduce_op1(&instruction, Some(&dst), Some(op0)),
+            deduce_op1(&instruction, Some(&dst), Some(op0)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 x,
                 y
@@ -2284,12 +2067,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let dst = MaybeRelocatable::Int(Felt252::from(4));
         let op0 = MaybeRelocatable::Int(Felt252::from(0));
         assert_matches!(
-            vm.deduce_op1(&instruction, Some(&dst), Some(op0)),
+            deduce_op1(&instruction, Some(&dst), Some(op0)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2313,11 +2094,9 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let op0 = MaybeRelocatable::Int(Felt252::from(0));
         assert_matches!(
-            vm.deduce_op1(&instruction, None, Some(op0)),
+            deduce_op1(&instruction, None, Some(op0)),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 None, None
             ))
@@ -2341,11 +2120,9 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let dst = MaybeRelocatable::Int(Felt252::from(7));
         assert_matches!(
-            vm.deduce_op1(&instruction, Some(&dst), None),
+            deduce_op1(&instruction, Some(&dst), None),
             Ok::<(Option<MaybeRelocatable>, Option<MaybeRelocatable>), VirtualMachineError>((
                 x,
                 y
@@ -2371,12 +2148,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let op1 = MaybeRelocatable::Int(Felt252::from(7));
         let op0 = MaybeRelocatable::Int(Felt252::from(9));
         assert_matches!(
-            vm.compute_res(&instruction, &op0, &op1),
+            compute_res(&instruction, &op0, &op1),
             Ok::<Option<MaybeRelocatable>, VirtualMachineError>(Some(MaybeRelocatable::Int(
                 x
             ))) if x == Felt252::from(7)
@@ -2400,12 +2175,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let op1 = MaybeRelocatable::Int(Felt252::from(7));
         let op0 = MaybeRelocatable::Int(Felt252::from(9));
         assert_matches!(
-            vm.compute_res(&instruction, &op0, &op1),
+            compute_res(&instruction, &op0, &op1),
             Ok::<Option<MaybeRelocatable>, VirtualMachineError>(Some(MaybeRelocatable::Int(
                 x
             ))) if x == Felt252::from(16)
@@ -2429,12 +2202,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let op1 = MaybeRelocatable::Int(Felt252::from(7));
         let op0 = MaybeRelocatable::Int(Felt252::from(9));
         assert_matches!(
-            vm.compute_res(&instruction, &op0, &op1),
+            compute_res(&instruction, &op0, &op1),
             Ok::<Option<MaybeRelocatable>, VirtualMachineError>(Some(MaybeRelocatable::Int(
                 x
             ))) if x == Felt252::from(63)
@@ -2458,12 +2229,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let op1 = MaybeRelocatable::from((2, 3));
         let op0 = MaybeRelocatable::from((2, 6));
         assert_matches!(
-            vm.compute_res(&instruction, &op0, &op1),
+            compute_res(&instruction, &op0, &op1),
             Err(VirtualMachineError::ComputeResRelocatableMul(bx)) if *bx == (op0, op1)
         );
     }
@@ -2485,12 +2254,10 @@ mod tests {
             opcode: Opcode::AssertEq,
         };
 
-        let vm = vm!();
-
         let op1 = MaybeRelocatable::Int(Felt252::from(7));
         let op0 = MaybeRelocatable::Int(Felt252::from(9));
         assert_matches!(
-            vm.compute_res(&instruction, &op0, &op1),
+            compute_res(&instruction, &op0, &op1),
             Ok::<Option<MaybeRelocatable>, VirtualMachineError>(None)
         );
     }
@@ -2517,7 +2284,7 @@ mod tests {
         let res = MaybeRelocatable::Int(Felt252::from(7));
         assert_eq!(
             MaybeRelocatable::Int(Felt252::from(7)),
-            vm.deduce_dst(&instruction, &Some(res)).unwrap()
+            deduce_dst(&vm.run_context, &instruction, &Some(res)).unwrap()
         );
     }
 
@@ -2540,7 +2307,7 @@ mod tests {
 
         let vm = vm!();
 
-        assert!(vm.deduce_dst(&instruction, &None).is_err());
+        assert!(deduce_dst(&vm.run_context, &instruction, &None).is_err());
     }
 
     #[test]
@@ -2564,7 +2331,7 @@ mod tests {
 
         assert_eq!(
             MaybeRelocatable::from((1, 0)),
-            vm.deduce_dst(&instruction, &None).unwrap()
+            deduce_dst(&vm.run_context, &instruction, &None).unwrap()
         );
     }
 
@@ -2587,7 +2354,7 @@ mod tests {
 
         let vm = vm!();
 
-        assert!(vm.deduce_dst(&instruction, &None).is_err());
+        assert!(deduce_dst(&vm.run_context, &instruction, &None).is_err());
     }
 
     #[test]
@@ -2815,7 +2582,7 @@ mod tests {
 
         let vm = vm!();
 
-        let error = vm.opcode_assertions(&instruction, &operands);
+        let error = opcode_assertions(&vm.run_context, &instruction, &operands);
         assert_matches!(error, Err(VirtualMachineError::UnconstrainedResAssertEq));
     }
 
@@ -2846,7 +2613,7 @@ mod tests {
         let vm = vm!();
 
         assert_matches!(
-            vm.opcode_assertions(&instruction, &operands),
+            opcode_assertions(&vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::DiffAssertValues(bx))
             if *bx == (MaybeRelocatable::Int(Felt252::from(9_i32)),
                  MaybeRelocatable::Int(Felt252::from(8_i32)))
@@ -2880,7 +2647,7 @@ mod tests {
         let vm = vm!();
 
         assert_matches!(
-            vm.opcode_assertions(&instruction, &operands),
+            opcode_assertions(&vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::DiffAssertValues(bx)) if *bx == (MaybeRelocatable::from((1, 1)), MaybeRelocatable::from((1, 2)))
         );
     }
@@ -2913,7 +2680,7 @@ mod tests {
         vm.run_context.pc = relocatable!(0, 4);
 
         assert_matches!(
-            vm.opcode_assertions(&instruction, &operands),
+            opcode_assertions(&vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::CantWriteReturnPc(bx)) if *bx == (mayberelocatable!(9), mayberelocatable!(0, 5))
         );
     }
@@ -2945,11 +2712,36 @@ mod tests {
         vm.run_context.fp = 6;
 
         assert_matches!(
-            vm.opcode_assertions(&instruction, &operands),
+            opcode_assertions(&vm.run_context, &instruction, &operands),
             Err(VirtualMachineError::CantWriteReturnFp(bx)) if *bx == (mayberelocatable!(8), mayberelocatable!(1, 6))
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn step_instruction_failed_register_update_keeps_registers() {
+        let mut vm = vm!(true);
+        // call abs 5
+        vm.segments = segments![
+            ((0, 0), 0x1084800180018000_i64),
+            ((0, 1), 5),
+            ((1, 0), (2, 0)),
+            ((1, 1), (3, 0))
+        ];
+        run_context!(vm, 0, 2, 2);
+
+        assert_matches!(
+            vm.step_instruction(),
+            Err(VirtualMachineError::UnconstrainedResJump)
+        );
+        assert_eq!(
+            (vm.run_context.pc, vm.run_context.ap, vm.run_context.fp),
+            (relocatable!(0, 0), 2, 2)
+        );
+        assert_eq!(vm.current_step, 0);
+        assert_eq!(vm.trace, Some(vec![]));
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /// Test for a simple program execution
@@ -3706,6 +3498,61 @@ mod tests {
         assert_eq!(vm.get_return_values(4).unwrap(), expected);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn extract_return_data_successful_call() {
+        let mut vm = vm!();
+        vm.set_ap(3);
+        vm.segments = segments![
+            ((1, 0), 0),
+            ((1, 1), (2, 0)),
+            ((1, 2), (2, 2)),
+            ((2, 0), 7),
+            ((2, 1), 8)
+        ];
+        assert_matches!(
+            vm.extract_return_data(),
+            Ok(retdata) if retdata == vec![Felt252::from(7), Felt252::from(8)]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn extract_return_data_panicking_call() {
+        let mut vm = vm!();
+        vm.set_ap(3);
+        vm.segments = segments![
+            ((1, 0), 1),
+            ((1, 1), (2, 0)),
+            ((1, 2), (2, 1)),
+            ((2, 0), 0x4f7574206f6620676173)
+        ];
+        assert_matches!(
+            vm.extract_return_data(),
+            Err(VirtualMachineError::PanicData(panic_data))
+            if panic_data == vec![Felt252::from(0x4f7574206f6620676173_u128)]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn extract_return_data_corrupted_pointers() {
+        for (start, end) in [((2, 0), (3, 0)), ((2, 1), (2, 0)), ((2, 0), (2, 3))] {
+            let mut vm = vm!();
+            vm.set_ap(3);
+            vm.segments = segments![((1, 0), 0), ((2, 0), 7), ((2, 1), 8), ((3, 0), 9)];
+            vm.insert_value((1, 1).into(), Relocatable::from(start))
+                .unwrap();
+            vm.insert_value((1, 2).into(), Relocatable::from(end))
+                .unwrap();
+            assert_matches!(
+                vm.extract_return_data(),
+                Err(VirtualMachineError::InvalidRetdataPointers(bx))
+                if *bx == (Relocatable::from(start), Relocatable::from(end))
+            );
+        }
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_return_values_fails_when_ap_is_0() {
@@ -4393,6 +4240,39 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn builder_with_methods() {
+        let vm = VirtualMachineBuilder::default()
+            .with_builtin(RangeCheckBuiltinRunner::new(Some(8), 8, true).into())
+            .with_memory(memory_entries![((2, 1), 5), ((-2, 0), (1, 3))])
+            .unwrap()
+            .with_run_context(4, 3, (0, 2).into())
+            .with_trace_enabled()
+            .build();
+
+        assert_eq!(vm.builtin_runners.len(), 1);
+        assert_matches!(vm.builtin_runners[0], BuiltinRunner::RangeCheck(_));
+        assert_eq!(vm.segments.num_segments(), 3);
+        assert_eq!(vm.segments.num_temp_segments(), 2);
+        check_memory![vm.segments.memory, ((2, 1), 5), ((-2, 0), (1, 3))];
+        assert_eq!(vm.get_ap(), (1, 4).into());
+        assert_eq!(vm.get_fp(), (1, 3).into());
+        assert_eq!(vm.get_pc(), (0, 2).into());
+        assert_eq!(vm.trace, Some(vec![]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn builder_with_memory_inconsistent_entries() {
+        assert_matches!(
+            VirtualMachineBuilder::default()
+                .with_memory(memory_entries![((1, 0), 1), ((1, 0), 2)])
+                .err(),
+            Some(MemoryError::InconsistentMemory(_))
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     /// Test for a simple program execution
@@ -1,4 +1,9 @@
-use crate::stdlib::{borrow::Cow, collections::HashMap, fmt, prelude::*};
+use crate::stdlib::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt,
+    prelude::*,
+};
 
 use crate::vm::runners::cairo_pie::CairoPieMemory;
 use crate::Felt252;
@@ -91,6 +96,18 @@ impl AddressSet {
     }
 }
 
+/// Optional caps on memory growth, used to stop programs that allocate or write without bound.
+/// All limits are disabled by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryLimits {
+    /// Maximum number of segments added while running, counted separately for real and temporary segments
+    pub max_segments: Option<usize>,
+    /// Maximum size of a single segment
+    pub max_segment_size: Option<usize>,
+    /// Maximum number of cells across all segments, memory gaps included
+    pub max_total_cells: Option<usize>,
+}
+
 pub struct Memory {
     pub(crate) data: Vec<Vec<Option<MemoryCell>>>,
     pub(crate) temp_data: Vec<Vec<Option<MemoryCell>>>,
@@ -99,6 +116,12 @@ pub struct Memory {
     pub(crate) relocation_rules: HashMap<usize, Relocatable>,
     pub validated_addresses: AddressSet,
     validation_rules: Vec<Option<ValidationRule>>,
+    pub(crate) limits: MemoryLimits,
+    // Number of cells across all segments, memory gaps included, kept up to date as segments
+    // grow so the max_total_cells limit can be checked on every write
+    pub(crate) allocated_cells: usize,
+    // Indexes of the (non-temporary) segments whose cells can't be written to anymore
+    pub(crate) read_only_segments: HashSet<usize>,
 }
 
 impl Memory {
@@ -109,6 +132,9 @@ impl Memory {
             relocation_rules: HashMap::new(),
             validated_addresses: AddressSet::new(),
             validation_rules: Vec::with_capacity(7),
+            limits: MemoryLimits::default(),
+            allocated_cells: 0,
+            read_only_segments: HashSet::new(),
         }
     }
 
@@ -116,35 +142,50 @@ impl Memory {
     /// Will return an Error if the segment index given by the address corresponds to a non-allocated segment,
     /// or if the inserted value is inconsistent with the current value at the memory cell
     /// If the address isnt contiguous with previously inserted data, memory gaps will be represented by None values
+    /// Read-only segments can't get new cells, so writing to them only succeeds if the cell already holds the same value
     pub fn insert<V>(&mut self, key: Relocatable, val: V) -> Result<(), MemoryError>
     where
         MaybeRelocatable: From<V>,
     {
         let val = MaybeRelocatable::from(val);
         let (value_index, value_offset) = from_relocatable_to_indexes(key);
-
         let data = if key.segment_index.is_negative() {
-            &mut self.temp_data
+            &self.temp_data
         } else {
-            &mut self.data
+            &self.data
         };
-
         let data_len = data.len();
         let segment = data
-            .get_mut(value_index)
+            .get(value_index)
             .ok_or_else(|| MemoryError::UnallocatedSegment(Box::new((value_index, data_len))))?;
-
+        let len = segment.len();
         //Check if the element is inserted next to the last one on the segment
         //Forgoing this check would allow data to be inserted in a different index
-        let (len, capacity) = (segment.len(), segment.capacity());
-        if len <= value_offset {
+        let new_len = if len <= value_offset {
             let new_len = value_offset
                 .checked_add(1)
                 .ok_or(MemoryError::VecCapacityExceeded)?;
+            self.check_allocation(key, new_len - len)?;
+            Some(new_len)
+        } else {
+            // Filling a memory gap allocates a cell too
+            if segment[value_offset].is_none() && self.is_read_only(key) {
+                return Err(MemoryError::WriteToReadOnlySegment(Box::new(key)));
+            }
+            None
+        };
+
+        let segment = if key.segment_index.is_negative() {
+            &mut self.temp_data[value_index]
+        } else {
+            &mut self.data[value_index]
+        };
+        if let Some(new_len) = new_len {
             segment
-                .try_reserve(new_len.saturating_sub(capacity))
+                .try_reserve(new_len.saturating_sub(segment.capacity()))
                 .map_err(|_| MemoryError::VecCapacityExceeded)?;
             segment.resize(new_len, None);
+            self.allocated_cells += new_len - len;
         }
         // At this point there's *something* in there
 
@@ -164,6 +205,38 @@ impl Memory {
         self.validate_memory_cell(key)
     }
 
+    /// Checks that the segment of `key` can grow by `new_cells` cells to fit `key`: it can't be
+    /// read-only and the memory must stay within its limits
+    fn check_allocation(&self, key: Relocatable, new_cells: usize) -> Result<(), MemoryError> {
+        if self.is_read_only(key) {
+            return Err(MemoryError::WriteToReadOnlySegment(Box::new(key)));
+        }
+        if let Some(max_segment_size) = self.limits.max_segment_size {
+            if key.offset >= max_segment_size {
+                return Err(MemoryError::SegmentSizeLimitExceeded(Box::new((
+                    key,
+                    max_segment_size,
+                ))));
+            }
+        }
+        if let Some(max_total_cells) = self.limits.max_total_cells {
+            if self.allocated_cells + new_cells > max_total_cells {
+                return Err(MemoryError::TotalCellsLimitExceeded(Box::new((
+                    key,
+                    max_total_cells,
+                ))));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_read_only(&self, key: Relocatable) -> bool {
+        !key.segment_index.is_negative()
+            && self
+                .read_only_segments
+                .contains(&(key.segment_index as usize))
+    }
+
     /// Retrieve a value from memory (either normal or temporary) and apply relocation rules
     pub(crate) fn get<'a, 'b: 'a, K: 'a>(&'b self, key: &'a K) -> Option<Cow<MaybeRelocatable>>
     where
@@ -218,6 +291,9 @@ impl Memory {
         for index in (0..self.temp_data.len()).rev() {
             if let Some(base_addr) = self.relocation_rules.get(&index) {
                 let data_segment = self.temp_data.remove(index);
+                // The relocated cells are counted again as they are inserted. Temporary segments
+                // filled without going through insert weren't counted in the first place
+                self.allocated_cells = self.allocated_cells.saturating_sub(data_segment.len());
                 // Insert the to-be relocated segment into the real memory
                 let mut addr = *base_addr;
                 if let Some(s) = self.data.get_mut(addr.segment_index as usize) {
@@ -516,40 +592,64 @@ impl Memory {
                 .count(),
         )
     }
+
+    /// Iterates over the written cells of a segment in increasing offset order, skipping memory gaps
+    /// Negative indexes refer to temporary segments
+    /// Yields nothing if the segment doesn't exist
+    pub fn iter_segment(
+        &self,
+        segment_index: isize,
+    ) -> impl Iterator<Item = (Relocatable, &MaybeRelocatable)> {
+        let (index, _) = from_relocatable_to_indexes((segment_index, 0).into());
+        let segment = if segment_index.is_negative() {
+            self.temp_data.get(index)
+        } else {
+            self.data.get(index)
+        };
+        segment.into_iter().flat_map(move |segment| {
+            segment
+                .iter()
+                .enumerate()
+                .filter_map(move |(offset, cell)| {
+                    cell.as_ref()
+                        .map(|cell| ((segment_index, offset).into(), cell.get_value()))
+                })
+        })
+    }
+
+    /// Iterates over the written cells of every segment, skipping memory gaps
+    /// If `include_temp` is set, temporary segments are yielded first (-1, -2, ...), followed by the
+    /// real segments in increasing index order
+    pub fn iter_all(
+        &self,
+        include_temp: bool,
+    ) -> impl Iterator<Item = (Relocatable, &MaybeRelocatable)> {
+        let temp_segments = if include_temp {
+            self.temp_data.len()
+        } else {
+            0
+        };
+        (1..=temp_segments as isize)
+            .map(|i| -i)
+            .chain(0..self.data.len() as isize)
+            .flat_map(move |segment_index| self.iter_segment(segment_index))
+    }
 }
 
 impl From<&Memory> for CairoPieMemory {
     fn from(mem: &Memory) -> CairoPieMemory {
-        let mut pie_memory = Vec::default();
-        for (i, segment) in mem.data.iter().enumerate() {
-            for (j, elem) in segment.iter().enumerate() {
-                if let Some(cell) = elem {
-                    pie_memory.push(((i, j), cell.get_value().clone()))
-                }
-            }
-        }
-        CairoPieMemory(pie_memory)
+        CairoPieMemory(
+            mem.iter_all(false)
+                .map(|(addr, value)| ((addr.segment_index as usize, addr.offset), value.clone()))
+                .collect(),
+        )
     }
 }
 
 impl fmt::Display for Memory {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for (i, segment) in self.temp_data.iter().enumerate() {
-            for (j, cell) in segment.iter().enumerate() {
-                if let Some(cell) = cell {
-                    let temp_segment = i + 1;
-                    let elem = cell.get_value();
-                    writeln!(f, "(-{temp_segment},{j}) : {elem}")?;
-                }
-            }
-        }
-        for (i, segment) in self.data.iter().enumerate() {
-            for (j, cell) in segment.iter().enumerate() {
-                if let Some(cell) = cell {
-                    let elem = cell.get_value();
-                    writeln!(f, "({i},{j}) : {elem}")?;
-                }
-            }
+        for (addr, elem) in self.iter_all(true) {
+            writeln!(f, "({},{}) : {elem}", addr.segment_index, addr.offset)?;
         }
         Ok(())
     }
@@ -616,8 +716,7 @@ mod memory_tests {
 
     use crate::vm::errors::memory_errors::MemoryError;
 
-    use crate::utils::test_utils::memory_from_memory;
-    use crate::utils::test_utils::memory_inner;
+    use crate::test_utils::memory;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -636,6 +735,136 @@ mod memory_tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn insert_segment_size_limit_exceeded() {
+        let mut memory = Memory::new();
+        memory.limits.max_segment_size = Some(10);
+        memory.data.push(Vec::new());
+        memory.temp_data.push(Vec::new());
+        memory.insert((0, 9).into(), &mayberelocatable!(1)).unwrap();
+        assert_matches!(
+            memory.insert((0, 10).into(), &mayberelocatable!(1)),
+            Err(MemoryError::SegmentSizeLimitExceeded(bx)) if *bx == ((0, 10).into(), 10)
+        );
+        assert_matches!(
+            memory.insert((-1, 1000).into(), &mayberelocatable!(1)),
+            Err(MemoryError::SegmentSizeLimitExceeded(bx)) if *bx == ((-1, 1000).into(), 10)
+        );
+        // Nothing was allocated for the rejected writes
+        assert_eq!(memory.data[0].len(), 10);
+        assert!(memory.temp_data[0].is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn insert_total_cells_limit_exceeded() {
+        let mut memory = Memory::new();
+        memory.limits.max_total_cells = Some(10);
+        memory.data.push(Vec::new());
+        memory.data.push(Vec::new());
+        memory.insert((0, 5).into(), &mayberelocatable!(1)).unwrap();
+        // Overwriting within already allocated cells doesn't count towards the limit
+        memory.insert((0, 2).into(), &mayberelocatable!(1)).unwrap();
+        memory.insert((1, 3).into(), &mayberelocatable!(1)).unwrap();
+        assert_matches!(
+            memory.insert((1, 4).into(), &mayberelocatable!(1)),
+            Err(MemoryError::TotalCellsLimitExceeded(bx)) if *bx == ((1, 4).into(), 10)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn allocated_cells_tracks_segment_growth() {
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        let temp = segments.add_temporary_segment();
+        segments
+            .load_data((0, 0).into(), &vec![mayberelocatable!(1); 3])
+            .unwrap();
+        // Memory gaps are allocated cells too
+        segments
+            .memory
+            .insert((0, 5).into(), &mayberelocatable!(2))
+            .unwrap();
+        segments.memory.insert(temp, &mayberelocatable!(3)).unwrap();
+        assert_eq!(segments.memory.allocated_cells, 7);
+        // Moving the temporary cell to the end of segment 0 keeps the count
+        segments
+            .memory
+            .add_relocation_rule(temp, (0, 6).into())
+            .unwrap();
+        segments.memory.relocate_memory().unwrap();
+        assert_eq!(segments.memory.allocated_cells, 7);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn allocated_cells_relocating_uncounted_temporary_cells() {
+        let mut memory = Memory::new();
+        memory.data.push(Vec::new());
+        // Filled directly, so these cells were never counted
+        memory.temp_data = vec![vec![None, Some(MemoryCell::new(mayberelocatable!(8)))]];
+        memory
+            .add_relocation_rule((-1, 0).into(), (0, 0).into())
+            .unwrap();
+        assert_eq!(memory.relocate_memory(), Ok(()));
+        assert_eq!(memory.allocated_cells, 2);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn insert_no_limits_by_default() {
+        let mut memory = Memory::new();
+        assert_eq!(memory.limits, MemoryLimits::default());
+        memory.data.push(Vec::new());
+        memory
+            .insert((0, 100000).into(), &mayberelocatable!(1))
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn insert_into_read_only_segment() {
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        segments.add();
+        segments
+            .memory
+            .insert((0, 0).into(), &mayberelocatable!(1))
+            .unwrap();
+        segments
+            .memory
+            .insert((0, 2).into(), &mayberelocatable!(3))
+            .unwrap();
+        segments.mark_read_only(0);
+        // Writing the value a cell already holds doesn't modify the segment
+        segments
+            .memory
+            .insert((0, 0).into(), &mayberelocatable!(1))
+            .unwrap();
+        // Existing cells can't be changed anyway
+        assert_matches!(
+            segments.memory.insert((0, 0).into(), &mayberelocatable!(2)),
+            Err(MemoryError::InconsistentMemory(_))
+        );
+        assert_matches!(
+            segments.memory.insert((0, 1).into(), &mayberelocatable!(1)),
+            Err(MemoryError::WriteToReadOnlySegment(bx)) if *bx == (0, 1).into()
+        );
+        assert_matches!(
+            segments.memory.insert((0, 3).into(), &mayberelocatable!(1)),
+            Err(MemoryError::WriteToReadOnlySegment(bx)) if *bx == (0, 3).into()
+        );
+        assert_eq!(segments.memory.data[0].len(), 3);
+        assert_eq!(segments.memory.data[0][1], None);
+        // Other segments are still writable
+        segments
+            .memory
+            .insert((1, 0).into(), &mayberelocatable!(2))
+            .unwrap();
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_valuef_from_temp_segment() {
@@ -1320,7 +1549,7 @@ mod memory_tests {
             Err(MemoryError::InconsistentMemory(Box::new((
                 (1, 0).into(),
                 (1, 1).into(),
-                7.into(),
+                mayberelocatable!(7),
             ))))
         );
     }
@@ -1538,6 +1767,67 @@ mod memory_tests {
         assert_eq!(memory.get_amount_of_accessed_addresses_for_segment(1), None);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_segment_skips_gaps() {
+        let memory = memory![((0, 0), 1), ((1, 1), 2), ((1, 4), (0, 0)), ((1, 5), 3)];
+        assert_eq!(
+            memory.iter_segment(1).collect::<Vec<_>>(),
+            vec![
+                (relocatable!(1, 1), &mayberelocatable!(2)),
+                (relocatable!(1, 4), &mayberelocatable!(0, 0)),
+                (relocatable!(1, 5), &mayberelocatable!(3)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_segment_temporary_segment() {
+        let memory = memory![((0, 0), 1), ((-1, 0), 2), ((-2, 1), 3)];
+        assert_eq!(
+            memory.iter_segment(-2).collect::<Vec<_>>(),
+            vec![(relocatable!(-2, 1), &mayberelocatable!(3))]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_segment_missing_segment() {
+        let memory = memory![((0, 0), 1), ((-1, 0), 2)];
+        assert_eq!(memory.iter_segment(1).count(), 0);
+        assert_eq!(memory.iter_segment(-2).count(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_all_without_temporary_segments() {
+        let memory = memory![((1, 0), 4), ((0, 2), 1), ((-1, 0), 2), ((0, 0), 3)];
+        assert_eq!(
+            memory.iter_all(false).collect::<Vec<_>>(),
+            vec![
+                (relocatable!(0, 0), &mayberelocatable!(3)),
+                (relocatable!(0, 2), &mayberelocatable!(1)),
+                (relocatable!(1, 0), &mayberelocatable!(4)),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn iter_all_with_temporary_segments() {
+        let memory = memory![((1, 0), 4), ((0, 0), 3), ((-2, 0), 5), ((-1, 1), 2)];
+        assert_eq!(
+            memory.iter_all(true).collect::<Vec<_>>(),
+            vec![
+                (relocatable!(-1, 1), &mayberelocatable!(2)),
+                (relocatable!(-2, 0), &mayberelocatable!(5)),
+                (relocatable!(0, 0), &mayberelocatable!(3)),
+                (relocatable!(1, 0), &mayberelocatable!(4)),
+            ]
+        );
+    }
+
     #[test]
     fn memory_cell_new_is_not_accessed() {
         let cell = MemoryCell::new(mayberelocatable!(1));
@@ -1635,9 +1925,9 @@ mod memory_tests {
         assert_eq!(
             CairoPieMemory::from(&memory),
             CairoPieMemory(vec![
-                ((1, 2), MaybeRelocatable::from(5)),
+                ((1, 2), mayberelocatable!(5)),
                 ((7, 6), MaybeRelocatable::from((1, 2))),
-                ((8, 9), MaybeRelocatable::from(3))
+                ((8, 9), mayberelocatable!(3))
             ])
         )
     }
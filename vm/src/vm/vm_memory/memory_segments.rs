@@ -6,6 +6,7 @@ use num_traits::Zero;
 
 use crate::stdlib::prelude::*;
 use crate::stdlib::{any::Any, collections::HashMap};
+use crate::utils::felt_from_short_string;
 use crate::vm::runners::cairo_runner::CairoArg;
 
 use crate::{
@@ -16,7 +17,7 @@ use crate::{
     },
 };
 
-use super::memory::MemoryCell;
+use super::memory::{MemoryCell, MemoryLimits};
 
 pub struct MemorySegmentManager {
     pub segment_sizes: HashMap<usize, usize>,
@@ -43,6 +44,18 @@ impl MemorySegmentManager {
         self.memory.temp_data.len()
     }
 
+    /// Sets the memory limits. Segment counts are checked by [`Self::try_add`] and
+    /// [`Self::try_add_temporary_segment`], sizes whenever a write grows a segment.
+    pub fn set_memory_limits(&mut self, limits: MemoryLimits) {
+        self.memory.limits = limits;
+    }
+
+    /// Marks a segment as read-only: from then on, any write that would add a cell to it (growing
+    /// it or filling one of its gaps) fails with `MemoryError::WriteToReadOnlySegment`.
+    pub fn mark_read_only(&mut self, index: usize) {
+        self.memory.read_only_segments.insert(index);
+    }
+
     ///Adds a new segment and returns its starting location as a Relocatable value. Its segment index will always be positive.
     pub fn add(&mut self) -> Relocatable {
         self.memory.data.push(Vec::new());
@@ -62,6 +75,30 @@ impl MemorySegmentManager {
         }
     }
 
+    /// Same as [`Self::add`], but fails with `MemoryError::SegmentCountLimitExceeded` instead of
+    /// adding a segment past `MemoryLimits::max_segments`.
+    pub fn try_add(&mut self) -> Result<Relocatable, MemoryError> {
+        self.check_segment_count(self.num_segments())?;
+        Ok(self.add())
+    }
+
+    /// Same as [`Self::add_temporary_segment`], but fails with
+    /// `MemoryError::SegmentCountLimitExceeded` instead of adding a temporary segment past
+    /// `MemoryLimits::max_segments`.
+    pub fn try_add_temporary_segment(&mut self) -> Result<Relocatable, MemoryError> {
+        self.check_segment_count(self.num_temp_segments())?;
+        Ok(self.add_temporary_segment())
+    }
+
+    fn check_segment_count(&self, num_segments: usize) -> Result<(), MemoryError> {
+        match self.memory.limits.max_segments {
+            Some(max_segments) if num_segments >= max_segments => {
+                Err(MemoryError::SegmentCountLimitExceeded(max_segments))
+            }
+            _ => Ok(()),
+        }
+    }
+
     ///Writes data into the memory from address ptr and returns the first address after the data.
     pub fn load_data(
         &mut self,
@@ -106,24 +143,37 @@ impl MemorySegmentManager {
             .or_else(|| self.get_segment_used_size(index))
     }
 
-    ///Returns a vector containing the first relocated address of each memory segment
+    ///Returns a vector containing the first relocated address of each memory segment.
+    ///Segments are laid out contiguously in index order, each one spanning its finalized size
+    ///if it has one, or its used size otherwise (zero-sized segments share their base with the
+    ///next segment).
+    ///Temporary segments don't get a base of their own: by now they must have been merged into
+    ///real segments through their relocation rules.
     pub fn relocate_segments(&self) -> Result<Vec<usize>, MemoryError> {
-        let first_addr = 1;
-        let mut relocation_table = vec![first_addr];
-        match &self.segment_used_sizes {
-            Some(segment_used_sizes) => {
-                for (i, _size) in segment_used_sizes.iter().enumerate() {
-                    let segment_size = self
-                        .get_segment_size(i)
-                        .ok_or(MemoryError::MissingSegmentUsedSizes)?;
-
-                    relocation_table.push(relocation_table[i] + segment_size);
-                }
-            }
-            None => return Err(MemoryError::MissingSegmentUsedSizes),
+        let segment_used_sizes = self
+            .segment_used_sizes
+            .as_ref()
+            .ok_or(MemoryError::MissingSegmentUsedSizes)?;
+        if let Some(index) = self
+            .memory
+            .temp_data
+            .iter()
+            .position(|segment| !segment.is_empty())
+        {
+            return Err(MemoryError::TemporarySegmentInRelocation(
+                -(index as isize) - 1,
+            ));
+        }
+        let num_segments = max(self.num_segments(), segment_used_sizes.len());
+        let mut relocation_table = Vec::with_capacity(num_segments);
+        let mut next_addr = 1;
+        for index in 0..num_segments {
+            let segment_size = self
+                .get_segment_size(index)
+                .ok_or(MemoryError::SegmentNotFinalized(index))?;
+            relocation_table.push(next_addr);
+            next_addr += segment_size;
         }
-        //The last value corresponds to the total amount of elements across all segments, which isnt needed for relocation.
-        relocation_table.pop();
         Ok(relocation_table)
     }
 
@@ -138,6 +188,10 @@ impl MemorySegmentManager {
             let base = self.add();
             self.write_arg(base, value)?;
             Ok(base.into())
+        } else if let Some(value) = arg.downcast_ref::<&str>() {
+            Ok(felt_from_short_string(value)?.into())
+        } else if let Some(value) = arg.downcast_ref::<String>() {
+            Ok(felt_from_short_string(value)?.into())
         } else {
             Err(MemoryError::GenArgInvalidType)
         }
@@ -202,6 +256,37 @@ impl MemorySegmentManager {
         &self,
         builtin_count: usize,
         has_output_builtin: bool,
+    ) -> Result<usize, MemoryError> {
+        self.count_memory_holes(builtin_count, has_output_builtin, |i| {
+            self.get_segment_size(i)
+        })
+    }
+
+    /// Same as `get_memory_holes`, but if the segment used sizes haven't been computed yet,
+    /// the effective size of each segment is taken straight from memory instead of failing
+    /// with `MissingSegmentUsedSizes`. The result is the same as calling
+    /// `compute_effective_sizes` beforehand, without needing mutable access.
+    pub fn get_memory_holes_with_effective_sizes(
+        &self,
+        builtin_count: usize,
+        has_output_builtin: bool,
+    ) -> Result<usize, MemoryError> {
+        if self.segment_used_sizes.is_some() {
+            return self.get_memory_holes(builtin_count, has_output_builtin);
+        }
+        self.count_memory_holes(builtin_count, has_output_builtin, |i| {
+            self.segment_sizes
+                .get(&i)
+                .cloned()
+                .or_else(|| self.memory.data.get(i).map(Vec::len))
+        })
+    }
+
+    fn count_memory_holes(
+        &self,
+        builtin_count: usize,
+        has_output_builtin: bool,
+        segment_size: impl Fn(usize) -> Option<usize>,
     ) -> Result<usize, MemoryError> {
         let data = &self.memory.data;
         let mut memory_holes = 0;
@@ -229,9 +314,7 @@ impl MemorySegmentManager {
                         _ => continue,
                     }
                 };
-            let segment_size = self
-                .get_segment_size(i)
-                .ok_or(MemoryError::MissingSegmentUsedSizes)?;
+            let segment_size = segment_size(i).ok_or(MemoryError::MissingSegmentUsedSizes)?;
             if accessed_amount > segment_size {
                 return Err(MemoryError::SegmentHasMoreAccessedAddressesThanSize(
                     Box::new((i, accessed_amount, segment_size)),
@@ -296,7 +379,8 @@ impl MemorySegmentManager {
             // As zero_segment_index is only accessible to the segment manager
             // we can asume that it is always valid and index direcly into it
             self.memory.data[self.zero_segment_index]
-                .push(Some(MemoryCell::new(Felt252::ZERO.into())))
+                .push(Some(MemoryCell::new(Felt252::ZERO.into())));
+            self.memory.allocated_cells += 1;
         }
         self.zero_segment_size = max(self.zero_segment_size, size);
         self.zero_segment_index
@@ -348,6 +432,8 @@ mod tests {
     use crate::Felt252;
     use crate::{relocatable, utils::test_utils::*, vm::vm_memory::memory::MemoryCell};
     use assert_matches::assert_matches;
+    #[cfg(not(target_arch = "wasm32"))]
+    use proptest::prelude::*;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::*;
@@ -577,6 +663,101 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_segments_zero_size_segment_in_between() {
+        let mut segments = MemorySegmentManager::new();
+        segments.segment_used_sizes = Some(vec![3, 0, 5, 0, 2]);
+        assert_eq!(segments.relocate_segments(), Ok(vec![1, 4, 4, 9, 9]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_segments_finalized_larger_than_used() {
+        let mut segments = MemorySegmentManager::new();
+        segments.segment_used_sizes = Some(vec![3, 2, 5]);
+        segments.finalize(Some(10), 1, None);
+        assert_eq!(segments.relocate_segments(), Ok(vec![1, 4, 14]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_segments_missing_used_sizes() {
+        let segments = MemorySegmentManager::new();
+        assert_eq!(
+            segments.relocate_segments(),
+            Err(MemoryError::MissingSegmentUsedSizes)
+        );
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_segments_segment_added_after_computing_sizes() {
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        segments.add();
+        segments.compute_effective_sizes();
+        segments.add();
+        assert_eq!(
+            segments.relocate_segments(),
+            Err(MemoryError::SegmentNotFinalized(2))
+        );
+        // A finalized size is enough to relocate it
+        segments.finalize(Some(4), 2, None);
+        assert_eq!(segments.relocate_segments(), Ok(vec![1, 1, 1]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_segments_skips_empty_temporary_segments() {
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        segments.add_temporary_segment();
+        segments.memory = memory![((0, 0), 1), ((0, 1), 2)];
+        segments.memory.temp_data = vec![vec![]];
+        segments.compute_effective_sizes();
+        assert_eq!(segments.relocate_segments(), Ok(vec![1]));
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn relocate_segments_temporary_segment_without_relocation_rule() {
+        let mut segments = MemorySegmentManager::new();
+        segments.add();
+        segments.add_temporary_segment();
+        segments.add_temporary_segment();
+        segments.memory = memory![((0, 0), 1), ((0, 1), 2), ((-2, 0), 7)];
+        segments.compute_effective_sizes();
+        assert_eq!(
+            segments.relocate_segments(),
+            Err(MemoryError::TemporarySegmentInRelocation(-2))
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    proptest! {
+        #[test]
+        fn relocate_segments_bases_follow_declared_sizes(
+            sizes in proptest::collection::vec((0..50_usize, proptest::option::of(0..20_usize)), 0..20)
+        ) {
+            let mut segments = MemorySegmentManager::new();
+            segments.segment_used_sizes = Some(sizes.iter().map(|(used, _)| *used).collect());
+            for (index, (used, extra)) in sizes.iter().enumerate() {
+                if let Some(extra) = extra {
+                    segments.finalize(Some(used + extra), index, None);
+                }
+            }
+            let relocation_table = segments.relocate_segments().unwrap();
+            prop_assert_eq!(relocation_table.len(), sizes.len());
+            prop_assert!(relocation_table.first().map_or(true, |base| *base == 1));
+            for (index, bases) in relocation_table.windows(2).enumerate() {
+                let (used, extra) = sizes[index];
+                prop_assert!(bases[0] <= bases[1]);
+                prop_assert_eq!(bases[1] - bases[0], used + extra.unwrap_or_default());
+            }
+        }
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn write_arg_relocatable() {
@@ -738,6 +919,30 @@ mod tests {
         assert_eq!(memory_segment_manager.get_memory_holes(0, false), Ok(2),);
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn get_memory_holes_with_effective_sizes_without_used_sizes() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+        memory_segment_manager.memory = memory![((0, 0), 0), ((0, 1), 0), ((0, 3), 0)];
+        for i in [0, 3] {
+            memory_segment_manager
+                .memory
+                .mark_as_accessed((0, i).into());
+        }
+        assert_eq!(
+            memory_segment_manager.get_memory_holes_with_effective_sizes(0, false),
+            Ok(2)
+        );
+        // Sizes are not stored, and computing them afterwards yields the same count
+        assert_eq!(memory_segment_manager.segment_used_sizes, None);
+        memory_segment_manager.compute_effective_sizes();
+        assert_eq!(memory_segment_manager.get_memory_holes(0, false), Ok(2));
+        assert_eq!(
+            memory_segment_manager.get_memory_holes_with_effective_sizes(0, false),
+            Ok(2)
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn get_memory_holes2() {
@@ -799,6 +1004,59 @@ mod tests {
         assert_eq!(memory_segment_manager.get_segment_size(0), Some(5));
     }
 
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn try_add_segment_count_limit_exceeded() {
+        let mut segments = MemorySegmentManager::new();
+        segments.set_memory_limits(MemoryLimits {
+            max_segments: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(segments.try_add(), Ok(relocatable!(0, 0)));
+        assert_eq!(segments.try_add(), Ok(relocatable!(1, 0)));
+        assert_eq!(
+            segments.try_add(),
+            Err(MemoryError::SegmentCountLimitExceeded(2))
+        );
+        assert_eq!(segments.num_segments(), 2);
+        // Temporary segments are counted separately
+        assert_eq!(
+            segments.try_add_temporary_segment(),
+            Ok(relocatable!(-1, 0))
+        );
+        assert_eq!(
+            segments.try_add_temporary_segment(),
+            Ok(relocatable!(-2, 0))
+        );
+        assert_eq!(
+            segments.try_add_temporary_segment(),
+            Err(MemoryError::SegmentCountLimitExceeded(2))
+        );
+        assert_eq!(segments.num_temp_segments(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn load_data_segment_size_limit_exceeded() {
+        let mut segments = MemorySegmentManager::new();
+        segments.set_memory_limits(MemoryLimits {
+            max_segment_size: Some(2),
+            ..Default::default()
+        });
+        let ptr = segments.add();
+        let data = vec![
+            mayberelocatable!(1),
+            mayberelocatable!(2),
+            mayberelocatable!(3),
+        ];
+        assert_matches!(
+            segments.load_data(ptr, &data),
+            Err(MemoryError::SegmentSizeLimitExceeded(bx)) if *bx == ((0, 2).into(), 2)
+        );
+        // The write past the limit is checked first, so nothing is written
+        assert!(segments.memory.data[0].is_empty());
+    }
+
     /// Test that the call to .gen_arg() with a relocatable just passes the
     /// value through.
     #[test]
@@ -877,11 +1135,40 @@ mod tests {
         let mut memory_segment_manager = MemorySegmentManager::new();
 
         assert_matches!(
-            memory_segment_manager.gen_arg(&""),
+            memory_segment_manager.gen_arg(&true),
             Err(MemoryError::GenArgInvalidType)
         );
     }
 
+    /// Test that the call to .gen_arg() with a string encodes it as a short
+    /// string felt.
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn gen_arg_short_string() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+
+        assert_matches!(
+            memory_segment_manager.gen_arg(&"hello"),
+            Ok(x) if x == mayberelocatable!(0x68656c6c6f)
+        );
+        assert_matches!(
+            memory_segment_manager.gen_arg(&String::from("hello")),
+            Ok(x) if x == mayberelocatable!(0x68656c6c6f)
+        );
+        assert_eq!(memory_segment_manager.num_segments(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    fn gen_arg_short_string_too_long() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+
+        assert_matches!(
+            memory_segment_manager.gen_arg(&"this string is longer than 31 bytes"),
+            Err(MemoryError::InvalidShortString(s)) if &*s == "this string is longer than 31 bytes"
+        );
+    }
+
     #[test]
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
     fn finalize_no_size_nor_memory() {